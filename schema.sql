@@ -21,6 +21,7 @@ CREATE TABLE IF NOT EXISTS channels (
     channel_id INTEGER NOT NULL UNIQUE,
     channel_name TEXT,
     guild_name TEXT,
+    roll_commands TEXT,
     added_at TEXT DEFAULT CURRENT_TIMESTAMP
 );
 
@@ -35,7 +36,62 @@ CREATE TABLE IF NOT EXISTS config (
     daily_time TEXT DEFAULT '00:00',
     wishlist_enabled INTEGER DEFAULT 1,
     fuzzy_match INTEGER DEFAULT 1,
-    fuzzy_threshold REAL DEFAULT 0.8
+    fuzzy_threshold REAL DEFAULT 0.8,
+    on_reconnect_commands TEXT DEFAULT '',
+    scheduled_commands TEXT DEFAULT '',
+    claim_priority_weight REAL DEFAULT 10000,
+    claim_kakera_weight REAL DEFAULT 1,
+    auto_idle INTEGER DEFAULT 0,
+    strict_wishlist_load INTEGER DEFAULT 0,
+    default_rolls INTEGER DEFAULT 10,
+    claim_gender_filter TEXT DEFAULT '',
+    claim_kakera_min INTEGER DEFAULT 0,
+    claim_kakera_max INTEGER DEFAULT 0,
+    show_user_messages INTEGER DEFAULT 1,
+    verify_delay_min_ms INTEGER DEFAULT 1500,
+    verify_delay_max_ms INTEGER DEFAULT 15000,
+    claim_rules TEXT DEFAULT 'wished',
+    roll_spread TEXT DEFAULT 'burst',
+    halt_key TEXT DEFAULT 'F9',
+    encrypt_token INTEGER DEFAULT 0,
+    attempt_claim_on_belongs_to INTEGER DEFAULT 0,
+    display_timezone TEXT DEFAULT 'local',
+    roll_command_cooldowns TEXT DEFAULT '',
+    catchup_messages INTEGER DEFAULT 0,
+    daily_commands TEXT DEFAULT '$daily,$dk',
+    kakera_power_min INTEGER DEFAULT 0,
+    claim_retries INTEGER DEFAULT 2,
+    claim_retry_window_ms INTEGER DEFAULT 5000,
+    auto_divorce_non_wished INTEGER DEFAULT 0,
+    auto_divorce_max_kakera INTEGER DEFAULT 100,
+    auto_divorce_commands TEXT DEFAULT '$divorce {name},y',
+    stats_save_interval_seconds INTEGER DEFAULT 60,
+    tui_tick_ms INTEGER DEFAULT 100,
+    mudae_language TEXT DEFAULT 'en',
+    ascii_mode INTEGER DEFAULT 0,
+    auto_verify_on_start INTEGER DEFAULT 0,
+    daily_kakera_react INTEGER DEFAULT 0,
+    mark_wished_emoji TEXT DEFAULT '',
+    trust_mudae_wish INTEGER DEFAULT 1,
+    startup_roll_delay_seconds INTEGER DEFAULT 5,
+    post_reset_roll_delay_seconds INTEGER DEFAULT 10,
+    inter_channel_delay_ms INTEGER DEFAULT 2000,
+    shuffle_channels INTEGER DEFAULT 0,
+    claim_emoji TEXT DEFAULT '💖',
+    track_seen_characters INTEGER DEFAULT 0,
+    max_session_hours INTEGER DEFAULT 0,
+    session_limit_action TEXT DEFAULT 'stop',
+    include_threads INTEGER DEFAULT 0,
+    remote_control_user_ids TEXT DEFAULT '',
+    max_consecutive_failures INTEGER DEFAULT 5,
+    claim_confirm_timeout_seconds INTEGER DEFAULT 15,
+    fuzzy_dedup INTEGER DEFAULT 0,
+    roll_command_yields TEXT DEFAULT '',
+    channel_refresh_minutes INTEGER DEFAULT 0,
+    auth_error_halt_count INTEGER DEFAULT 5,
+    claim_grace_seconds INTEGER DEFAULT 0,
+    delay_distribution TEXT DEFAULT 'uniform',
+    include_dms INTEGER DEFAULT 0
 );
 
 -- Runtime statistics
@@ -47,9 +103,36 @@ CREATE TABLE IF NOT EXISTS stats (
     kakera_collected INTEGER DEFAULT 0,
     rolls_executed INTEGER DEFAULT 0,
     total_uptime_seconds INTEGER DEFAULT 0,
+    kakera_received INTEGER DEFAULT 0,
     updated_at TEXT DEFAULT CURRENT_TIMESTAMP
 );
 
+-- Claimed characters ledger
+CREATE TABLE IF NOT EXISTS claims (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    character_name TEXT NOT NULL,
+    series TEXT,
+    kakera_value INTEGER,
+    channel_id INTEGER NOT NULL,
+    claimed_at TEXT NOT NULL
+);
+
+-- Characters owned according to the last harem sync
+CREATE TABLE IF NOT EXISTS owned_characters (
+    name TEXT PRIMARY KEY COLLATE NOCASE,
+    synced_at TEXT NOT NULL
+);
+
+-- Every distinct character seen in a roll
+CREATE TABLE IF NOT EXISTS seen_characters (
+    name TEXT NOT NULL COLLATE NOCASE,
+    series TEXT NOT NULL COLLATE NOCASE,
+    first_seen TEXT NOT NULL,
+    last_seen TEXT NOT NULL,
+    roll_count INTEGER NOT NULL DEFAULT 1,
+    PRIMARY KEY (name, series)
+);
+
 -- Initialize singleton rows
 INSERT OR IGNORE INTO config (id) VALUES (1);
 INSERT OR IGNORE INTO stats (id) VALUES (1);
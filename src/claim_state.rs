@@ -0,0 +1,130 @@
+use chrono::{DateTime, Duration, Utc};
+
+pub const RESET_DRIFT_SECS: i64 = 30;
+pub const FALLBACK_CLAIM_INTERVAL_MINS: i64 = 180;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ClaimReport {
+    available: bool,
+    reported_at: DateTime<Utc>,
+    reset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClaimState {
+    report: Option<ClaimReport>,
+    last_claim_at: Option<DateTime<Utc>>,
+}
+
+impl ClaimState {
+    pub fn record_report(&mut self, available: bool, reset_at: Option<DateTime<Utc>>, now: DateTime<Utc>) {
+        self.report = Some(ClaimReport { available, reported_at: now, reset_at });
+    }
+
+    pub fn record_claim(&mut self, now: DateTime<Utc>) {
+        self.last_claim_at = Some(now);
+    }
+
+    pub fn is_available(&self, now: DateTime<Utc>) -> bool {
+        self.available_at(now).is_none()
+    }
+
+    pub fn available_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.blocked_until().filter(|until| *until > now)
+    }
+
+    fn blocked_until(&self) -> Option<DateTime<Utc>> {
+        let drift = Duration::seconds(RESET_DRIFT_SECS);
+        let fallback = Duration::minutes(FALLBACK_CLAIM_INTERVAL_MINS);
+
+        let claim_is_newest = match (self.last_claim_at, self.report) {
+            (Some(claimed_at), Some(report)) => claimed_at >= report.reported_at,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+
+        if claim_is_newest {
+            let claimed_at = self.last_claim_at?;
+            let known_reset = self.report.and_then(|r| r.reset_at).filter(|reset| *reset > claimed_at);
+            return Some(known_reset.unwrap_or(claimed_at + fallback) + drift);
+        }
+
+        let report = self.report?;
+        if report.available {
+            return None;
+        }
+        Some(report.reset_at.unwrap_or(report.reported_at + fallback) + drift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn test_available_without_any_information() {
+        let state = ClaimState::default();
+        assert!(state.is_available(at(0)));
+        assert_eq!(state.available_at(at(0)), None);
+    }
+
+    #[test]
+    fn test_cooldown_report_blocks_until_reset_plus_drift() {
+        let mut state = ClaimState::default();
+        state.record_report(false, Some(at(45)), at(0));
+
+        assert!(!state.is_available(at(44)));
+        assert!(!state.is_available(at(45)));
+        assert_eq!(state.available_at(at(0)), Some(at(45) + Duration::seconds(RESET_DRIFT_SECS)));
+        assert!(state.is_available(at(46)));
+    }
+
+    #[test]
+    fn test_cooldown_without_reset_falls_back_to_default_interval() {
+        let mut state = ClaimState::default();
+        state.record_report(false, None, at(0));
+
+        assert!(!state.is_available(at(FALLBACK_CLAIM_INTERVAL_MINS - 1)));
+        assert!(state.is_available(at(FALLBACK_CLAIM_INTERVAL_MINS + 1)));
+    }
+
+    #[test]
+    fn test_claim_after_available_report_waits_for_reported_reset() {
+        let mut state = ClaimState::default();
+        state.record_report(true, Some(at(90)), at(0));
+        assert!(state.is_available(at(10)));
+
+        state.record_claim(at(10));
+        assert!(!state.is_available(at(11)));
+        assert!(!state.is_available(at(89)));
+        assert!(state.is_available(at(91)));
+    }
+
+    #[test]
+    fn test_claim_after_stale_reset_uses_fallback_interval() {
+        let mut state = ClaimState::default();
+        state.record_report(true, Some(at(30)), at(0));
+
+        state.record_claim(at(60));
+        assert!(!state.is_available(at(61)));
+        assert!(state.is_available(at(60 + FALLBACK_CLAIM_INTERVAL_MINS + 1)));
+    }
+
+    #[test]
+    fn test_newer_report_overrides_earlier_claim() {
+        let mut state = ClaimState::default();
+        state.record_claim(at(0));
+        assert!(!state.is_available(at(5)));
+
+        state.record_report(true, None, at(5));
+        assert!(state.is_available(at(5)));
+
+        state.record_report(false, Some(at(20)), at(6));
+        assert!(!state.is_available(at(7)));
+        assert!(state.is_available(at(21)));
+    }
+}
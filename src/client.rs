@@ -1,12 +1,19 @@
+use crate::database::Database;
 use crate::stats::{ConnectionStatus, EventType, Stats};
 use anyhow::{Context as AnyhowContext, Result};
 use serenity_self::async_trait;
 use serenity_self::client::Context;
-use serenity_self::http::Http;
+use serenity_self::gateway::{ConnectionStage, ShardStageUpdateEvent};
+use serenity_self::http::{Http, HttpError};
 use serenity_self::model::channel::{Channel, Message, Reaction};
+use serenity_self::model::event::ResumedEvent;
 use serenity_self::model::gateway::Ready;
 use serenity_self::model::id::{ChannelId, GuildId, MessageId};
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, warn};
 
@@ -16,6 +23,7 @@ pub enum GatewayEvent {
     MessageCreate(DiscordMessage),
     MessageUpdate(DiscordMessage),
     ReactionAdd { message_id: u64, channel_id: u64, user_id: u64, emoji: String },
+    Reconnected { resumed: bool },
     Unknown(String),
 }
 
@@ -23,10 +31,20 @@ pub enum GatewayEvent {
 pub struct DiscordMessage {
     pub id: u64,
     pub channel_id: u64,
+    pub parent_channel_id: Option<u64>,
+    pub guild_id: Option<u64>,
     pub author: Author,
     pub content: String,
     pub embeds: Vec<Embed>,
     pub components: Vec<Component>,
+    pub webhook_id: Option<u64>,
+    pub message_type: u8,
+}
+
+impl DiscordMessage {
+    pub fn is_webhook_or_system(&self) -> bool {
+        self.webhook_id.is_some() || !matches!(self.message_type, 0 | 19 | 20 | 23)
+    }
 }
 
 impl From<&Message> for DiscordMessage {
@@ -61,6 +79,8 @@ impl From<&Message> for DiscordMessage {
         Self {
             id: msg.id.get(),
             channel_id: msg.channel_id.get(),
+            parent_channel_id: None,
+            guild_id: msg.guild_id.map(|g| g.get()),
             author: Author {
                 id: msg.author.id.get(),
                 username: msg.author.name.clone(),
@@ -69,6 +89,8 @@ impl From<&Message> for DiscordMessage {
             content: msg.content.clone(),
             embeds: msg.embeds.iter().map(|e| e.into()).collect(),
             components,
+            webhook_id: msg.webhook_id.map(|w| w.get()),
+            message_type: u8::from(msg.kind),
         }
     }
 }
@@ -161,11 +183,174 @@ pub struct ButtonEmoji {
     pub id: Option<String>,
 }
 
+#[async_trait]
+pub trait MessageSender: Send + Sync {
+    async fn send_message(&self, channel_id: u64, content: &str) -> Result<()>;
+
+    async fn add_reaction(&self, channel_id: u64, message_id: u64, emoji: &str) -> Result<()>;
+
+    async fn click_button(
+        &self,
+        message_id: u64,
+        channel_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
+        custom_id: &str,
+    ) -> Result<()>;
+
+    async fn get_messages(&self, channel_id: u64, limit: u8) -> Result<Vec<DiscordMessage>>;
+}
+
+#[async_trait]
+impl MessageSender for DiscordClient {
+    async fn send_message(&self, channel_id: u64, content: &str) -> Result<()> {
+        Ok(DiscordClient::send_message(self, channel_id, content).await?)
+    }
+
+    async fn add_reaction(&self, channel_id: u64, message_id: u64, emoji: &str) -> Result<()> {
+        Ok(DiscordClient::add_reaction(self, channel_id, message_id, emoji).await?)
+    }
+
+    async fn click_button(
+        &self,
+        message_id: u64,
+        channel_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
+        custom_id: &str,
+    ) -> Result<()> {
+        Ok(DiscordClient::click_button(self, message_id, channel_id, guild_id, application_id, custom_id).await?)
+    }
+
+    async fn get_messages(&self, channel_id: u64, limit: u8) -> Result<Vec<DiscordMessage>> {
+        Ok(DiscordClient::get_messages(self, channel_id, limit).await?)
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    RateLimited { retry_after: Option<Duration> },
+    Unauthorized,
+    Forbidden { channel_id: Option<u64> },
+    NotFound { channel_id: Option<u64> },
+    Other(anyhow::Error),
+}
+
+impl ClientError {
+    pub fn from_status(status: u16, channel_id: Option<u64>, retry_after: Option<Duration>) -> Option<Self> {
+        match status {
+            401 => Some(ClientError::Unauthorized),
+            403 => Some(ClientError::Forbidden { channel_id }),
+            404 => Some(ClientError::NotFound { channel_id }),
+            429 => Some(ClientError::RateLimited { retry_after }),
+            _ => None,
+        }
+    }
+
+    fn from_serenity(error: serenity_self::Error, channel_id: Option<u64>, action: &'static str) -> Self {
+        http_status(&error)
+            .and_then(|status| Self::from_status(status, channel_id, None))
+            .unwrap_or_else(|| ClientError::Other(anyhow::Error::new(error).context(action)))
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::RateLimited { .. } => Some(429),
+            ClientError::Unauthorized => Some(401),
+            ClientError::Forbidden { .. } => Some(403),
+            ClientError::NotFound { .. } => Some(404),
+            ClientError::Other(_) => None,
+        }
+    }
+
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, ClientError::Unauthorized | ClientError::Forbidden { .. } | ClientError::NotFound { .. })
+    }
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::RateLimited { retry_after: Some(wait) } => {
+                write!(f, "Rate limited by Discord, retry after {:.1}s", wait.as_secs_f64())
+            }
+            ClientError::RateLimited { retry_after: None } => write!(f, "Rate limited by Discord"),
+            ClientError::Unauthorized => write!(f, "Discord rejected the token (HTTP 401)"),
+            ClientError::Forbidden { channel_id: Some(id) } => write!(f, "No access to channel {} (HTTP 403)", id),
+            ClientError::Forbidden { channel_id: None } => write!(f, "Discord refused the request (HTTP 403)"),
+            ClientError::NotFound { channel_id: Some(id) } => write!(f, "No access to channel {} (HTTP 404)", id),
+            ClientError::NotFound { channel_id: None } => write!(f, "Not found on Discord (HTTP 404)"),
+            ClientError::Other(e) => write!(f, "{:#}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+pub fn client_error(error: &anyhow::Error) -> Option<&ClientError> {
+    error.downcast_ref::<ClientError>()
+}
+
+pub fn is_channel_access_error(error: &anyhow::Error) -> bool {
+    matches!(
+        client_error(error),
+        Some(ClientError::Forbidden { channel_id: Some(_) } | ClientError::NotFound { channel_id: Some(_) })
+    )
+}
+
+pub const AUTH_ERROR_WINDOW_SECS: u64 = 120;
+
+const ACCOUNT_ERROR_CODES: [i64; 2] = [40001, 40002];
+
+pub fn is_account_error(status: u16, code: Option<i64>) -> bool {
+    status == 401 || (status == 403 && code.is_some_and(|code| ACCOUNT_ERROR_CODES.contains(&code)))
+}
+
+fn discord_error_code(error: &serenity_self::Error) -> Option<i64> {
+    match error {
+        serenity_self::Error::Http(HttpError::UnsuccessfulRequest(response)) => Some(response.error.code as i64),
+        _ => None,
+    }
+}
+
+fn http_status(error: &serenity_self::Error) -> Option<u16> {
+    match error {
+        serenity_self::Error::Http(http_error) => http_error.status_code().map(|status| status.as_u16()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct AuthErrorTracker {
+    threshold: u32,
+    errors: VecDeque<Instant>,
+}
+
+impl AuthErrorTracker {
+    fn record(&mut self, now: Instant) -> bool {
+        if self.threshold == 0 {
+            return false;
+        }
+        let window = Duration::from_secs(AUTH_ERROR_WINDOW_SECS);
+        while self.errors.front().is_some_and(|at| now.duration_since(*at) > window) {
+            self.errors.pop_front();
+        }
+        self.errors.push_back(now);
+        if self.errors.len() < self.threshold as usize {
+            return false;
+        }
+        self.errors.clear();
+        true
+    }
+}
+
 #[derive(Clone)]
 pub struct DiscordClient {
     http: Arc<Http>,
     token: String,
     stats: Option<Arc<Stats>>,
+    auth_errors: Arc<Mutex<AuthErrorTracker>>,
+    halt_marker: PathBuf,
 }
 
 impl DiscordClient {
@@ -175,6 +360,8 @@ impl DiscordClient {
             http,
             token,
             stats: None,
+            auth_errors: Arc::new(Mutex::new(AuthErrorTracker::default())),
+            halt_marker: Database::halt_marker_path(),
         }
     }
 
@@ -183,6 +370,11 @@ impl DiscordClient {
         self
     }
 
+    pub fn with_auth_error_halt(self, threshold: u32) -> Self {
+        self.auth_errors.lock().unwrap().threshold = threshold;
+        self
+    }
+
     pub fn http(&self) -> Arc<Http> {
         self.http.clone()
     }
@@ -199,19 +391,61 @@ impl DiscordClient {
         }
     }
 
-    pub async fn send_message(&self, channel_id: u64, content: &str) -> Result<()> {
+    async fn classify(&self, error: serenity_self::Error, channel_id: Option<u64>, action: &'static str) -> ClientError {
+        let code = discord_error_code(&error);
+        let error = ClientError::from_serenity(error, channel_id, action);
+        if let Some(status) = error.status() {
+            self.record_status(status, code).await;
+        }
+        error
+    }
+
+    async fn record_status(&self, status: u16, code: Option<i64>) {
+        if !is_account_error(status, code) {
+            return;
+        }
+        let tripped = self.auth_errors.lock().unwrap().record(Instant::now());
+        let Some(ref stats) = self.stats else {
+            return;
+        };
+        if !tripped || stats.is_halted() {
+            return;
+        }
+        if let Err(e) = stats.halt_for_account_alert(&self.halt_marker) {
+            warn!("Failed to write halt marker {}: {}", self.halt_marker.display(), e);
+        }
+        let threshold = self.auth_errors.lock().unwrap().threshold;
+        self.log_event(
+            EventType::Error,
+            format!(
+                "HALTED: {} Discord auth errors (last HTTP {}) within {}s. The account may be flagged or locked, check it in Discord before clearing the halt",
+                threshold, status, AUTH_ERROR_WINDOW_SECS
+            ),
+        ).await;
+    }
+
+    fn ensure_not_halted(&self) -> Result<(), ClientError> {
+        if self.stats.as_ref().is_some_and(|stats| stats.is_halted()) {
+            return Err(ClientError::Other(anyhow::anyhow!("Outbound actions are halted")));
+        }
+        Ok(())
+    }
+
+    pub async fn send_message(&self, channel_id: u64, content: &str) -> Result<(), ClientError> {
+        self.ensure_not_halted()?;
         let channel_id = ChannelId::new(channel_id);
-        channel_id
-            .say(&self.http, content)
-            .await
-            .context("Failed to send message")?;
+        if let Err(e) = channel_id.say(&self.http, content).await {
+            return Err(self.classify(e, Some(channel_id.get()), "Failed to send message").await);
+        }
 
         debug!("Sent message to channel {}: {}", channel_id.get(), content);
         Ok(())
     }
 
-    pub async fn add_reaction(&self, channel_id: u64, message_id: u64, emoji: &str) -> Result<()> {
+    pub async fn add_reaction(&self, channel_id: u64, message_id: u64, emoji: &str) -> Result<(), ClientError> {
         use serenity_self::model::channel::ReactionType;
+
+        self.ensure_not_halted()?;
         
         let channel_id = ChannelId::new(channel_id);
         let message_id = MessageId::new(message_id);
@@ -222,10 +456,9 @@ impl DiscordClient {
             ReactionType::Unicode(emoji.to_string())
         };
         
-        channel_id
-            .create_reaction(&self.http, message_id, reaction_type)
-            .await
-            .context("Failed to add reaction")?;
+        if let Err(e) = channel_id.create_reaction(&self.http, message_id, reaction_type).await {
+            return Err(self.classify(e, Some(channel_id.get()), "Failed to add reaction").await);
+        }
 
         debug!("Added reaction {} to message {}", emoji, message_id.get());
         Ok(())
@@ -238,9 +471,11 @@ impl DiscordClient {
         guild_id: Option<u64>,
         application_id: u64,
         custom_id: &str,
-    ) -> Result<()> {
+    ) -> Result<(), ClientError> {
         use serde_json::json;
 
+        self.ensure_not_halted()?;
+
         let url = "https://discord.com/api/v10/interactions";
         let nonce = format!("{}", rand::random::<u64>());
 
@@ -268,24 +503,38 @@ impl DiscordClient {
             .json(&payload)
             .send()
             .await
-            .context("Failed to send button click request")?;
+            .context("Failed to send button click request")
+            .map_err(ClientError::Other)?;
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<f64>().ok())
+                .map(Duration::from_secs_f64);
             let text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to click button: {} - {}", status, text);
+            let code = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|body| body["code"].as_i64());
+            self.record_status(status.as_u16(), code).await;
+            if let Some(error) = ClientError::from_status(status.as_u16(), Some(channel_id), retry_after) {
+                return Err(error);
+            }
+            return Err(ClientError::Other(anyhow::anyhow!("Failed to click button: {} - {}", status, text)));
         }
 
         debug!("Clicked button {} on message {}", custom_id, message_id);
         Ok(())
     }
 
-    pub async fn get_channel(&self, channel_id: u64) -> Result<ChannelResponse> {
+    pub async fn get_channel(&self, channel_id: u64) -> Result<ChannelResponse, ClientError> {
         let channel_id = ChannelId::new(channel_id);
-        let channel = channel_id
-            .to_channel(&self.http)
-            .await
-            .context("Failed to get channel")?;
+        let channel = match channel_id.to_channel(&self.http).await {
+            Ok(channel) => channel,
+            Err(e) => return Err(self.classify(e, None, "Failed to get channel").await),
+        };
 
         let (name, guild_id) = match channel {
             Channel::Guild(ch) => (
@@ -306,12 +555,27 @@ impl DiscordClient {
         })
     }
 
-    pub async fn get_guild(&self, guild_id: u64) -> Result<GuildResponse> {
-        let guild_id = GuildId::new(guild_id);
-        let guild = guild_id
-            .to_partial_guild(&self.http)
+    pub async fn get_messages(&self, channel_id: u64, limit: u8) -> Result<Vec<DiscordMessage>, ClientError> {
+        use serenity_self::builder::GetMessages;
+
+        let messages = match ChannelId::new(channel_id)
+            .messages(&self.http, GetMessages::new().limit(limit.clamp(1, 100)))
             .await
-            .context("Failed to get guild")?;
+        {
+            Ok(messages) => messages,
+            Err(e) => return Err(self.classify(e, Some(channel_id), "Failed to fetch messages").await),
+        };
+
+        debug!("Fetched {} messages from channel {}", messages.len(), channel_id);
+        Ok(messages.iter().map(DiscordMessage::from).collect())
+    }
+
+    pub async fn get_guild(&self, guild_id: u64) -> Result<GuildResponse, ClientError> {
+        let guild_id = GuildId::new(guild_id);
+        let guild = match guild_id.to_partial_guild(&self.http).await {
+            Ok(guild) => guild,
+            Err(e) => return Err(self.classify(e, None, "Failed to get guild").await),
+        };
 
         Ok(GuildResponse {
             id: guild_id.get(),
@@ -319,11 +583,11 @@ impl DiscordClient {
         })
     }
 
-    pub async fn get_current_user(&self) -> Result<UserResponse> {
-        let user = self.http
-            .get_current_user()
-            .await
-            .context("Failed to get current user")?;
+    pub async fn get_current_user(&self) -> Result<UserResponse, ClientError> {
+        let user = match self.http.get_current_user().await {
+            Ok(user) => user,
+            Err(e) => return Err(self.classify(e, None, "Failed to get current user").await),
+        };
 
         Ok(UserResponse {
             id: user.id.get(),
@@ -355,14 +619,29 @@ pub struct GuildResponse {
     pub name: String,
 }
 
+fn thread_parent(ctx: &Context, msg: &Message) -> Option<u64> {
+    let guild = ctx.cache.guild(msg.guild_id?)?;
+    let thread = guild.threads.iter().find(|thread| thread.id == msg.channel_id)?;
+    thread.parent_id.map(|id| id.get())
+}
+
 pub struct EventHandler {
     event_tx: mpsc::Sender<GatewayEvent>,
     stats: Option<Arc<Stats>>,
+    connected_once: AtomicBool,
+    connection_lost: AtomicBool,
+    session_id: Mutex<Option<String>>,
 }
 
 impl EventHandler {
     pub fn new(event_tx: mpsc::Sender<GatewayEvent>, stats: Option<Arc<Stats>>) -> Self {
-        Self { event_tx, stats }
+        Self {
+            event_tx,
+            stats,
+            connected_once: AtomicBool::new(false),
+            connection_lost: AtomicBool::new(false),
+            session_id: Mutex::new(None),
+        }
     }
 
     async fn update_status(&self, status: ConnectionStatus) {
@@ -371,6 +650,52 @@ impl EventHandler {
         }
     }
 
+    async fn session_connected(&self, resumed: bool) {
+        let first_connect = !self.connected_once.swap(true, Ordering::SeqCst);
+        let was_lost = self.connection_lost.swap(false, Ordering::SeqCst);
+        self.update_status(ConnectionStatus::Connected).await;
+
+        if first_connect || !was_lost {
+            return;
+        }
+
+        if let Some(ref stats) = self.stats {
+            stats.increment_reconnects();
+        }
+        if resumed {
+            self.log_event(EventType::Success, "Reconnected to Discord (RESUME)".to_string()).await;
+        } else {
+            self.log_event(
+                EventType::Warning,
+                "Reconnected to Discord (new session READY), events during the gap were not replayed".to_string(),
+            ).await;
+        }
+        if let Err(e) = self.event_tx.send(GatewayEvent::Reconnected { resumed }).await {
+            warn!("Failed to send Reconnected event: {}", e);
+        }
+    }
+
+    fn replace_session(&self, session_id: String) -> Option<String> {
+        self.session_id.lock().unwrap().replace(session_id)
+    }
+
+    async fn transition_status(&self, status: ConnectionStatus) {
+        match status {
+            ConnectionStatus::Connected => {
+                self.update_status(status).await;
+            }
+            ConnectionStatus::Disconnected | ConnectionStatus::Reconnecting => {
+                if self.connected_once.load(Ordering::SeqCst) {
+                    self.connection_lost.store(true, Ordering::SeqCst);
+                }
+                self.update_status(status).await;
+            }
+            ConnectionStatus::Connecting => {
+                self.update_status(status).await;
+            }
+        }
+    }
+
     async fn log_event(&self, event_type: EventType, message: String) {
         if let Some(ref stats) = self.stats {
             stats.log_event(event_type, message).await;
@@ -386,8 +711,12 @@ impl serenity_self::client::EventHandler for EventHandler {
         let display_name = ready.user.global_name.clone().unwrap_or(username.clone());
         let session_id = ready.session_id.clone();
 
+        if let Some(previous) = self.replace_session(session_id.clone()) {
+            debug!("Gateway session replaced: {} -> {}", previous, session_id);
+        }
+
         debug!("Discord client ready, updating status to Connected");
-        self.update_status(ConnectionStatus::Connected).await;
+        self.session_connected(false).await;
         self.log_event(EventType::Success, format!("Connected as {}", display_name)).await;
 
         let event = GatewayEvent::Ready {
@@ -403,8 +732,33 @@ impl serenity_self::client::EventHandler for EventHandler {
         }
     }
 
-    async fn message(&self, _ctx: Context, msg: Message) {
-        let discord_msg = DiscordMessage::from(&msg);
+    async fn resume(&self, _ctx: Context, _event: ResumedEvent) {
+        debug!("Gateway session resumed");
+        self.session_connected(true).await;
+    }
+
+    async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+        debug!("Shard stage update: {:?} -> {:?}", event.old, event.new);
+
+        if event.new == ConnectionStage::Disconnected {
+            if let Some(ref stats) = self.stats {
+                stats.set_last_disconnect_reason(format!("Gateway dropped while {:?}", event.old)).await;
+            }
+        }
+
+        let status = match event.new {
+            ConnectionStage::Connected => ConnectionStatus::Connected,
+            ConnectionStage::Disconnected => ConnectionStatus::Disconnected,
+            _ if self.connected_once.load(Ordering::SeqCst) => ConnectionStatus::Reconnecting,
+            _ => ConnectionStatus::Connecting,
+        };
+
+        self.transition_status(status).await;
+    }
+
+    async fn message(&self, ctx: Context, msg: Message) {
+        let mut discord_msg = DiscordMessage::from(&msg);
+        discord_msg.parent_channel_id = thread_parent(&ctx, &msg);
         let event = GatewayEvent::MessageCreate(discord_msg);
 
         if let Err(e) = self.event_tx.send(event).await {
@@ -414,13 +768,14 @@ impl serenity_self::client::EventHandler for EventHandler {
 
     async fn message_update(
         &self,
-        _ctx: Context,
+        ctx: Context,
         _old: Option<Message>,
         new: Option<Message>,
         _event: serenity_self::model::event::MessageUpdateEvent,
     ) {
         if let Some(msg) = new {
-            let discord_msg = DiscordMessage::from(&msg);
+            let mut discord_msg = DiscordMessage::from(&msg);
+            discord_msg.parent_channel_id = thread_parent(&ctx, &msg);
             let event = GatewayEvent::MessageUpdate(discord_msg);
 
             if let Err(e) = self.event_tx.send(event).await {
@@ -443,3 +798,366 @@ impl serenity_self::client::EventHandler for EventHandler {
         }
     }
 }
+
+#[cfg(test)]
+pub mod mock {
+    use super::{ClientError, DiscordMessage, MessageSender};
+    use anyhow::Result;
+    use serenity_self::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum SentCall {
+        Message { channel_id: u64, content: String },
+        Reaction { channel_id: u64, message_id: u64, emoji: String },
+        Button {
+            message_id: u64,
+            channel_id: u64,
+            guild_id: Option<u64>,
+            application_id: u64,
+            custom_id: String,
+        },
+    }
+
+    #[derive(Clone, Default)]
+    pub struct MockSender {
+        calls: Arc<Mutex<Vec<SentCall>>>,
+        history: Arc<Mutex<Vec<DiscordMessage>>>,
+        failures: Arc<Mutex<u32>>,
+        failure_status: Arc<Mutex<Option<u16>>>,
+    }
+
+    impl MockSender {
+        pub fn calls(&self) -> Vec<SentCall> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        pub fn set_history(&self, messages: Vec<DiscordMessage>) {
+            *self.history.lock().unwrap() = messages;
+        }
+
+        pub fn fail_next(&self, count: u32) {
+            *self.failures.lock().unwrap() = count;
+            *self.failure_status.lock().unwrap() = None;
+        }
+
+        pub fn fail_next_with_status(&self, count: u32, status: u16) {
+            *self.failures.lock().unwrap() = count;
+            *self.failure_status.lock().unwrap() = Some(status);
+        }
+
+        fn take_failure(&self, channel_id: u64) -> Result<()> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                if let Some(error) = self.failure_status.lock().unwrap().and_then(|status| ClientError::from_status(status, Some(channel_id), None)) {
+                    return Err(error.into());
+                }
+                anyhow::bail!("mock failure");
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl MessageSender for MockSender {
+        async fn send_message(&self, channel_id: u64, content: &str) -> Result<()> {
+            self.take_failure(channel_id)?;
+            self.calls.lock().unwrap().push(SentCall::Message {
+                channel_id,
+                content: content.to_string(),
+            });
+            Ok(())
+        }
+
+        async fn add_reaction(&self, channel_id: u64, message_id: u64, emoji: &str) -> Result<()> {
+            self.take_failure(channel_id)?;
+            self.calls.lock().unwrap().push(SentCall::Reaction {
+                channel_id,
+                message_id,
+                emoji: emoji.to_string(),
+            });
+            Ok(())
+        }
+
+        async fn click_button(
+            &self,
+            message_id: u64,
+            channel_id: u64,
+            guild_id: Option<u64>,
+            application_id: u64,
+            custom_id: &str,
+        ) -> Result<()> {
+            self.take_failure(channel_id)?;
+            self.calls.lock().unwrap().push(SentCall::Button {
+                message_id,
+                channel_id,
+                guild_id,
+                application_id,
+                custom_id: custom_id.to_string(),
+            });
+            Ok(())
+        }
+
+        async fn get_messages(&self, channel_id: u64, limit: u8) -> Result<Vec<DiscordMessage>> {
+            Ok(self.history
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|m| m.channel_id == channel_id)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod fixtures {
+    use super::{Author, Button, ButtonEmoji, Component, DiscordMessage, Embed, EmbedAuthor, EmbedField, EmbedFooter, EmbedImage};
+    use serde_json::Value;
+
+    fn id(value: &Value) -> Option<u64> {
+        value.as_u64().or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+    }
+
+    fn text(value: &Value, key: &str) -> Option<String> {
+        value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+    }
+
+    fn embed(value: &Value) -> Embed {
+        Embed {
+            title: text(value, "title"),
+            description: text(value, "description"),
+            author: value.get("author").and_then(|a| text(a, "name")).map(|name| EmbedAuthor { name }),
+            footer: value.get("footer").and_then(|f| text(f, "text")).map(|text| EmbedFooter { text }),
+            fields: value.get("fields").and_then(|f| f.as_array()).map(|fields| {
+                fields.iter().map(|f| EmbedField {
+                    name: text(f, "name").unwrap_or_default(),
+                    value: text(f, "value").unwrap_or_default(),
+                }).collect()
+            }),
+            image: value.get("image").and_then(|i| text(i, "url")).map(|url| EmbedImage { url }),
+            color: value.get("color").and_then(|c| c.as_u64()).map(|c| c as u32),
+        }
+    }
+
+    fn component(value: &Value) -> Component {
+        let buttons = value.get("components")
+            .and_then(|c| c.as_array())
+            .map(|buttons| {
+                buttons.iter()
+                    .filter(|b| b.get("type").and_then(|t| t.as_u64()) == Some(2))
+                    .map(|b| Button {
+                        button_type: 2,
+                        style: b.get("style").and_then(|s| s.as_u64()).map(|s| s as u8),
+                        label: text(b, "label"),
+                        custom_id: text(b, "custom_id"),
+                        emoji: b.get("emoji").map(|e| ButtonEmoji {
+                            name: text(e, "name"),
+                            id: text(e, "id"),
+                        }),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Component {
+            component_type: 1,
+            components: buttons,
+        }
+    }
+
+    pub fn message_from_json(value: &Value) -> DiscordMessage {
+        let author = value.get("author").cloned().unwrap_or(Value::Null);
+        DiscordMessage {
+            id: value.get("id").and_then(id).unwrap_or_default(),
+            channel_id: value.get("channel_id").and_then(id).unwrap_or_default(),
+            parent_channel_id: value.get("parent_channel_id").and_then(id),
+            guild_id: value.get("guild_id").and_then(id),
+            author: Author {
+                id: author.get("id").and_then(id).unwrap_or_default(),
+                username: text(&author, "username").unwrap_or_default(),
+                bot: author.get("bot").and_then(|b| b.as_bool()).unwrap_or(false),
+            },
+            content: text(value, "content").unwrap_or_default(),
+            embeds: value.get("embeds")
+                .and_then(|e| e.as_array())
+                .map(|embeds| embeds.iter().map(embed).collect())
+                .unwrap_or_default(),
+            components: value.get("components")
+                .and_then(|c| c.as_array())
+                .map(|rows| rows.iter().map(component).collect())
+                .unwrap_or_default(),
+            webhook_id: value.get("webhook_id").and_then(id),
+            message_type: value.get("type").and_then(|t| t.as_u64()).unwrap_or(0) as u8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_message_conversion() {
+        let message: Message = serde_json::from_value(json!({
+            "id": "1100",
+            "channel_id": "100",
+            "guild_id": "200",
+            "author": { "id": "432610292342587392", "username": "Mudae", "discriminator": "0", "avatar": null, "bot": true },
+            "content": "",
+            "timestamp": "2024-01-01T00:00:00+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [{
+                "author": { "name": "Rem" },
+                "description": "Re:Zero\n**120**<:kakera:469835869059153940>",
+                "footer": { "text": "1 / 2" }
+            }],
+            "components": [{
+                "type": 1,
+                "components": [{ "type": 2, "style": 2, "custom_id": "marry", "emoji": { "name": "💖" } }]
+            }],
+            "pinned": false,
+            "type": 0
+        })).unwrap();
+
+        let converted = DiscordMessage::from(&message);
+        assert_eq!(converted.id, 1100);
+        assert_eq!(converted.channel_id, 100);
+        assert_eq!(converted.guild_id, Some(200));
+        assert_eq!(converted.author.id, 432610292342587392);
+        assert!(converted.author.bot);
+        assert!(!converted.is_webhook_or_system());
+
+        let embed = &converted.embeds[0];
+        assert_eq!(embed.author.as_ref().map(|a| a.name.as_str()), Some("Rem"));
+        assert_eq!(embed.footer.as_ref().map(|f| f.text.as_str()), Some("1 / 2"));
+
+        let button = &converted.components[0].components[0];
+        assert_eq!(button.custom_id.as_deref(), Some("marry"));
+        assert_eq!(button.emoji.as_ref().and_then(|e| e.name.as_deref()), Some("💖"));
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_are_counted() {
+        let stats = Stats::new();
+        let (event_tx, _event_rx) = mpsc::channel(10);
+        let handler = EventHandler::new(event_tx, Some(stats.clone()));
+
+        handler.session_connected(false).await;
+        assert_eq!(stats.get_reconnects(), 0);
+
+        for _ in 0..2 {
+            handler.transition_status(ConnectionStatus::Reconnecting).await;
+            handler.transition_status(ConnectionStatus::Connected).await;
+            handler.session_connected(true).await;
+        }
+        assert_eq!(stats.get_reconnects(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_reports_resume_or_new_session() {
+        let (event_tx, mut event_rx) = mpsc::channel(10);
+        let handler = EventHandler::new(event_tx, None);
+
+        assert_eq!(handler.replace_session("first".to_string()), None);
+        handler.session_connected(false).await;
+        assert!(event_rx.try_recv().is_err());
+
+        handler.transition_status(ConnectionStatus::Reconnecting).await;
+        handler.session_connected(true).await;
+        assert!(matches!(event_rx.try_recv(), Ok(GatewayEvent::Reconnected { resumed: true })));
+
+        handler.transition_status(ConnectionStatus::Disconnected).await;
+        assert_eq!(handler.replace_session("second".to_string()), Some("first".to_string()));
+        handler.session_connected(false).await;
+        assert!(matches!(event_rx.try_recv(), Ok(GatewayEvent::Reconnected { resumed: false })));
+
+        handler.session_connected(true).await;
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_client_errors_classified_by_status() {
+        assert!(matches!(ClientError::from_status(401, None, None), Some(ClientError::Unauthorized)));
+        assert!(matches!(
+            ClientError::from_status(429, None, Some(Duration::from_millis(1500))),
+            Some(ClientError::RateLimited { retry_after: Some(_) })
+        ));
+        assert!(ClientError::from_status(500, None, None).is_none());
+
+        let denied: anyhow::Error = ClientError::from_status(403, Some(42), None).unwrap().into();
+        assert_eq!(denied.to_string(), "No access to channel 42 (HTTP 403)");
+        assert!(is_channel_access_error(&denied));
+        assert!(client_error(&denied).is_some_and(ClientError::is_permanent));
+
+        let rejected: anyhow::Error = ClientError::NotFound { channel_id: None }.into();
+        assert!(!is_channel_access_error(&rejected));
+        assert!(!is_channel_access_error(&anyhow::anyhow!("No access to channel 42 (HTTP 403)")));
+        assert_eq!(ClientError::RateLimited { retry_after: None }.status(), Some(429));
+    }
+
+    #[test]
+    fn test_auth_error_burst_trips_within_window() {
+        let start = Instant::now();
+        let mut tracker = AuthErrorTracker { threshold: 3, ..Default::default() };
+        assert!(!tracker.record(start));
+        assert!(!tracker.record(start + Duration::from_secs(10)));
+        assert!(!tracker.record(start + Duration::from_secs(AUTH_ERROR_WINDOW_SECS + 20)));
+        assert!(!tracker.record(start + Duration::from_secs(AUTH_ERROR_WINDOW_SECS + 25)));
+        assert!(tracker.record(start + Duration::from_secs(AUTH_ERROR_WINDOW_SECS + 30)));
+        assert!(tracker.errors.is_empty());
+
+        let mut disabled = AuthErrorTracker::default();
+        assert!((0..10).all(|_| !disabled.record(start)));
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_burst_halts_with_alert() {
+        let stats = Stats::new();
+        let mut client = DiscordClient::new("test-token".to_string())
+            .with_stats(stats.clone())
+            .with_auth_error_halt(2);
+        client.halt_marker = std::env::temp_dir().join(format!("mudae-halt-auth-{}", std::process::id()));
+
+        client.record_status(404, Some(10003)).await;
+        client.record_status(500, None).await;
+        client.record_status(401, None).await;
+        assert!(!stats.is_halted());
+
+        client.record_status(403, Some(40002)).await;
+        assert!(stats.is_halted());
+        assert!(stats.has_account_alert());
+        assert!(client.send_message(1, "$w").await.is_err());
+        let log = stats.get_activity_log().await;
+        assert!(log.iter().any(|event| event.message.contains("2 Discord auth errors")));
+
+        stats.clear_halt(&client.halt_marker).unwrap();
+        assert!(!stats.has_account_alert());
+    }
+
+    #[tokio::test]
+    async fn test_channel_access_errors_do_not_halt() {
+        let stats = Stats::new();
+        let mut client = DiscordClient::new("test-token".to_string())
+            .with_stats(stats.clone())
+            .with_auth_error_halt(2);
+        client.halt_marker = std::env::temp_dir().join(format!("mudae-halt-channels-{}", std::process::id()));
+
+        for _ in 0..10 {
+            client.record_status(403, Some(50001)).await;
+            client.record_status(403, Some(50013)).await;
+            client.record_status(403, None).await;
+            client.record_status(404, Some(10003)).await;
+        }
+        assert!(!stats.is_halted());
+        assert!(!client.halt_marker.exists());
+    }
+}
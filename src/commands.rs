@@ -1,34 +1,206 @@
 #![allow(dead_code)]
 
-use crate::client::DiscordClient;
-use crate::config::Config;
-use crate::stats::{EventType, Stats};
+use crate::client::{client_error, is_channel_access_error, ClientError, MessageSender};
+use crate::config::{CommandSchedule, Config, RollSpread, ScheduledCommand};
+use crate::parser::MudaeParser;
+use crate::stats::{EventType, PendingClaim, RollEntry, Stats};
+use crate::utils::{random_delay, Cooldown};
 use anyhow::Result;
-use chrono::{DateTime, Local, NaiveTime, Utc};
-use std::collections::HashMap;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, warn};
 
+const ROLL_ATTRIBUTION_SECS: i64 = 10;
+const OWNED_LIST_COMMAND: &str = "$mm";
+const DAILY_KAKERA_WINDOW_SECS: i64 = 120;
+const DAILY_KAKERA_EMOJI: &str = "💎";
+const ECHO_SUPPRESS_SECS: u64 = 10;
+const RECENT_COMMANDS_CAPACITY: usize = 32;
+const ROLL_DELAY_JITTER_MS_PER_SEC: u64 = 500;
+const FAILURE_BREAKER_RETRY_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ActionKind {
+    Command,
+    Claim,
+    Kakera,
+}
+
+impl ActionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActionKind::Command => "commands",
+            ActionKind::Claim => "claims",
+            ActionKind::Kakera => "kakera reactions",
+        }
+    }
+}
+
 pub struct CommandExecutor {
-    client: DiscordClient,
+    client: Arc<dyn MessageSender>,
     config: Config,
     stats: Arc<Stats>,
     roll_cooldowns: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+    last_roll_commands: Arc<RwLock<HashMap<u64, (String, DateTime<Utc>)>>>,
     last_daily: Arc<RwLock<Option<DateTime<Utc>>>>,
-    claim_available: Arc<RwLock<bool>>,
+    last_daily_channel: Arc<RwLock<Option<u64>>>,
+    recent_commands: Arc<RwLock<VecDeque<(u64, String, tokio::time::Instant)>>>,
+    channel_roll_commands: HashMap<u64, Vec<String>>,
+    failure_streaks: Arc<RwLock<HashMap<ActionKind, (u32, tokio::time::Instant)>>>,
 }
 
 impl CommandExecutor {
-    pub fn new(client: DiscordClient, config: Config, stats: Arc<Stats>) -> Self {
+    pub fn new(client: impl MessageSender + 'static, config: Config, stats: Arc<Stats>) -> Self {
         Self {
-            client,
+            client: Arc::new(client),
             config,
             stats,
             roll_cooldowns: Arc::new(RwLock::new(HashMap::new())),
+            last_roll_commands: Arc::new(RwLock::new(HashMap::new())),
             last_daily: Arc::new(RwLock::new(None)),
-            claim_available: Arc::new(RwLock::new(true)),
+            last_daily_channel: Arc::new(RwLock::new(None)),
+            recent_commands: Arc::new(RwLock::new(VecDeque::new())),
+            channel_roll_commands: HashMap::new(),
+            failure_streaks: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_channel_roll_commands(mut self, channel_roll_commands: HashMap<u64, Vec<String>>) -> Self {
+        self.channel_roll_commands = channel_roll_commands;
+        self
+    }
+
+    fn roll_commands_for(&self, channel_id: u64) -> &[String] {
+        self.channel_roll_commands
+            .get(&channel_id)
+            .unwrap_or(&self.config.roll_commands)
+    }
+
+    pub fn roll_command_for(&self, channel_id: u64) -> Option<&String> {
+        self.roll_commands_for(channel_id).first()
+    }
+
+    pub async fn available_roll_command_for(&self, channel_id: u64) -> Option<String> {
+        let cooldowns = self.roll_cooldowns.read().await;
+        let now = Utc::now();
+        self.roll_commands_for(channel_id)
+            .iter()
+            .filter(|cmd| {
+                cooldowns.get(*cmd).is_none_or(|last_used| {
+                    let cooldown_duration = chrono::Duration::seconds(self.config.roll_cooldown_for(cmd) as i64);
+                    now.signed_duration_since(*last_used) >= cooldown_duration
+                })
+            })
+            .min_by_key(|cmd| cooldowns.get(*cmd))
+            .cloned()
+    }
+
+    fn ensure_not_halted(&self) -> Result<()> {
+        if self.stats.is_halted() {
+            anyhow::bail!("Outbound actions are halted");
+        }
+        Ok(())
+    }
+
+    async fn ensure_breaker_closed(&self, kind: ActionKind) -> Result<()> {
+        let threshold = self.config.max_consecutive_failures;
+        if threshold == 0 {
+            return Ok(());
+        }
+        if let Some((streak, last_failure)) = self.failure_streaks.read().await.get(&kind) {
+            let retry_after = tokio::time::Duration::from_secs(FAILURE_BREAKER_RETRY_SECS);
+            if *streak >= threshold && last_failure.elapsed() < retry_after {
+                anyhow::bail!("{} paused after {} consecutive failures", kind.label(), streak);
+            }
+        }
+        Ok(())
+    }
+
+    async fn record_outcome<T>(&self, kind: ActionKind, result: Result<T>) -> Result<T> {
+        let threshold = self.config.max_consecutive_failures;
+        if result.is_ok() {
+            let cleared = self.failure_streaks.write().await.remove(&kind);
+            if threshold > 0 && cleared.is_some_and(|(streak, _)| streak >= threshold) {
+                self.stats.log_event(EventType::Success, format!("Resumed {} after a successful attempt", kind.label())).await;
+            }
+            return result;
+        }
+
+        let error = result.as_ref().err().and_then(client_error);
+        if matches!(error, Some(ClientError::RateLimited { .. })) {
+            return result;
+        }
+        let unauthorized = matches!(error, Some(ClientError::Unauthorized));
+
+        let (previous, streak) = {
+            let mut streaks = self.failure_streaks.write().await;
+            let entry = streaks.entry(kind).or_insert((0, tokio::time::Instant::now()));
+            let previous = entry.0;
+            entry.0 = if unauthorized { (previous + 1).max(threshold) } else { previous + 1 };
+            entry.1 = tokio::time::Instant::now();
+            (previous, entry.0)
+        };
+        if threshold > 0 && previous < threshold && streak >= threshold {
+            let reason = if unauthorized {
+                "Discord rejected the token".to_string()
+            } else {
+                format!("{} consecutive failures", streak)
+            };
+            self.stats.log_event(
+                EventType::Warning,
+                format!("Paused {} after {} (press A to reset)", kind.label(), reason),
+            ).await;
+        }
+        result
+    }
+
+    pub async fn paused_actions(&self) -> Vec<ActionKind> {
+        let threshold = self.config.max_consecutive_failures;
+        if threshold == 0 {
+            return Vec::new();
         }
+        let mut kinds: Vec<ActionKind> = self.failure_streaks
+            .read()
+            .await
+            .iter()
+            .filter(|(_, (streak, _))| *streak >= threshold)
+            .map(|(kind, _)| *kind)
+            .collect();
+        kinds.sort();
+        kinds
+    }
+
+    pub async fn reset_failure_breakers(&self) -> Vec<ActionKind> {
+        let paused = self.paused_actions().await;
+        self.failure_streaks.write().await.clear();
+        paused
+    }
+
+    async fn guarded<T>(&self, kind: ActionKind, channel_id: u64, action: impl Future<Output = Result<T>>) -> Result<T> {
+        self.ensure_not_halted()?;
+        self.ensure_breaker_closed(kind).await?;
+        let result = action.await;
+        match &result {
+            Ok(_) => self.stats.clear_channel_access_failures(channel_id).await,
+            Err(e) if is_channel_access_error(e) && self.stats.record_channel_access_failure(channel_id).await => {
+                self.stats.log_event(
+                    EventType::Error,
+                    format!("Lost access to channel {}, stopped rolling there (press A to retry)", channel_id),
+                ).await;
+            }
+            Err(_) => {}
+        }
+        self.record_outcome(kind, result).await
+    }
+
+    pub async fn send(&self, channel_id: u64, content: &str) -> Result<()> {
+        self.guarded(ActionKind::Command, channel_id, self.client.send_message(channel_id, content)).await?;
+        self.record_sent_command(channel_id, content).await;
+        Ok(())
     }
 
     pub async fn execute_roll(&self, channel_id: u64) -> Result<bool> {
@@ -56,28 +228,46 @@ impl CommandExecutor {
                 break;
             }
 
-            self.client.send_message(channel_id, &cmd).await?;
-            self.update_roll_cooldown(&cmd).await;
+            self.send(channel_id, &cmd).await?;
+            self.update_roll_cooldown(channel_id, &cmd).await;
             self.stats.increment_rolls_executed();
+            self.stats.consume_rolls(self.config.roll_yield_for(&cmd) as u64);
             self.stats.log_event(EventType::Roll, format!("Executed {}", cmd)).await;
             executed_any = true;
             
-            let delay = 500 + rand::random::<u64>() % 1000;
-            tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+            tokio::time::sleep(random_delay(500, 1500)).await;
         }
 
         Ok(executed_any)
     }
 
+    pub async fn execute_manual_roll(&self, channel_id: u64) -> Result<Option<String>> {
+        let rolls_remaining = self.stats.get_rolls_remaining();
+        if rolls_remaining == 0 {
+            return Ok(None);
+        }
+
+        let Some(cmd) = self.available_roll_command_for(channel_id).await.or_else(|| self.roll_command_for(channel_id).cloned()) else {
+            return Ok(None);
+        };
+
+        self.send(channel_id, &cmd).await?;
+        self.update_roll_cooldown(channel_id, &cmd).await;
+        self.stats.increment_rolls_executed();
+        self.stats.consume_rolls(self.config.roll_yield_for(&cmd) as u64);
+        self.stats.log_event(EventType::Roll, format!("Manual roll with {}", cmd)).await;
+        Ok(Some(cmd))
+    }
+
     pub async fn get_all_available_roll_commands(&self) -> Vec<String> {
         let cooldowns = self.roll_cooldowns.read().await;
         let now = Utc::now();
-        let cooldown_duration = chrono::Duration::seconds(self.config.roll_cooldown_seconds as i64);
 
         self.config.roll_commands
             .iter()
             .filter(|cmd| {
                 if let Some(last_used) = cooldowns.get(*cmd) {
+                    let cooldown_duration = chrono::Duration::seconds(self.config.roll_cooldown_for(cmd) as i64);
                     now.signed_duration_since(*last_used) >= cooldown_duration
                 } else {
                     true
@@ -92,14 +282,65 @@ impl CommandExecutor {
         available.first().cloned()
     }
 
-    async fn update_roll_cooldown(&self, command: &str) {
-        let mut cooldowns = self.roll_cooldowns.write().await;
-        cooldowns.insert(command.to_string(), Utc::now());
+    async fn update_roll_cooldown(&self, channel_id: u64, command: &str) {
+        let now = Utc::now();
+        self.roll_cooldowns.write().await.insert(command.to_string(), now);
+        self.last_roll_commands.write().await.insert(channel_id, (command.to_string(), now));
+    }
+
+    async fn record_sent_command(&self, channel_id: u64, content: &str) {
+        let mut recent = self.recent_commands.write().await;
+        if recent.len() >= RECENT_COMMANDS_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back((channel_id, content.trim().to_string(), tokio::time::Instant::now()));
+    }
+
+    pub async fn is_recent_command(&self, channel_id: u64, content: &str) -> bool {
+        let window = tokio::time::Duration::from_secs(ECHO_SUPPRESS_SECS);
+        let content = content.trim();
+        self.recent_commands.read().await.iter().any(|(channel, command, sent_at)| {
+            *channel == channel_id && command == content && sent_at.elapsed() <= window
+        })
+    }
+
+    pub async fn last_sent_at(&self) -> Option<tokio::time::Instant> {
+        self.recent_commands.read().await.back().map(|(_, _, sent_at)| *sent_at)
+    }
+
+    pub async fn recent_roll_command(&self, channel_id: u64) -> Option<String> {
+        let last = self.last_roll_commands.read().await;
+        let (command, sent_at) = last.get(&channel_id)?;
+        let age = Utc::now().signed_duration_since(*sent_at);
+        if age <= chrono::Duration::seconds(ROLL_ATTRIBUTION_SECS) {
+            Some(command.clone())
+        } else {
+            None
+        }
+    }
+
+    fn application_id_or_default(application_id: u64) -> u64 {
+        if application_id == 0 {
+            Config::mudae_bot_id()
+        } else {
+            application_id
+        }
+    }
+
+    pub async fn execute_claim(&self, channel_id: u64, message_id: u64, emoji: Option<&str>) -> Result<()> {
+        self.ensure_not_halted()?;
+        let emoji = emoji.unwrap_or(&self.config.claim_emoji);
+        self.client.add_reaction(channel_id, message_id, emoji).await?;
+        debug!("Attempted to claim character with {} (message {})", emoji, message_id);
+        Ok(())
     }
 
-    pub async fn execute_claim(&self, channel_id: u64, message_id: u64) -> Result<()> {
-        self.client.add_reaction(channel_id, message_id, "💖").await?;
-        debug!("Attempted to claim character (message {})", message_id);
+    pub async fn execute_mark_wished(&self, channel_id: u64, message_id: u64, emoji: &str) -> Result<()> {
+        if MudaeParser::is_claim_emoji(emoji) {
+            anyhow::bail!("{} is a claim emoji and cannot be used as a marker", emoji);
+        }
+        self.guarded(ActionKind::Command, channel_id, self.client.add_reaction(channel_id, message_id, emoji)).await?;
+        debug!("Marked missed wish on message {} with {}", message_id, emoji);
         Ok(())
     }
 
@@ -107,44 +348,107 @@ impl CommandExecutor {
         &self,
         channel_id: u64,
         message_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
         button_id: &str,
     ) -> Result<()> {
+        self.ensure_not_halted()?;
         self.client.click_button(
             message_id,
             channel_id,
-            None,
-            Config::mudae_bot_id(),
+            guild_id,
+            Self::application_id_or_default(application_id),
             button_id,
         ).await?;
         debug!("Clicked claim button {} on message {}", button_id, message_id);
         Ok(())
     }
 
+    pub async fn claim_message(
+        &self,
+        channel_id: u64,
+        message_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
+        claim_button_id: Option<&str>,
+        claim_emoji: Option<&str>,
+    ) -> Result<()> {
+        let claim = self.try_claim_message(channel_id, message_id, guild_id, application_id, claim_button_id, claim_emoji);
+        self.guarded(ActionKind::Claim, channel_id, claim).await
+    }
+
+    pub async fn claim_roll(&self, entry: &RollEntry) -> Result<()> {
+        self.stats.add_pending_claim(PendingClaim {
+            name: entry.character_name.clone(),
+            series: entry.series.clone(),
+            message_id: entry.message_id,
+            channel_id: entry.channel_id,
+            kakera_value: entry.kakera_value,
+            fuzzy_match: false,
+        }).await;
+        let result = self
+            .claim_message(
+                entry.channel_id,
+                entry.message_id,
+                entry.guild_id,
+                entry.application_id,
+                entry.claim_button_id.as_deref(),
+                entry.claim_emoji.as_deref(),
+            )
+            .await;
+        if result.is_err() {
+            self.stats.cancel_pending_claim(&entry.character_name, entry.message_id).await;
+        }
+        result
+    }
+
+    async fn try_claim_message(
+        &self,
+        channel_id: u64,
+        message_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
+        claim_button_id: Option<&str>,
+        claim_emoji: Option<&str>,
+    ) -> Result<()> {
+        if let Some(button_id) = claim_button_id {
+            match self.execute_button_claim(channel_id, message_id, guild_id, application_id, button_id).await {
+                Ok(_) => return Ok(()),
+                Err(e) => warn!("Failed to click claim button: {}", e),
+            }
+        }
+        self.execute_claim(channel_id, message_id, claim_emoji).await
+    }
+
     pub async fn execute_kakera_react(
         &self,
         channel_id: u64,
         message_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
         button_id: Option<&str>,
     ) -> Result<()> {
         if !self.config.auto_react_kakera {
             return Ok(());
         }
-
-        if let Some(btn_id) = button_id {
-            self.client.click_button(
-                message_id,
-                channel_id,
-                None,
-                Config::mudae_bot_id(),
-                btn_id,
-            ).await?;
-            debug!("Clicked kakera button {} on message {}", btn_id, message_id);
-        } else {
-            if let Err(e) = self.client.add_reaction(channel_id, message_id, "💎").await {
-                warn!("Failed to add kakera reaction: {}", e);
+        let react = async {
+            if let Some(btn_id) = button_id {
+                let clicked = self.client.click_button(
+                    message_id,
+                    channel_id,
+                    guild_id,
+                    Self::application_id_or_default(application_id),
+                    btn_id,
+                ).await;
+                if clicked.is_ok() {
+                    debug!("Clicked kakera button {} on message {}", btn_id, message_id);
+                }
+                clicked
+            } else {
+                self.client.add_reaction(channel_id, message_id, "💎").await
             }
-        }
-        Ok(())
+        };
+        self.guarded(ActionKind::Kakera, channel_id, react).await
     }
 
     pub async fn execute_daily_commands(&self, channel_id: u64) -> Result<()> {
@@ -156,12 +460,137 @@ impl CommandExecutor {
             return Ok(());
         }
 
-        self.client.send_message(channel_id, "$daily").await?;
-        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-        self.client.send_message(channel_id, "$dk").await?;
+        for (i, cmd) in self.config.daily_commands.iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(random_delay(1500, 4000)).await;
+            }
+            self.send(channel_id, cmd).await?;
+        }
         self.stats.log_event(EventType::Success, "Executed daily commands".to_string()).await;
 
         *self.last_daily.write().await = Some(Utc::now());
+        *self.last_daily_channel.write().await = Some(channel_id);
+        Ok(())
+    }
+
+    pub async fn execute_reconnect_commands(&self, channel_id: u64) -> Result<()> {
+        if self.config.on_reconnect_commands.is_empty() || self.stats.is_paused() {
+            return Ok(());
+        }
+
+        for cmd in &self.config.on_reconnect_commands {
+            tokio::time::sleep(random_delay(1500, 4000)).await;
+            self.send(channel_id, cmd).await?;
+            debug!("Sent reconnect command: {}", cmd);
+        }
+
+        self.stats.log_event(
+            EventType::Success,
+            format!("Executed {} reconnect command(s)", self.config.on_reconnect_commands.len()),
+        ).await;
+        Ok(())
+    }
+
+    pub async fn execute_auto_divorce(
+        &self,
+        channel_id: u64,
+        name: &str,
+        fuzzy_match: bool,
+        kakera_value: Option<u32>,
+    ) -> Result<bool> {
+        if !self.config.should_auto_divorce(fuzzy_match, kakera_value) {
+            return Ok(false);
+        }
+
+        self.execute_divorce(channel_id, name).await?;
+        self.stats.log_event(
+            EventType::Warning,
+            format!("⚠ Auto-divorced {} (fuzzy wishlist match, {} ka)", name, kakera_value.unwrap_or(0)),
+        ).await;
+        Ok(true)
+    }
+
+    pub async fn execute_divorce(&self, channel_id: u64, name: &str) -> Result<()> {
+        for cmd in &self.config.auto_divorce_commands {
+            tokio::time::sleep(random_delay(3000, 6000)).await;
+            self.send(channel_id, &cmd.replace("{name}", name)).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn execute_owned_sync(&self, channel_id: u64) -> Result<()> {
+        self.stats.request_owned_sync();
+        self.send(channel_id, OWNED_LIST_COMMAND).await?;
+        self.stats.log_event(EventType::Info, format!("Syncing owned characters with {}", OWNED_LIST_COMMAND)).await;
+        Ok(())
+    }
+
+    pub async fn turn_page(
+        &self,
+        channel_id: u64,
+        message_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
+        button_id: Option<&str>,
+    ) -> Result<()> {
+        let turn = async {
+            match button_id {
+                Some(button_id) => {
+                    self.client.click_button(
+                        message_id,
+                        channel_id,
+                        guild_id,
+                        Self::application_id_or_default(application_id),
+                        button_id,
+                    ).await
+                }
+                None => self.client.add_reaction(channel_id, message_id, "➡️").await,
+            }
+        };
+        self.guarded(ActionKind::Command, channel_id, turn).await?;
+        debug!("Turned page on message {}", message_id);
+        Ok(())
+    }
+
+    pub async fn execute_daily_kakera(
+        &self,
+        channel_id: u64,
+        message_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
+        button_id: Option<&str>,
+    ) -> Result<bool> {
+        let recent = self.last_daily.read().await
+            .is_some_and(|at| (Utc::now() - at).num_seconds() <= DAILY_KAKERA_WINDOW_SECS);
+        let same_channel = *self.last_daily_channel.read().await == Some(channel_id);
+        if !self.config.daily_kakera_react || !recent || !same_channel || self.stats.is_paused() {
+            return Ok(false);
+        }
+        self.ensure_not_halted()?;
+
+        tokio::time::sleep(random_delay(800, 2000)).await;
+        let collect = async {
+            match button_id {
+                Some(button_id) => {
+                    self.client.click_button(
+                        message_id,
+                        channel_id,
+                        guild_id,
+                        Self::application_id_or_default(application_id),
+                        button_id,
+                    ).await
+                }
+                None => self.client.add_reaction(channel_id, message_id, DAILY_KAKERA_EMOJI).await,
+            }
+        };
+        self.guarded(ActionKind::Kakera, channel_id, collect).await?;
+        debug!("Collected daily kakera on message {}", message_id);
+        Ok(true)
+    }
+
+    pub async fn execute_scheduled_command(&self, channel_id: u64, command: &str) -> Result<()> {
+        self.send(channel_id, command).await?;
+        self.stats.log_event(EventType::Info, format!("Executed scheduled {}", command)).await;
         Ok(())
     }
 
@@ -182,55 +611,50 @@ impl CommandExecutor {
         }
     }
 
+    pub async fn next_daily_at(&self) -> Option<DateTime<Local>> {
+        let last = *self.last_daily.read().await;
+        crate::utils::next_daily_run(last, self.parse_schedule_time(), Utc::now())
+    }
+
     fn parse_schedule_time(&self) -> NaiveTime {
         NaiveTime::parse_from_str(&self.config.daily_time, "%H:%M")
             .unwrap_or_else(|_| NaiveTime::from_hms_opt(0, 0, 0).unwrap())
     }
 
     pub async fn check_rolls(&self, channel_id: u64) -> Result<()> {
-        self.client.send_message(channel_id, "$ru").await?;
+        self.send(channel_id, "$ru").await?;
         Ok(())
     }
 
     pub async fn check_claim_status(&self, channel_id: u64) -> Result<()> {
-        self.client.send_message(channel_id, "$tu").await?;
+        self.send(channel_id, "$tu").await?;
         Ok(())
     }
 
     pub async fn verify_character(&self, channel_id: u64, character_name: &str) -> Result<()> {
         let cmd = format!("$im {}", character_name);
-        self.client.send_message(channel_id, &cmd).await?;
+        self.send(channel_id, &cmd).await?;
         debug!("Sent character verification: {}", cmd);
         Ok(())
     }
 
     pub async fn search_character(&self, channel_id: u64, query: &str) -> Result<()> {
         let cmd = format!("$search {}", query);
-        self.client.send_message(channel_id, &cmd).await?;
+        self.send(channel_id, &cmd).await?;
         debug!("Sent character search: {}", cmd);
         Ok(())
     }
 
-    pub async fn set_claim_available(&self, available: bool) {
-        *self.claim_available.write().await = available;
-        self.stats.set_claim_available(available);
-    }
-
-    pub async fn is_claim_available(&self) -> bool {
-        *self.claim_available.read().await
-    }
-
-    pub async fn get_time_until_next_roll(&self) -> Option<chrono::Duration> {
+    pub async fn get_time_until_next_roll(&self, channel_id: u64) -> Option<chrono::Duration> {
         let cooldowns = self.roll_cooldowns.read().await;
         let now = Utc::now();
-        let cooldown_secs = self.config.roll_cooldown_seconds as i64;
 
         let mut min_wait: Option<chrono::Duration> = None;
 
-        for cmd in &self.config.roll_commands {
+        for cmd in self.roll_commands_for(channel_id) {
             if let Some(last_used) = cooldowns.get(cmd) {
                 let elapsed = now.signed_duration_since(*last_used);
-                let remaining = chrono::Duration::seconds(cooldown_secs) - elapsed;
+                let remaining = chrono::Duration::seconds(self.config.roll_cooldown_for(cmd) as i64) - elapsed;
                 
                 if remaining > chrono::Duration::zero() {
                     match min_wait {
@@ -268,33 +692,254 @@ impl CommandExecutor {
     }
 }
 
+struct ScheduledTask {
+    command: ScheduledCommand,
+    cooldown: Cooldown,
+    last_date: Option<NaiveDate>,
+}
+
+impl ScheduledTask {
+    fn new(command: ScheduledCommand) -> Self {
+        let cooldown_secs = match command.schedule {
+            CommandSchedule::Every(secs) => secs,
+            CommandSchedule::DailyAt(_) => 0,
+        };
+        Self {
+            command,
+            cooldown: Cooldown::new(cooldown_secs),
+            last_date: None,
+        }
+    }
+
+    fn take_if_due(&mut self, local_now: NaiveDateTime) -> Option<String> {
+        match self.command.schedule {
+            CommandSchedule::Every(_) => {
+                let key = self.command.command.as_str();
+                if !self.cooldown.is_ready(key) {
+                    return None;
+                }
+                self.cooldown.trigger(key);
+            }
+            CommandSchedule::DailyAt(time) => {
+                let today = local_now.date();
+                if self.last_date == Some(today) || local_now.time() < time {
+                    return None;
+                }
+                self.last_date = Some(today);
+            }
+        }
+        Some(self.command.command.clone())
+    }
+}
+
 pub struct RollScheduler {
     executor: Arc<CommandExecutor>,
     channels: Vec<u64>,
     stats: Arc<Stats>,
+    scheduled_tasks: Mutex<Vec<ScheduledTask>>,
+    next_spread_roll: Mutex<Option<DateTime<Utc>>>,
+    roll_hold_until: Mutex<Option<tokio::time::Instant>>,
+    delayed_reset: Mutex<Option<DateTime<Utc>>>,
+    last_roll_channel: Mutex<Option<u64>>,
 }
 
 impl RollScheduler {
     pub fn new(executor: Arc<CommandExecutor>, channels: Vec<u64>, stats: Arc<Stats>) -> Self {
-        Self { executor, channels, stats }
+        let scheduled_tasks = executor.config.scheduled_commands
+            .iter()
+            .cloned()
+            .map(ScheduledTask::new)
+            .collect();
+
+        Self {
+            executor,
+            channels,
+            stats,
+            scheduled_tasks: Mutex::new(scheduled_tasks),
+            next_spread_roll: Mutex::new(None),
+            roll_hold_until: Mutex::new(None),
+            delayed_reset: Mutex::new(None),
+            last_roll_channel: Mutex::new(None),
+        }
+    }
+
+    fn channel_order(&self) -> Vec<u64> {
+        let mut channels = self.channels.clone();
+        if self.executor.config.shuffle_channels {
+            channels.shuffle(&mut rand::thread_rng());
+        }
+        channels
+    }
+
+    async fn wait_for_channel_switch(&self, channel_id: u64) {
+        let previous = self.last_roll_channel.lock().await.replace(channel_id);
+        let base_ms = self.executor.config.inter_channel_delay_ms;
+        if base_ms == 0 || previous.is_none() || previous == Some(channel_id) {
+            return;
+        }
+        let delay = random_delay(base_ms, base_ms + base_ms / 2);
+        let elapsed = self.executor.last_sent_at().await.map(|at| at.elapsed()).unwrap_or(delay);
+        let remaining = delay.saturating_sub(elapsed);
+        if !remaining.is_zero() {
+            debug!("Waiting {}ms before rolling in channel {}", remaining.as_millis(), channel_id);
+            tokio::time::sleep(remaining).await;
+        }
+    }
+
+    async fn hold_rolls(&self, base_secs: u64, reason: &str) {
+        if base_secs == 0 {
+            return;
+        }
+        let delay = random_delay(base_secs * 1000, base_secs * (1000 + ROLL_DELAY_JITTER_MS_PER_SEC));
+        *self.roll_hold_until.lock().await = Some(tokio::time::Instant::now() + delay);
+        debug!("Holding rolls for {:.1}s {}", delay.as_secs_f64(), reason);
+    }
+
+    async fn rolls_held(&self) -> bool {
+        matches!(*self.roll_hold_until.lock().await, Some(until) if tokio::time::Instant::now() < until)
+    }
+
+    async fn spread_roll_due(&self) -> bool {
+        if self.executor.config.roll_spread != RollSpread::Spread {
+            return true;
+        }
+        match *self.next_spread_roll.lock().await {
+            Some(next) => Utc::now() >= next,
+            None => true,
+        }
+    }
+
+    async fn update_pending_rolls(&self) {
+        let active = self.executor.is_roll_enabled()
+            && !self.stats.is_paused()
+            && !self.stats.is_halted()
+            && !self.rolls_held().await;
+        let pending = match self.stats.get_rolls_remaining() {
+            0 => 0,
+            _ if !active => 0,
+            remaining if self.executor.config.roll_spread == RollSpread::Burst => remaining,
+            _ if self.spread_roll_due().await => 1,
+            _ => 0,
+        };
+        self.stats.set_pending_rolls(pending);
+    }
+
+    async fn schedule_next_spread_roll(&self) {
+        if self.executor.config.roll_spread != RollSpread::Spread {
+            return;
+        }
+        let now = Utc::now();
+        let until_reset = self.stats.get_next_roll_reset().await.map(|reset| reset.signed_duration_since(now));
+        let interval = self.executor.config.spread_interval(self.stats.get_rolls_remaining(), until_reset);
+        let next = now + chrono::Duration::from_std(interval).unwrap_or_else(|_| chrono::Duration::zero());
+        *self.next_spread_roll.lock().await = Some(next);
+        debug!("Next spread roll at {}", next.with_timezone(&Local).format("%H:%M:%S"));
+    }
+
+    async fn first_accessible_channel(&self) -> Option<u64> {
+        for &channel_id in &self.channels {
+            if self.stats.is_channel_accessible(channel_id).await {
+                return Some(channel_id);
+            }
+        }
+        None
+    }
+
+    async fn run_scheduled_commands(&self) {
+        if self.stats.is_paused() || self.stats.is_halted() {
+            return;
+        }
+
+        let Some(channel_id) = self.first_accessible_channel().await else {
+            return;
+        };
+
+        let due: Vec<String> = {
+            let mut tasks = self.scheduled_tasks.lock().await;
+            let local_now = Local::now().naive_local();
+            tasks.iter_mut().filter_map(|task| task.take_if_due(local_now)).collect()
+        };
+
+        for cmd in due {
+            if let Err(e) = self.executor.execute_scheduled_command(channel_id, &cmd).await {
+                warn!("Failed to send scheduled command {}: {}", cmd, e);
+            }
+            tokio::time::sleep(random_delay(1000, 2500)).await;
+        }
+    }
+
+    async fn check_idle(&self) -> bool {
+        let now = Utc::now();
+        if let Some(until) = self.stats.get_idle_until().await {
+            if now < until {
+                return true;
+            }
+            self.stats.set_idle_until(None).await;
+            self.stats.log_event(EventType::Info, "Waking up from idle".to_string()).await;
+            return false;
+        }
+
+        if self.stats.is_paused() || self.stats.get_rolls_remaining() > 0 || self.stats.is_claim_available() {
+            return false;
+        }
+
+        let wake_at = [self.stats.get_next_roll_reset().await, self.stats.get_next_claim_reset()]
+            .into_iter()
+            .flatten()
+            .min();
+        match wake_at {
+            Some(wake_at) if wake_at > now => {
+                self.stats.set_idle_until(Some(wake_at)).await;
+                self.stats.log_event(
+                    EventType::Info,
+                    format!("Nothing to do, idling until {}", wake_at.with_timezone(&Local).format("%H:%M")),
+                ).await;
+                true
+            }
+            _ => false,
+        }
     }
 
     pub async fn run(&self) {
         self.stats.log_event(EventType::Info, "Roll scheduler started".to_string()).await;
+        self.hold_rolls(self.executor.config.startup_roll_delay_seconds, "after startup").await;
         
         for &channel_id in &self.channels {
-            if self.executor.is_daily_enabled() {
+            if self.executor.is_daily_enabled() && self.stats.is_channel_accessible(channel_id).await {
                 let _ = self.executor.execute_daily_commands(channel_id).await;
             }
         }
         
         loop {
-            for &channel_id in &self.channels {
-                if !self.executor.is_roll_enabled() || self.stats.is_paused() {
+            self.run_scheduled_commands().await;
+
+            if self.executor.config.auto_idle && self.check_idle().await {
+                self.stats.set_pending_rolls(0);
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                continue;
+            }
+
+            if self.first_accessible_channel().await.is_none() {
+                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                continue;
+            }
+
+            for channel_id in self.channel_order() {
+                if !self.stats.is_channel_accessible(channel_id).await {
+                    continue;
+                }
+
+                self.update_pending_rolls().await;
+                if !self.executor.is_roll_enabled() || self.stats.is_paused() || self.stats.is_halted() {
                     tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
                     continue;
                 }
 
+                if self.rolls_held().await {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    continue;
+                }
+
                 let rolls_remaining = self.stats.get_rolls_remaining();
                 if rolls_remaining == 0 {
                     let reset_time = self.stats.get_next_roll_reset().await;
@@ -302,13 +947,19 @@ impl RollScheduler {
                     
                     if let Some(reset) = reset_time {
                         if reset <= now {
+                            let first_after_reset = self.delayed_reset.lock().await.replace(reset) != Some(reset);
+                            if first_after_reset && self.executor.config.post_reset_roll_delay_seconds > 0 {
+                                self.hold_rolls(self.executor.config.post_reset_roll_delay_seconds, "after roll reset").await;
+                                continue;
+                            }
                             debug!("Reset time has passed, attempting to roll to refresh roll count");
-                            let cmd = self.executor.config.roll_commands.first();
+                            let cmd = self.executor.available_roll_command_for(channel_id).await;
                             if let Some(cmd) = cmd {
-                                if let Err(e) = self.executor.client.send_message(channel_id, cmd).await {
+                                self.wait_for_channel_switch(channel_id).await;
+                                if let Err(e) = self.executor.send(channel_id, &cmd).await {
                                     warn!("Failed to send roll command after reset: {}", e);
                                 } else {
-                                    self.executor.update_roll_cooldown(cmd).await;
+                                    self.executor.update_roll_cooldown(channel_id, &cmd).await;
                                     self.stats.log_event(EventType::Roll, format!("Rolling after reset with {}", cmd)).await;
                                 }
                             }
@@ -334,7 +985,12 @@ impl RollScheduler {
                     }
                 }
 
-                let cmd = self.executor.config.roll_commands.first();
+                if !self.spread_roll_due().await {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    continue;
+                }
+
+                let cmd = self.executor.available_roll_command_for(channel_id).await;
                 if let Some(cmd) = cmd {
                     let current_rolls = self.stats.get_rolls_remaining();
                     let is_extra_roll = current_rolls == 0;
@@ -342,18 +998,19 @@ impl RollScheduler {
                     if is_extra_roll {
                         debug!("Rolls exhausted, sending one extra roll (n+1) to trigger cooldown message");
                     }
-                    
-                    if let Err(e) = self.executor.client.send_message(channel_id, cmd).await {
+
+                    self.wait_for_channel_switch(channel_id).await;
+                    if let Err(e) = self.executor.send(channel_id, &cmd).await {
                         warn!("Failed to send roll command: {}", e);
                         tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                         continue;
                     }
                     
-                    self.executor.update_roll_cooldown(cmd).await;
+                    self.executor.update_roll_cooldown(channel_id, &cmd).await;
                     self.stats.increment_rolls_executed();
                     
-                    if !is_extra_roll && current_rolls > 0 {
-                        self.stats.set_rolls_remaining(current_rolls - 1);
+                    if !is_extra_roll {
+                        self.stats.consume_rolls(self.executor.config.roll_yield_for(&cmd) as u64);
                     }
                     
                     if is_extra_roll {
@@ -362,9 +1019,14 @@ impl RollScheduler {
                         continue;
                     } else {
                         self.stats.log_event(EventType::Roll, format!("Rolling with {}", cmd)).await;
+                        self.schedule_next_spread_roll().await;
                     }
                     
                     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                } else if let Some(wait) = self.executor.get_time_until_next_roll(channel_id).await {
+                    debug!("All roll commands on cooldown, next in {}s", wait.num_seconds());
+                    let wait = wait.to_std().unwrap_or_default().max(tokio::time::Duration::from_secs(1));
+                    tokio::time::sleep(wait).await;
                 } else {
                     debug!("No roll commands configured");
                     tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
@@ -373,3 +1035,332 @@ impl RollScheduler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::{MockSender, SentCall};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_daily_commands_run_in_order() {
+        let config = Config {
+            auto_daily: true,
+            daily_commands: vec!["$daily".to_string(), "$dk".to_string(), "$vote".to_string()],
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let executor = CommandExecutor::new(sender.clone(), config, Stats::new());
+
+        executor.execute_daily_commands(100).await.unwrap();
+
+        let sent: Vec<_> = sender.calls().into_iter().filter_map(|c| match c {
+            SentCall::Message { content, .. } => Some(content),
+            _ => None,
+        }).collect();
+        assert_eq!(sent, vec!["$daily", "$dk", "$vote"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_repeated_failures_pause_action_until_success_or_reset() {
+        let config = Config {
+            max_consecutive_failures: 3,
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        let executor = CommandExecutor::new(sender.clone(), config, stats.clone());
+
+        sender.fail_next(3);
+        for _ in 0..3 {
+            assert!(executor.send(100, "$wa").await.is_err());
+        }
+        assert_eq!(executor.paused_actions().await, vec![ActionKind::Command]);
+        let log = stats.get_activity_log().await;
+        assert!(log.iter().any(|e| e.event_type == EventType::Warning && e.message.contains("Paused commands")));
+
+        assert!(executor.send(100, "$wa").await.is_err());
+        assert!(sender.calls().is_empty());
+        assert!(executor.claim_message(100, 1, None, 0, None, None).await.is_ok());
+
+        tokio::time::advance(tokio::time::Duration::from_secs(FAILURE_BREAKER_RETRY_SECS)).await;
+        executor.send(100, "$wa").await.unwrap();
+        assert!(executor.paused_actions().await.is_empty());
+
+        sender.fail_next(3);
+        for _ in 0..3 {
+            assert!(executor.send(100, "$wa").await.is_err());
+        }
+        assert_eq!(executor.reset_failure_breakers().await, vec![ActionKind::Command]);
+        executor.send(100, "$wa").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_breaker_branches_on_client_error_kind() {
+        let config = Config {
+            max_consecutive_failures: 3,
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        let executor = CommandExecutor::new(sender.clone(), config, stats.clone());
+
+        sender.fail_next_with_status(5, 429);
+        for _ in 0..5 {
+            assert!(executor.send(100, "$wa").await.is_err());
+        }
+        assert!(executor.paused_actions().await.is_empty());
+
+        sender.fail_next_with_status(1, 401);
+        assert!(executor.send(100, "$wa").await.is_err());
+        assert_eq!(executor.paused_actions().await, vec![ActionKind::Command]);
+        let log = stats.get_activity_log().await;
+        assert!(log.iter().any(|e| e.message == "Paused commands after Discord rejected the token (press A to reset)"));
+    }
+
+    #[tokio::test]
+    async fn test_reactions_and_page_turns_go_through_breaker_and_channel_tracking() {
+        let config = Config {
+            max_consecutive_failures: 3,
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        let executor = CommandExecutor::new(sender.clone(), config, stats.clone());
+
+        sender.fail_next_with_status(3, 403);
+        assert!(executor.execute_mark_wished(100, 1, "⭐").await.is_err());
+        assert!(executor.turn_page(100, 2, None, 0, None).await.is_err());
+        assert!(executor.send(100, "$wa").await.is_err());
+        assert_eq!(executor.paused_actions().await, vec![ActionKind::Command]);
+        assert!(!stats.is_channel_accessible(100).await);
+
+        assert!(executor.turn_page(100, 2, None, 0, None).await.is_err());
+        assert!(sender.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_manual_roll_uses_configured_yield() {
+        let mut config = Config {
+            roll_commands: vec!["$w10".to_string()],
+            ..Config::default()
+        };
+        config.set_roll_yields("$w10=10").unwrap();
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        stats.set_rolls_remaining(12);
+        let executor = CommandExecutor::new(sender.clone(), config, stats.clone());
+
+        executor.execute_manual_roll(100).await.unwrap();
+        assert_eq!(stats.get_rolls_remaining(), 2);
+        executor.execute_manual_roll(100).await.unwrap();
+        assert_eq!(stats.get_rolls_remaining(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_first_roll_waits_for_startup_delay() {
+        let config = Config {
+            auto_daily: false,
+            startup_roll_delay_seconds: 4,
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        stats.set_rolls_remaining(1);
+        let executor = Arc::new(CommandExecutor::new(sender.clone(), config, stats.clone()));
+        let scheduler = RollScheduler::new(executor, vec![100], stats);
+        let handle = tokio::spawn(async move { scheduler.run().await });
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(3900)).await;
+        assert!(sender.calls().is_empty());
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+        assert!(sender.calls().iter().any(|c| matches!(c, SentCall::Message { content, .. } if content == "$wa")));
+        handle.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_channel_switch_waits_for_inter_channel_delay() {
+        let config = Config {
+            auto_daily: false,
+            startup_roll_delay_seconds: 0,
+            inter_channel_delay_ms: 5000,
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        stats.set_rolls_remaining(2);
+        let executor = Arc::new(CommandExecutor::new(sender.clone(), config, stats.clone()));
+        let scheduler = RollScheduler::new(executor, vec![100, 200], stats);
+        let handle = tokio::spawn(async move { scheduler.run().await });
+
+        let rolled_channels = || -> Vec<u64> {
+            sender.calls().into_iter().filter_map(|c| match c {
+                SentCall::Message { channel_id, .. } => Some(channel_id),
+                _ => None,
+            }).collect()
+        };
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(4900)).await;
+        assert_eq!(rolled_channels(), vec![100]);
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+        assert_eq!(rolled_channels(), vec![100, 200]);
+        handle.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduler_rolls_first_command_off_cooldown() {
+        let config = Config {
+            auto_daily: false,
+            startup_roll_delay_seconds: 0,
+            roll_commands: vec!["$wa".to_string(), "$ha".to_string()],
+            roll_command_cooldowns: HashMap::from([("$ha".to_string(), 0)]),
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        stats.set_rolls_remaining(3);
+        let executor = Arc::new(CommandExecutor::new(sender.clone(), config, stats.clone()));
+        let scheduler = RollScheduler::new(executor, vec![100], stats);
+        let handle = tokio::spawn(async move { scheduler.run().await });
+        let rolled = || -> Vec<String> {
+            sender.calls().into_iter().filter_map(|c| match c {
+                SentCall::Message { content, .. } => Some(content),
+                _ => None,
+            }).collect()
+        };
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+        assert_eq!(rolled(), vec!["$wa", "$ha", "$ha"]);
+        handle.abort();
+
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        stats.set_rolls_remaining(10);
+        let config = Config { auto_daily: false, startup_roll_delay_seconds: 0, ..Config::default() };
+        let executor = Arc::new(CommandExecutor::new(sender.clone(), config, stats.clone()));
+        let scheduler = RollScheduler::new(executor.clone(), vec![100], stats);
+        let handle = tokio::spawn(async move { scheduler.run().await });
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(600)).await;
+        assert_eq!(sender.calls().len(), 2);
+        let wait = executor.get_time_until_next_roll(100).await.unwrap();
+        assert!(wait > chrono::Duration::minutes(59));
+        handle.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sent_commands_are_recognised_as_echoes() {
+        let executor = CommandExecutor::new(MockSender::default(), Config::default(), Stats::new());
+        executor.send(100, "$wa").await.unwrap();
+
+        assert!(executor.is_recent_command(100, " $wa ").await);
+        assert!(!executor.is_recent_command(200, "$wa").await);
+        assert!(!executor.is_recent_command(100, "$ha").await);
+
+        tokio::time::advance(tokio::time::Duration::from_secs(ECHO_SUPPRESS_SECS + 1)).await;
+        assert!(!executor.is_recent_command(100, "$wa").await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_daily_kakera_reacts_only_after_daily() {
+        let config = Config {
+            auto_daily: true,
+            daily_kakera_react: true,
+            daily_commands: vec!["$dk".to_string()],
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let executor = CommandExecutor::new(sender.clone(), config, Stats::new());
+
+        assert!(!executor.execute_daily_kakera(100, 7, None, 0, None).await.unwrap());
+        executor.execute_daily_commands(100).await.unwrap();
+        assert!(!executor.execute_daily_kakera(200, 8, None, 0, None).await.unwrap());
+        executor.stats.set_paused(true);
+        assert!(!executor.execute_daily_kakera(100, 7, None, 0, None).await.unwrap());
+        executor.stats.set_paused(false);
+        assert!(executor.execute_daily_kakera(100, 7, None, 0, None).await.unwrap());
+
+        assert_eq!(
+            sender.calls().last(),
+            Some(&SentCall::Reaction { channel_id: 100, message_id: 7, emoji: DAILY_KAKERA_EMOJI.to_string() })
+        );
+    }
+
+    #[test]
+    fn test_channel_roll_command_overrides() {
+        let executor = CommandExecutor::new(MockSender::default(), Config::default(), Stats::new())
+            .with_channel_roll_commands(HashMap::from([(200, vec!["$w".to_string()])]));
+
+        assert_eq!(executor.roll_command_for(100).map(String::as_str), Some("$wa"));
+        assert_eq!(executor.roll_command_for(200).map(String::as_str), Some("$w"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_channel_override_rotates_through_its_commands() {
+        let config = Config {
+            auto_daily: false,
+            startup_roll_delay_seconds: 0,
+            roll_cooldown_seconds: 0,
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        stats.set_rolls_remaining(4);
+        let executor = Arc::new(
+            CommandExecutor::new(sender.clone(), config, stats.clone())
+                .with_channel_roll_commands(HashMap::from([(222, vec!["$w".to_string(), "$wg".to_string()])])),
+        );
+        let scheduler = RollScheduler::new(executor, vec![222], stats);
+        let handle = tokio::spawn(async move { scheduler.run().await });
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+        let rolled: Vec<String> = sender.calls().into_iter().filter_map(|c| match c {
+            SentCall::Message { content, .. } => Some(content),
+            _ => None,
+        }).collect();
+        assert_eq!(rolled, vec!["$w", "$wg", "$w", "$wg"]);
+        handle.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduled_commands_run_while_idle() {
+        let config = Config {
+            auto_daily: false,
+            auto_idle: true,
+            startup_roll_delay_seconds: 0,
+            scheduled_commands: vec![ScheduledCommand { command: "$dk".to_string(), schedule: CommandSchedule::Every(3600) }],
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let stats = Stats::new();
+        stats.set_idle_until(Some(Utc::now() + chrono::Duration::hours(1))).await;
+        let executor = Arc::new(CommandExecutor::new(sender.clone(), config, stats.clone()));
+        let scheduler = RollScheduler::new(executor, vec![100], stats.clone());
+        let handle = tokio::spawn(async move { scheduler.run().await });
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+        assert!(stats.get_idle_until().await.is_some());
+        assert_eq!(sender.calls(), vec![SentCall::Message { channel_id: 100, content: "$dk".to_string() }]);
+        handle.abort();
+    }
+
+    #[test]
+    fn test_daily_scheduled_command_fires_once_per_local_day() {
+        let mut task = ScheduledTask::new(ScheduledCommand {
+            command: "$dk".to_string(),
+            schedule: CommandSchedule::DailyAt(NaiveTime::from_hms_opt(12, 30, 0).unwrap()),
+        });
+        let at = |day: u32, hour: u32, minute: u32| {
+            NaiveDate::from_ymd_opt(2024, 5, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+        };
+
+        assert_eq!(task.take_if_due(at(1, 12, 0)), None);
+        assert_eq!(task.take_if_due(at(1, 12, 30)), Some("$dk".to_string()));
+        assert_eq!(task.take_if_due(at(1, 23, 59)), None);
+        assert_eq!(task.take_if_due(at(2, 12, 29)), None);
+        assert_eq!(task.take_if_due(at(2, 12, 31)), Some("$dk".to_string()));
+        assert_eq!(task.take_if_due(at(3, 23, 0)), Some("$dk".to_string()));
+        assert_eq!(task.take_if_due(at(4, 12, 30)), Some("$dk".to_string()));
+    }
+}
@@ -1,5 +1,170 @@
 use crate::database::{Database, SavedConfig};
+use crate::parser::{Gender, MudaeLanguage, ParsedCharacter};
+use crate::rules::ClaimRules;
+use crate::stats::{ClaimDecision, KakeraPower};
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
+use tracing::warn;
+
+pub const WISHED_CLAIM_SCORE: f64 = 1_000_000_000.0;
+pub const MIN_TUI_TICK_MS: u64 = 20;
+pub const DEFAULT_ROLL_YIELD: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RollSpread {
+    #[default]
+    Burst,
+    Spread,
+}
+
+impl RollSpread {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "burst" => Some(RollSpread::Burst),
+            "spread" => Some(RollSpread::Spread),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RollSpread::Burst => "burst",
+            RollSpread::Spread => "spread",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayTimezone {
+    #[default]
+    Local,
+    Utc,
+}
+
+impl DisplayTimezone {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "local" => Some(DisplayTimezone::Local),
+            "utc" => Some(DisplayTimezone::Utc),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DisplayTimezone::Local => "local",
+            DisplayTimezone::Utc => "utc",
+        }
+    }
+
+    pub fn format(self, timestamp: DateTime<Utc>, fmt: &str) -> String {
+        match self {
+            DisplayTimezone::Local => timestamp.with_timezone(&Local).format(fmt).to_string(),
+            DisplayTimezone::Utc => timestamp.format(fmt).to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionLimitAction {
+    #[default]
+    Stop,
+    Pause,
+}
+
+impl SessionLimitAction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "stop" => Some(SessionLimitAction::Stop),
+            "pause" => Some(SessionLimitAction::Pause),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SessionLimitAction::Stop => "stop",
+            SessionLimitAction::Pause => "pause",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DelayDistribution {
+    #[default]
+    Uniform,
+    Gaussian,
+}
+
+impl DelayDistribution {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "uniform" => Some(DelayDistribution::Uniform),
+            "gaussian" => Some(DelayDistribution::Gaussian),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DelayDistribution::Uniform => "uniform",
+            DelayDistribution::Gaussian => "gaussian",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandSchedule {
+    Every(u64),
+    DailyAt(NaiveTime),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledCommand {
+    pub command: String,
+    pub schedule: CommandSchedule,
+}
+
+impl ScheduledCommand {
+    pub fn parse(entry: &str) -> Option<Self> {
+        let (command, schedule) = entry.rsplit_once('@')?;
+        let command = command.trim();
+        let schedule = schedule.trim();
+        if command.is_empty() {
+            return None;
+        }
+
+        let schedule = if let Some((hours, minutes)) = crate::utils::parse_time(schedule) {
+            CommandSchedule::DailyAt(NaiveTime::from_hms_opt(hours, minutes, 0)?)
+        } else {
+            match schedule.parse::<u64>() {
+                Ok(secs) if secs > 0 => CommandSchedule::Every(secs),
+                _ => return None,
+            }
+        };
+
+        Some(Self {
+            command: command.to_string(),
+            schedule,
+        })
+    }
+
+    pub fn parse_list<S: AsRef<str>>(entries: &[S]) -> Vec<Self> {
+        entries.iter().filter_map(|e| Self::parse(e.as_ref())).collect()
+    }
+}
+
+impl fmt::Display for ScheduledCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.schedule {
+            CommandSchedule::Every(secs) => write!(f, "{}@{}", self.command, secs),
+            CommandSchedule::DailyAt(time) => write!(f, "{}@{}", self.command, time.format("%H:%M")),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -14,6 +179,61 @@ pub struct Config {
     pub auto_verify: bool,
     pub fuzzy_match: bool,
     pub fuzzy_threshold: f64,
+    pub on_reconnect_commands: Vec<String>,
+    pub scheduled_commands: Vec<ScheduledCommand>,
+    pub claim_priority_weight: f64,
+    pub claim_kakera_weight: f64,
+    pub auto_idle: bool,
+    pub strict_wishlist_load: bool,
+    pub default_rolls: u64,
+    pub claim_gender_filter: Option<Gender>,
+    pub claim_kakera_min: u32,
+    pub claim_kakera_max: u32,
+    pub show_user_messages: bool,
+    pub verify_delay_min_ms: u64,
+    pub verify_delay_max_ms: u64,
+    pub claim_rules: ClaimRules,
+    pub roll_spread: RollSpread,
+    pub halt_key: String,
+    pub encrypt_token: bool,
+    pub attempt_claim_on_belongs_to: bool,
+    pub display_timezone: DisplayTimezone,
+    pub roll_command_cooldowns: HashMap<String, u64>,
+    pub catchup_messages: u32,
+    pub daily_commands: Vec<String>,
+    pub kakera_power_min: u32,
+    pub claim_retries: u32,
+    pub claim_retry_window_ms: u64,
+    pub auto_divorce_non_wished: bool,
+    pub auto_divorce_max_kakera: u32,
+    pub auto_divorce_commands: Vec<String>,
+    pub stats_save_interval_seconds: u64,
+    pub tui_tick_ms: u64,
+    pub mudae_language: MudaeLanguage,
+    pub ascii_mode: bool,
+    pub auto_verify_on_start: bool,
+    pub daily_kakera_react: bool,
+    pub mark_wished_emoji: Option<String>,
+    pub trust_mudae_wish: bool,
+    pub startup_roll_delay_seconds: u64,
+    pub post_reset_roll_delay_seconds: u64,
+    pub inter_channel_delay_ms: u64,
+    pub shuffle_channels: bool,
+    pub claim_emoji: String,
+    pub track_seen_characters: bool,
+    pub max_session_hours: Option<u64>,
+    pub session_limit_action: SessionLimitAction,
+    pub include_threads: bool,
+    pub remote_control_user_ids: Vec<u64>,
+    pub max_consecutive_failures: u32,
+    pub claim_confirm_timeout_seconds: u64,
+    pub fuzzy_dedup: bool,
+    pub roll_command_yields: HashMap<String, u32>,
+    pub channel_refresh_minutes: u64,
+    pub auth_error_halt_count: u32,
+    pub claim_grace_seconds: u64,
+    pub delay_distribution: DelayDistribution,
+    pub include_dms: bool,
 }
 
 impl Config {
@@ -33,6 +253,61 @@ impl Config {
             wishlist_enabled: self.wishlist_enabled,
             fuzzy_match: self.fuzzy_match,
             fuzzy_threshold: self.fuzzy_threshold,
+            on_reconnect_commands: self.on_reconnect_commands.clone(),
+            scheduled_commands: self.scheduled_commands.iter().map(|c| c.to_string()).collect(),
+            claim_priority_weight: self.claim_priority_weight,
+            claim_kakera_weight: self.claim_kakera_weight,
+            auto_idle: self.auto_idle,
+            strict_wishlist_load: self.strict_wishlist_load,
+            default_rolls: self.default_rolls,
+            claim_gender_filter: self.claim_gender_filter.map(|g| g.as_str().to_string()).unwrap_or_default(),
+            claim_kakera_min: self.claim_kakera_min,
+            claim_kakera_max: self.claim_kakera_max,
+            show_user_messages: self.show_user_messages,
+            verify_delay_min_ms: self.verify_delay_min_ms,
+            verify_delay_max_ms: self.verify_delay_max_ms,
+            claim_rules: self.claim_rules.to_string(),
+            roll_spread: self.roll_spread.as_str().to_string(),
+            halt_key: self.halt_key.clone(),
+            encrypt_token: self.encrypt_token,
+            attempt_claim_on_belongs_to: self.attempt_claim_on_belongs_to,
+            display_timezone: self.display_timezone.as_str().to_string(),
+            roll_command_cooldowns: self.roll_cooldown_entries(),
+            catchup_messages: self.catchup_messages,
+            daily_commands: self.daily_commands.clone(),
+            kakera_power_min: self.kakera_power_min,
+            claim_retries: self.claim_retries,
+            claim_retry_window_ms: self.claim_retry_window_ms,
+            auto_divorce_non_wished: self.auto_divorce_non_wished,
+            auto_divorce_max_kakera: self.auto_divorce_max_kakera,
+            auto_divorce_commands: self.auto_divorce_commands.clone(),
+            stats_save_interval_seconds: self.stats_save_interval_seconds,
+            tui_tick_ms: self.tui_tick_ms,
+            mudae_language: self.mudae_language.as_str().to_string(),
+            ascii_mode: self.ascii_mode,
+            auto_verify_on_start: self.auto_verify_on_start,
+            daily_kakera_react: self.daily_kakera_react,
+            mark_wished_emoji: self.mark_wished_emoji.clone().unwrap_or_default(),
+            trust_mudae_wish: self.trust_mudae_wish,
+            startup_roll_delay_seconds: self.startup_roll_delay_seconds,
+            post_reset_roll_delay_seconds: self.post_reset_roll_delay_seconds,
+            inter_channel_delay_ms: self.inter_channel_delay_ms,
+            shuffle_channels: self.shuffle_channels,
+            claim_emoji: self.claim_emoji.clone(),
+            track_seen_characters: self.track_seen_characters,
+            max_session_hours: self.max_session_hours.unwrap_or(0),
+            session_limit_action: self.session_limit_action.as_str().to_string(),
+            include_threads: self.include_threads,
+            remote_control_user_ids: self.remote_control_user_ids.clone(),
+            max_consecutive_failures: self.max_consecutive_failures,
+            claim_confirm_timeout_seconds: self.claim_confirm_timeout_seconds,
+            fuzzy_dedup: self.fuzzy_dedup,
+            roll_command_yields: self.roll_yield_entries(),
+            channel_refresh_minutes: self.channel_refresh_minutes,
+            auth_error_halt_count: self.auth_error_halt_count,
+            claim_grace_seconds: self.claim_grace_seconds,
+            delay_distribution: self.delay_distribution.as_str().to_string(),
+            include_dms: self.include_dms,
         };
         db.save_config(&saved)
     }
@@ -50,7 +325,247 @@ impl Config {
             auto_verify: true,
             fuzzy_match: saved.fuzzy_match,
             fuzzy_threshold: saved.fuzzy_threshold,
+            on_reconnect_commands: saved.on_reconnect_commands,
+            scheduled_commands: ScheduledCommand::parse_list(&saved.scheduled_commands),
+            claim_priority_weight: saved.claim_priority_weight,
+            claim_kakera_weight: saved.claim_kakera_weight,
+            auto_idle: saved.auto_idle,
+            strict_wishlist_load: saved.strict_wishlist_load,
+            default_rolls: saved.default_rolls,
+            claim_gender_filter: Gender::parse(&saved.claim_gender_filter),
+            claim_kakera_min: saved.claim_kakera_min,
+            claim_kakera_max: saved.claim_kakera_max,
+            show_user_messages: saved.show_user_messages,
+            verify_delay_min_ms: saved.verify_delay_min_ms,
+            verify_delay_max_ms: saved.verify_delay_max_ms,
+            claim_rules: ClaimRules::parse(&saved.claim_rules).unwrap_or_else(|| {
+                warn!("Invalid claim rules \"{}\", falling back to \"{}\"", saved.claim_rules, ClaimRules::default());
+                ClaimRules::default()
+            }),
+            roll_spread: RollSpread::parse(&saved.roll_spread).unwrap_or_default(),
+            halt_key: saved.halt_key,
+            encrypt_token: saved.encrypt_token,
+            attempt_claim_on_belongs_to: saved.attempt_claim_on_belongs_to,
+            display_timezone: DisplayTimezone::parse(&saved.display_timezone).unwrap_or_default(),
+            roll_command_cooldowns: Self::parse_roll_cooldowns(&saved.roll_command_cooldowns),
+            catchup_messages: saved.catchup_messages,
+            daily_commands: saved.daily_commands,
+            kakera_power_min: saved.kakera_power_min,
+            claim_retries: saved.claim_retries,
+            claim_retry_window_ms: saved.claim_retry_window_ms,
+            auto_divorce_non_wished: saved.auto_divorce_non_wished,
+            auto_divorce_max_kakera: saved.auto_divorce_max_kakera,
+            auto_divorce_commands: saved.auto_divorce_commands,
+            stats_save_interval_seconds: saved.stats_save_interval_seconds,
+            tui_tick_ms: saved.tui_tick_ms,
+            mudae_language: MudaeLanguage::parse(&saved.mudae_language).unwrap_or_default(),
+            ascii_mode: saved.ascii_mode,
+            auto_verify_on_start: saved.auto_verify_on_start,
+            daily_kakera_react: saved.daily_kakera_react,
+            mark_wished_emoji: Some(saved.mark_wished_emoji).filter(|e| !e.is_empty()),
+            trust_mudae_wish: saved.trust_mudae_wish,
+            startup_roll_delay_seconds: saved.startup_roll_delay_seconds,
+            post_reset_roll_delay_seconds: saved.post_reset_roll_delay_seconds,
+            inter_channel_delay_ms: saved.inter_channel_delay_ms,
+            shuffle_channels: saved.shuffle_channels,
+            claim_emoji: saved.claim_emoji,
+            track_seen_characters: saved.track_seen_characters,
+            max_session_hours: Some(saved.max_session_hours).filter(|hours| *hours > 0),
+            session_limit_action: SessionLimitAction::parse(&saved.session_limit_action).unwrap_or_default(),
+            include_threads: saved.include_threads,
+            remote_control_user_ids: saved.remote_control_user_ids,
+            max_consecutive_failures: saved.max_consecutive_failures,
+            claim_confirm_timeout_seconds: saved.claim_confirm_timeout_seconds,
+            fuzzy_dedup: saved.fuzzy_dedup,
+            roll_command_yields: Self::parse_roll_yields(&saved.roll_command_yields),
+            channel_refresh_minutes: saved.channel_refresh_minutes,
+            auth_error_halt_count: saved.auth_error_halt_count,
+            claim_grace_seconds: saved.claim_grace_seconds,
+            delay_distribution: DelayDistribution::parse(&saved.delay_distribution).unwrap_or_default(),
+            include_dms: saved.include_dms,
+        }
+    }
+
+    pub fn wishlist_path(&self) -> PathBuf {
+        Database::resolve_data_path(&self.wishlist_file)
+    }
+
+    pub fn claim_score(&self, is_wished: bool, priority: u8, kakera_value: Option<u32>) -> f64 {
+        let wished_score = if is_wished { WISHED_CLAIM_SCORE } else { 0.0 };
+        wished_score
+            + priority as f64 * self.claim_priority_weight
+            + kakera_value.unwrap_or(0) as f64 * self.claim_kakera_weight
+    }
+
+    pub fn kakera_range_enabled(&self) -> bool {
+        self.claim_kakera_min > 0 || self.claim_kakera_max > 0
+    }
+
+    pub fn kakera_in_range(&self, kakera_value: Option<u32>) -> bool {
+        let Some(value) = kakera_value else {
+            return true;
+        };
+        value >= self.claim_kakera_min && (self.claim_kakera_max == 0 || value <= self.claim_kakera_max)
+    }
+
+    pub fn should_claim_character(&self, character: &ParsedCharacter, is_wished: bool, soft_wish: bool) -> ClaimDecision {
+        if soft_wish {
+            return ClaimDecision::SoftWish;
+        }
+        if is_wished {
+            return ClaimDecision::Claiming { wished: true };
         }
+        if self.claim_rules.matches(character, false) {
+            return ClaimDecision::Claiming { wished: false };
+        }
+        if !self.kakera_range_enabled() {
+            return if self.claim_rules == ClaimRules::default() { ClaimDecision::NotWished } else { ClaimDecision::NoRuleMatch };
+        }
+        match character.kakera_value {
+            _ if self.kakera_in_range(character.kakera_value) => ClaimDecision::Claiming { wished: false },
+            Some(value) if value < self.claim_kakera_min => ClaimDecision::BelowMinKakera,
+            _ => ClaimDecision::AboveMaxKakera,
+        }
+    }
+
+    pub fn should_auto_divorce(&self, fuzzy_match: bool, kakera_value: Option<u32>) -> bool {
+        self.auto_divorce_non_wished
+            && fuzzy_match
+            && matches!(kakera_value, Some(value) if value < self.auto_divorce_max_kakera)
+    }
+
+    pub fn kakera_react_allowed(&self, power: Option<KakeraPower>) -> bool {
+        self.auto_react_kakera && power.is_none_or(|p| p.can_react && p.power >= self.kakera_power_min)
+    }
+
+    pub fn spread_interval(&self, rolls_remaining: u64, until_reset: Option<chrono::Duration>) -> std::time::Duration {
+        let window = until_reset
+            .filter(|d| *d > chrono::Duration::zero())
+            .and_then(|d| d.to_std().ok())
+            .unwrap_or_else(|| std::time::Duration::from_secs(self.roll_cooldown_seconds));
+        let rolls = if until_reset.is_some() { rolls_remaining } else { self.default_rolls };
+        window / rolls.max(1) as u32
+    }
+
+    pub fn session_time_left(&self, started_at: DateTime<Utc>, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        let limit = chrono::Duration::hours(self.max_session_hours? as i64);
+        Some((limit - (now - started_at)).max(chrono::Duration::zero()))
+    }
+
+    pub fn roll_cooldown_for(&self, command: &str) -> u64 {
+        self.roll_command_cooldowns
+            .get(command)
+            .copied()
+            .unwrap_or(self.roll_cooldown_seconds)
+    }
+
+    pub fn parse_roll_cooldowns<S: AsRef<str>>(entries: &[S]) -> HashMap<String, u64> {
+        entries
+            .iter()
+            .filter_map(|entry| match Self::parse_roll_command(entry.as_ref()) {
+                Ok((cmd, Some(secs))) => Some((cmd, secs)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn roll_cooldown_entries(&self) -> Vec<String> {
+        self.roll_commands
+            .iter()
+            .filter_map(|cmd| self.roll_command_cooldowns.get(cmd).map(|secs| format!("{}:{}", cmd, secs)))
+            .collect()
+    }
+
+    fn parse_roll_command(entry: &str) -> Result<(String, Option<u64>), String> {
+        let (command, cooldown) = match entry.rsplit_once(':') {
+            Some((command, secs)) => match secs.trim().parse::<u64>() {
+                Ok(secs) if secs > 0 => (command, Some(secs)),
+                _ => return Err(format!("Invalid cooldown in '{}'", entry.trim())),
+            },
+            None => (entry, None),
+        };
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(format!("Missing command in '{}'", entry.trim()));
+        }
+        Ok((command.to_string(), cooldown))
+    }
+
+    pub fn set_roll_commands(&mut self, input: &str) -> Result<(), String> {
+        let mut commands = Vec::new();
+        let mut cooldowns = HashMap::new();
+        for entry in input.split(',').filter(|e| !e.trim().is_empty()) {
+            let (command, cooldown) = Self::parse_roll_command(entry)?;
+            if let Some(secs) = cooldown {
+                cooldowns.insert(command.clone(), secs);
+            }
+            commands.push(command);
+        }
+        if commands.is_empty() {
+            return Err("Enter at least one command".to_string());
+        }
+        self.roll_commands = commands;
+        self.roll_command_cooldowns = cooldowns;
+        Ok(())
+    }
+
+    pub fn roll_commands_input(&self) -> String {
+        self.roll_commands
+            .iter()
+            .map(|cmd| match self.roll_command_cooldowns.get(cmd) {
+                Some(secs) => format!("{}:{}", cmd, secs),
+                None => cmd.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn roll_yield_for(&self, command: &str) -> u32 {
+        self.roll_command_yields.get(command).copied().unwrap_or(DEFAULT_ROLL_YIELD)
+    }
+
+    pub fn parse_roll_yields<S: AsRef<str>>(entries: &[S]) -> HashMap<String, u32> {
+        entries
+            .iter()
+            .filter_map(|entry| Self::parse_roll_yield(entry.as_ref()).ok())
+            .collect()
+    }
+
+    fn parse_roll_yield(entry: &str) -> Result<(String, u32), String> {
+        let Some((command, rolls)) = entry.rsplit_once('=') else {
+            return Err(format!("Expected command=rolls in '{}'", entry.trim()));
+        };
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(format!("Missing command in '{}'", entry.trim()));
+        }
+        match rolls.trim().parse::<u32>() {
+            Ok(rolls) if rolls > 0 => Ok((command.to_string(), rolls)),
+            _ => Err(format!("Invalid roll count in '{}'", entry.trim())),
+        }
+    }
+
+    fn roll_yield_entries(&self) -> Vec<String> {
+        let mut entries: Vec<String> = self.roll_command_yields
+            .iter()
+            .map(|(cmd, rolls)| format!("{}={}", cmd, rolls))
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    pub fn set_roll_yields(&mut self, input: &str) -> Result<(), String> {
+        let yields = input
+            .split(',')
+            .filter(|e| !e.trim().is_empty())
+            .map(Self::parse_roll_yield)
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        self.roll_command_yields = yields;
+        Ok(())
+    }
+
+    pub fn roll_yields_input(&self) -> String {
+        self.roll_yield_entries().join(", ")
     }
 
     pub fn mudae_bot_id() -> u64 {
@@ -72,6 +587,282 @@ impl Default for Config {
             auto_verify: true,
             fuzzy_match: true,
             fuzzy_threshold: 0.8,
+            on_reconnect_commands: Vec::new(),
+            scheduled_commands: Vec::new(),
+            claim_priority_weight: 10000.0,
+            claim_kakera_weight: 1.0,
+            auto_idle: false,
+            strict_wishlist_load: false,
+            default_rolls: 10,
+            claim_gender_filter: None,
+            claim_kakera_min: 0,
+            claim_kakera_max: 0,
+            show_user_messages: true,
+            verify_delay_min_ms: 1500,
+            verify_delay_max_ms: 15000,
+            claim_rules: ClaimRules::default(),
+            roll_spread: RollSpread::Burst,
+            halt_key: "F9".to_string(),
+            encrypt_token: false,
+            attempt_claim_on_belongs_to: false,
+            display_timezone: DisplayTimezone::Local,
+            roll_command_cooldowns: HashMap::new(),
+            catchup_messages: 0,
+            daily_commands: vec!["$daily".to_string(), "$dk".to_string()],
+            kakera_power_min: 0,
+            claim_retries: 2,
+            claim_retry_window_ms: 5000,
+            auto_divorce_non_wished: false,
+            auto_divorce_max_kakera: 100,
+            auto_divorce_commands: vec!["$divorce {name}".to_string(), "y".to_string()],
+            stats_save_interval_seconds: 60,
+            tui_tick_ms: 100,
+            mudae_language: MudaeLanguage::English,
+            ascii_mode: false,
+            auto_verify_on_start: false,
+            daily_kakera_react: false,
+            mark_wished_emoji: None,
+            trust_mudae_wish: true,
+            startup_roll_delay_seconds: 5,
+            post_reset_roll_delay_seconds: 10,
+            inter_channel_delay_ms: 2000,
+            shuffle_channels: false,
+            claim_emoji: "💖".to_string(),
+            track_seen_characters: false,
+            max_session_hours: None,
+            session_limit_action: SessionLimitAction::Stop,
+            include_threads: false,
+            remote_control_user_ids: Vec::new(),
+            max_consecutive_failures: 5,
+            claim_confirm_timeout_seconds: 15,
+            fuzzy_dedup: false,
+            roll_command_yields: HashMap::new(),
+            channel_refresh_minutes: 0,
+            auth_error_halt_count: 5,
+            claim_grace_seconds: 0,
+            delay_distribution: DelayDistribution::Uniform,
+            include_dms: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scheduled_command() {
+        assert_eq!(
+            ScheduledCommand::parse("$dk@72000"),
+            Some(ScheduledCommand { command: "$dk".to_string(), schedule: CommandSchedule::Every(72000) })
+        );
+        assert_eq!(
+            ScheduledCommand::parse(" $p @ 12:30 "),
+            Some(ScheduledCommand {
+                command: "$p".to_string(),
+                schedule: CommandSchedule::DailyAt(NaiveTime::from_hms_opt(12, 30, 0).unwrap()),
+            })
+        );
+        assert_eq!(ScheduledCommand::parse("$dk"), None);
+        assert_eq!(ScheduledCommand::parse("$dk@0"), None);
+        assert_eq!(ScheduledCommand::parse("@60"), None);
+    }
+
+    #[test]
+    fn test_scheduled_command_round_trip() {
+        for entry in ["$dk@72000", "$p@08:05"] {
+            assert_eq!(ScheduledCommand::parse(entry).unwrap().to_string(), entry);
+        }
+    }
+
+    #[test]
+    fn test_default_claim_score_ordering() {
+        let config = Config::default();
+        assert!(config.claim_score(true, 0, Some(0)) > config.claim_score(false, 255, Some(5000)));
+        assert!(config.claim_score(true, 2, Some(0)) > config.claim_score(true, 1, Some(5000)));
+        assert!(config.claim_score(true, 1, Some(300)) > config.claim_score(true, 1, Some(200)));
+    }
+
+    fn roll(kakera_value: Option<u32>) -> ParsedCharacter {
+        ParsedCharacter {
+            name: "Rem".to_string(),
+            series: "Re:Zero".to_string(),
+            kakera_value,
+            image_url: None,
+            is_claimed: false,
+            claim_rank: None,
+            is_wished: false,
+            gender: None,
+            claim_emoji: None,
+        }
+    }
+
+    fn range_config(min: u32, max: u32) -> Config {
+        Config {
+            claim_kakera_min: min,
+            claim_kakera_max: max,
+            ..Config::default()
         }
     }
+
+    #[test]
+    fn test_kakera_range_below_min() {
+        let config = range_config(100, 500);
+        assert_eq!(config.should_claim_character(&roll(Some(99)), false, false), ClaimDecision::BelowMinKakera);
+        assert!(config.should_claim_character(&roll(Some(99)), true, false).is_claim());
+    }
+
+    #[test]
+    fn test_kakera_range_in_range() {
+        let config = range_config(100, 500);
+        assert!(config.should_claim_character(&roll(Some(100)), false, false).is_claim());
+        assert!(config.should_claim_character(&roll(Some(500)), false, false).is_claim());
+        assert!(range_config(100, 0).should_claim_character(&roll(Some(5000)), false, false).is_claim());
+    }
+
+    #[test]
+    fn test_kakera_range_above_max() {
+        let config = range_config(100, 500);
+        assert_eq!(config.should_claim_character(&roll(Some(501)), false, false), ClaimDecision::AboveMaxKakera);
+        assert_eq!(config.should_claim_character(&roll(Some(501)), true, false), ClaimDecision::Claiming { wished: true });
+    }
+
+    #[test]
+    fn test_kakera_range_unknown_value() {
+        assert!(range_config(100, 500).should_claim_character(&roll(None), false, false).is_claim());
+        assert_eq!(range_config(0, 0).should_claim_character(&roll(None), false, false), ClaimDecision::NotWished);
+    }
+
+    #[test]
+    fn test_wished_rolls_claimed_regardless_of_rules() {
+        let config = Config {
+            claim_rules: ClaimRules::parse("kakera>=500").unwrap(),
+            ..Config::default()
+        };
+        assert_eq!(config.should_claim_character(&roll(Some(10)), true, false), ClaimDecision::Claiming { wished: true });
+        assert_eq!(config.should_claim_character(&roll(Some(600)), false, false), ClaimDecision::Claiming { wished: false });
+        assert_eq!(config.should_claim_character(&roll(Some(10)), false, false), ClaimDecision::NoRuleMatch);
+        assert_eq!(Config::default().should_claim_character(&roll(Some(10)), false, false), ClaimDecision::NotWished);
+        assert_eq!(config.should_claim_character(&roll(Some(600)), true, true), ClaimDecision::SoftWish);
+    }
+
+    #[test]
+    fn test_roll_spread_parse() {
+        assert_eq!(RollSpread::parse("Spread"), Some(RollSpread::Spread));
+        assert_eq!(RollSpread::parse("burst"), Some(RollSpread::Burst));
+        assert_eq!(RollSpread::parse(""), None);
+        assert_eq!(RollSpread::default(), RollSpread::Burst);
+    }
+
+    #[test]
+    fn test_session_time_left() {
+        let started = Utc::now();
+        let mut config = Config::default();
+        assert_eq!(config.session_time_left(started, started), None);
+
+        config.max_session_hours = Some(2);
+        let later = started + chrono::Duration::minutes(90);
+        assert_eq!(config.session_time_left(started, later), Some(chrono::Duration::minutes(30)));
+        let past = started + chrono::Duration::hours(3);
+        assert_eq!(config.session_time_left(started, past), Some(chrono::Duration::zero()));
+    }
+
+    #[test]
+    fn test_should_auto_divorce() {
+        let config = Config {
+            auto_divorce_non_wished: true,
+            auto_divorce_max_kakera: 100,
+            ..Config::default()
+        };
+
+        assert!(config.should_auto_divorce(true, Some(50)));
+        assert!(!config.should_auto_divorce(true, None));
+        assert!(!config.should_auto_divorce(true, Some(100)));
+        assert!(!config.should_auto_divorce(false, Some(50)));
+        assert!(!Config::default().should_auto_divorce(true, Some(50)));
+    }
+
+    #[test]
+    fn test_kakera_react_allowed() {
+        let config = Config {
+            auto_react_kakera: true,
+            kakera_power_min: 50,
+            ..Config::default()
+        };
+
+        assert!(config.kakera_react_allowed(None));
+        assert!(config.kakera_react_allowed(Some(KakeraPower { power: 110, can_react: true })));
+        assert!(!config.kakera_react_allowed(Some(KakeraPower { power: 40, can_react: true })));
+        assert!(!config.kakera_react_allowed(Some(KakeraPower { power: 110, can_react: false })));
+
+        let disabled = Config { auto_react_kakera: false, ..config };
+        assert!(!disabled.kakera_react_allowed(None));
+    }
+
+    #[test]
+    fn test_roll_command_cooldowns() {
+        let mut config = Config::default();
+        config.set_roll_commands("$wa:7200, $wg, $ha:600").unwrap();
+
+        assert_eq!(config.roll_commands, vec!["$wa", "$wg", "$ha"]);
+        assert_eq!(config.roll_cooldown_for("$wa"), 7200);
+        assert_eq!(config.roll_cooldown_for("$wg"), config.roll_cooldown_seconds);
+        assert_eq!(config.roll_commands_input(), "$wa:7200, $wg, $ha:600");
+
+        let restored = Config::parse_roll_cooldowns(&config.roll_cooldown_entries());
+        assert_eq!(restored, config.roll_command_cooldowns);
+
+        assert!(config.set_roll_commands("$wa:soon").is_err());
+        assert!(config.set_roll_commands(" , ").is_err());
+        assert_eq!(config.roll_commands, vec!["$wa", "$wg", "$ha"]);
+    }
+
+    #[test]
+    fn test_roll_command_yields() {
+        let mut config = Config::default();
+        assert_eq!(config.roll_yield_for("$wa"), DEFAULT_ROLL_YIELD);
+
+        config.set_roll_yields("$wa=1, $w10 = 10").unwrap();
+        assert_eq!(config.roll_yield_for("$w10"), 10);
+        assert_eq!(config.roll_yield_for("$ha"), DEFAULT_ROLL_YIELD);
+        assert_eq!(config.roll_yields_input(), "$w10=10, $wa=1");
+
+        let restored = Config::parse_roll_yields(&config.roll_yield_entries());
+        assert_eq!(restored, config.roll_command_yields);
+
+        assert!(config.set_roll_yields("$wa=0").is_err());
+        assert!(config.set_roll_yields("$wa").is_err());
+        assert_eq!(config.roll_yield_for("$w10"), 10);
+
+        config.set_roll_yields("").unwrap();
+        assert!(config.roll_command_yields.is_empty());
+    }
+
+    #[test]
+    fn test_display_timezone() {
+        assert_eq!(DisplayTimezone::parse("UTC"), Some(DisplayTimezone::Utc));
+        assert_eq!(DisplayTimezone::parse("local"), Some(DisplayTimezone::Local));
+        assert_eq!(DisplayTimezone::parse("mars"), None);
+
+        let timestamp = DateTime::parse_from_rfc3339("2024-05-01T13:45:30Z").unwrap().with_timezone(&Utc);
+        assert_eq!(DisplayTimezone::Utc.format(timestamp, "%H:%M:%S"), "13:45:30");
+        assert_eq!(
+            DisplayTimezone::Local.format(timestamp, "%H:%M:%S"),
+            timestamp.with_timezone(&Local).format("%H:%M:%S").to_string()
+        );
+    }
+
+    #[test]
+    fn test_spread_interval() {
+        let config = Config::default();
+        assert_eq!(config.spread_interval(10, None), std::time::Duration::from_secs(360));
+        assert_eq!(
+            config.spread_interval(4, Some(chrono::Duration::minutes(20))),
+            std::time::Duration::from_secs(300)
+        );
+        assert_eq!(
+            config.spread_interval(0, Some(chrono::Duration::minutes(20))),
+            std::time::Duration::from_secs(1200)
+        );
+    }
 }
@@ -0,0 +1,150 @@
+use anyhow::{anyhow, bail, Context, Result};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::digest::{digest, SHA256};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::num::NonZeroU32;
+
+const ENCRYPTED_PREFIX: &str = "enc1";
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const FINGERPRINT_PREFIX_CHARS: usize = 4;
+const FINGERPRINT_HASH_BYTES: usize = 4;
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(&format!("{}:", ENCRYPTED_PREFIX))
+}
+
+pub fn encrypt(plaintext: &str, passphrase: &str) -> Result<String> {
+    encrypt_with_iterations(plaintext, passphrase, PBKDF2_ITERATIONS)
+}
+
+pub fn decrypt(encoded: &str, passphrase: &str) -> Result<String> {
+    let parts: Vec<&str> = encoded.split(':').collect();
+    let [prefix, iterations, salt, nonce, ciphertext] = parts[..] else {
+        bail!("Stored token is not in the encrypted format");
+    };
+    if prefix != ENCRYPTED_PREFIX {
+        bail!("Stored token is not in the encrypted format");
+    }
+
+    let iterations: u32 = iterations.parse().context("Invalid iteration count in encrypted token")?;
+    let salt = decode_hex(salt)?;
+    let nonce: [u8; NONCE_LEN] = decode_hex(nonce)?
+        .try_into()
+        .map_err(|_| anyhow!("Invalid nonce in encrypted token"))?;
+    let mut in_out = decode_hex(ciphertext)?;
+
+    let key = derive_key(passphrase, &salt, iterations)?;
+    let plaintext = key
+        .open_in_place(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut in_out)
+        .map_err(|_| anyhow!("Wrong passphrase or corrupted token"))?;
+
+    String::from_utf8(plaintext.to_vec()).context("Decrypted token is not valid UTF-8")
+}
+
+pub fn fingerprint(token: &str) -> String {
+    let prefix: String = token.chars().take(FINGERPRINT_PREFIX_CHARS).collect();
+    let hash = digest(&SHA256, token.as_bytes());
+    format!(
+        "{}… ({} chars) #{}",
+        prefix,
+        token.chars().count(),
+        encode_hex(&hash.as_ref()[..FINGERPRINT_HASH_BYTES])
+    )
+}
+
+fn encrypt_with_iterations(plaintext: &str, passphrase: &str, iterations: u32) -> Result<String> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt).map_err(|_| anyhow!("Failed to generate salt"))?;
+    rng.fill(&mut nonce).map_err(|_| anyhow!("Failed to generate nonce"))?;
+
+    let key = derive_key(passphrase, &salt, iterations)?;
+    let mut in_out = plaintext.as_bytes().to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut in_out)
+        .map_err(|_| anyhow!("Failed to encrypt token"))?;
+
+    Ok(format!(
+        "{}:{}:{}:{}:{}",
+        ENCRYPTED_PREFIX,
+        iterations,
+        encode_hex(&salt),
+        encode_hex(&nonce),
+        encode_hex(&in_out)
+    ))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).ok_or_else(|| anyhow!("Iteration count must be positive"))?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations, salt, passphrase.as_bytes(), &mut key);
+    let unbound = UnboundKey::new(&CHACHA20_POLY1305, &key).map_err(|_| anyhow!("Failed to build encryption key"))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    if !value.is_ascii() || !value.len().is_multiple_of(2) {
+        bail!("Invalid hex in encrypted token");
+    }
+    value
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .context("Invalid hex in encrypted token")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let encoded = encrypt_with_iterations("my.discord.token", "hunter2", 1000).unwrap();
+        assert!(is_encrypted(&encoded));
+        assert!(!encoded.contains("my.discord.token"));
+        assert_eq!(decrypt(&encoded, "hunter2").unwrap(), "my.discord.token");
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails() {
+        let encoded = encrypt_with_iterations("my.discord.token", "hunter2", 1000).unwrap();
+        let err = decrypt(&encoded, "hunter3").unwrap_err();
+        assert!(err.to_string().contains("Wrong passphrase"));
+    }
+
+    #[test]
+    fn test_decode_hex_rejects_non_ascii() {
+        assert_eq!(decode_hex("00ff").unwrap(), vec![0x00, 0xff]);
+        assert!(decode_hex("éé").is_err());
+        assert!(decode_hex("0é0").is_err());
+        assert!(decode_hex("zz").is_err());
+    }
+
+    #[test]
+    fn test_plaintext_is_not_encrypted() {
+        assert!(!is_encrypted("my.discord.token"));
+        assert!(decrypt("my.discord.token", "hunter2").is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_hides_token() {
+        let token = "MTIzNDU2Nzg5.abcdef.secretpart";
+        let print = fingerprint(token);
+        assert!(print.starts_with("MTIz… (30 chars) #"));
+        assert!(!print.contains("secretpart"));
+        assert_eq!(print, fingerprint(token));
+        assert_ne!(print, fingerprint("MTIzNDU2Nzg5.abcdef.secretparu"));
+    }
+}
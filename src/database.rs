@@ -1,56 +1,195 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
+use crate::crypto;
+use crate::stats::HALT_MARKER_FILE;
 use rusqlite::{params, Connection};
-use std::path::PathBuf;
-use std::sync::Mutex;
-use tracing::{debug, info};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+use tracing::{debug, info, warn};
 
 const SCHEMA_SQL: &str = include_str!("../schema.sql");
+const WRITE_PROBE_FILE: &str = ".write-probe";
+
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 pub struct Database {
     conn: Mutex<Connection>,
+    reader: Option<Mutex<Connection>>,
 }
 
 #[allow(dead_code)]
 impl Database {
     pub fn new() -> Result<Self> {
         let db_path = Self::get_db_path()?;
+        Self::ensure_writable(&Self::data_dir())?;
         
         info!("Initializing database at: {:?}", db_path);
-        
+        Self::open_at(&db_path)
+    }
+
+    pub fn ensure_writable(dir: &Path) -> Result<()> {
+        let probe = dir.join(WRITE_PROBE_FILE);
+        let result = std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&probe, b"ok"))
+            .and_then(|_| std::fs::remove_file(&probe));
+        result.map_err(|e| {
+            anyhow::anyhow!(
+                "Data directory {} is not writable ({}). Pass --data-dir <path> to store data somewhere else.",
+                dir.display(),
+                e
+            )
+        })
+    }
+
+    pub fn open_at(db_path: &Path) -> Result<Self> {
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).context("Failed to create data directory")?;
         }
 
         let db_exists = db_path.exists();
-        let conn = Connection::open(&db_path).context("Failed to open database")?;
+        let conn = Connection::open(db_path).context("Failed to open database")?;
         
         if !db_exists {
             info!("Creating new database...");
         }
 
+        Self::configure_connection(&conn)?;
+
+        let mut db = Self::from_connection(conn)?;
+        let reader = Connection::open(db_path).context("Failed to open database reader")?;
+        reader.busy_timeout(Duration::from_secs(5))?;
+        reader.pragma_update(None, "query_only", true)?;
+        db.reader = Some(Mutex::new(reader));
+        info!("Database ready");
+        Ok(db)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn configure_connection(conn: &Connection) -> Result<()> {
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))
+            .context("Failed to enable WAL journal mode")?;
+        if !mode.eq_ignore_ascii_case("wal") {
+            warn!("SQLite kept journal mode '{}', WAL is unavailable on this filesystem", mode);
+        }
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        debug!("Database journal mode: {}", mode);
+        Ok(())
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
         conn.execute_batch(SCHEMA_SQL).context("Failed to initialize database schema")?;
-        
         Self::migrate_existing_tables(&conn)?;
-        
-        info!("Database ready");
-        
-        Ok(Self { conn: Mutex::new(conn) })
+        Ok(Self { conn: Mutex::new(conn), reader: None })
+    }
+
+    fn read_conn(&self) -> MutexGuard<'_, Connection> {
+        self.reader.as_ref().unwrap_or(&self.conn).lock().unwrap()
+    }
+
+    pub fn get_db_path() -> Result<PathBuf> {
+        Ok(Self::data_dir().join("mudae.db"))
+    }
+
+    pub fn set_data_dir(dir: PathBuf) {
+        if DATA_DIR_OVERRIDE.set(dir).is_err() {
+            warn!("Data directory was already set, ignoring override");
+        }
+    }
+
+    pub fn data_dir() -> PathBuf {
+        if let Some(dir) = DATA_DIR_OVERRIDE.get() {
+            return dir.clone();
+        }
+        ProjectDirs::from("com", "mudae", "selfbot")
+            .map(|proj_dirs| proj_dirs.data_dir().to_path_buf())
+            .unwrap_or_default()
+    }
+
+    pub fn resolve_data_path(path: impl AsRef<Path>) -> PathBuf {
+        Self::root_path(DATA_DIR_OVERRIDE.get().map(PathBuf::as_path), path.as_ref())
     }
 
-    fn get_db_path() -> Result<PathBuf> {
-        if let Some(proj_dirs) = ProjectDirs::from("com", "mudae", "selfbot") {
-            Ok(proj_dirs.data_dir().join("mudae.db"))
-        } else {
-            Ok(PathBuf::from("mudae.db"))
+    fn root_path(dir: Option<&Path>, path: &Path) -> PathBuf {
+        match dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
         }
     }
 
+    pub fn halt_marker_path() -> PathBuf {
+        Self::data_dir().join(HALT_MARKER_FILE)
+    }
+
     fn migrate_existing_tables(conn: &Connection) -> Result<()> {
         Self::add_column_if_missing(conn, "credentials", "username", "TEXT")?;
         Self::add_column_if_missing(conn, "credentials", "user_id", "INTEGER")?;
         Self::add_column_if_missing(conn, "channels", "channel_name", "TEXT")?;
         Self::add_column_if_missing(conn, "channels", "guild_name", "TEXT")?;
+        Self::add_column_if_missing(conn, "channels", "roll_commands", "TEXT")?;
+        Self::add_column_if_missing(conn, "config", "on_reconnect_commands", "TEXT DEFAULT ''")?;
+        Self::add_column_if_missing(conn, "config", "scheduled_commands", "TEXT DEFAULT ''")?;
+        Self::add_column_if_missing(conn, "config", "claim_priority_weight", "REAL DEFAULT 10000")?;
+        Self::add_column_if_missing(conn, "config", "claim_kakera_weight", "REAL DEFAULT 1")?;
+        Self::add_column_if_missing(conn, "config", "auto_idle", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "strict_wishlist_load", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "default_rolls", "INTEGER DEFAULT 10")?;
+        Self::add_column_if_missing(conn, "config", "claim_gender_filter", "TEXT DEFAULT ''")?;
+        Self::add_column_if_missing(conn, "config", "claim_kakera_min", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "claim_kakera_max", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "show_user_messages", "INTEGER DEFAULT 1")?;
+        Self::add_column_if_missing(conn, "config", "verify_delay_min_ms", "INTEGER DEFAULT 1500")?;
+        Self::add_column_if_missing(conn, "config", "verify_delay_max_ms", "INTEGER DEFAULT 15000")?;
+        Self::add_column_if_missing(conn, "config", "claim_rules", "TEXT DEFAULT 'wished'")?;
+        Self::add_column_if_missing(conn, "config", "roll_spread", "TEXT DEFAULT 'burst'")?;
+        Self::add_column_if_missing(conn, "config", "halt_key", "TEXT DEFAULT 'F9'")?;
+        Self::add_column_if_missing(conn, "config", "encrypt_token", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "attempt_claim_on_belongs_to", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "display_timezone", "TEXT DEFAULT 'local'")?;
+        Self::add_column_if_missing(conn, "config", "roll_command_cooldowns", "TEXT DEFAULT ''")?;
+        Self::add_column_if_missing(conn, "config", "catchup_messages", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "daily_commands", "TEXT DEFAULT '$daily,$dk'")?;
+        Self::add_column_if_missing(conn, "config", "kakera_power_min", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "claim_retries", "INTEGER DEFAULT 2")?;
+        Self::add_column_if_missing(conn, "config", "claim_retry_window_ms", "INTEGER DEFAULT 5000")?;
+        Self::add_column_if_missing(conn, "config", "auto_divorce_non_wished", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "auto_divorce_max_kakera", "INTEGER DEFAULT 100")?;
+        Self::add_column_if_missing(conn, "config", "auto_divorce_commands", "TEXT DEFAULT '$divorce {name},y'")?;
+        Self::add_column_if_missing(conn, "config", "stats_save_interval_seconds", "INTEGER DEFAULT 60")?;
+        Self::add_column_if_missing(conn, "config", "tui_tick_ms", "INTEGER DEFAULT 100")?;
+        Self::add_column_if_missing(conn, "config", "mudae_language", "TEXT DEFAULT 'en'")?;
+        Self::add_column_if_missing(conn, "config", "ascii_mode", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "auto_verify_on_start", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "daily_kakera_react", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "mark_wished_emoji", "TEXT DEFAULT ''")?;
+        Self::add_column_if_missing(conn, "config", "trust_mudae_wish", "INTEGER DEFAULT 1")?;
+        Self::add_column_if_missing(conn, "config", "startup_roll_delay_seconds", "INTEGER DEFAULT 5")?;
+        Self::add_column_if_missing(conn, "config", "post_reset_roll_delay_seconds", "INTEGER DEFAULT 10")?;
+        Self::add_column_if_missing(conn, "config", "inter_channel_delay_ms", "INTEGER DEFAULT 2000")?;
+        Self::add_column_if_missing(conn, "config", "shuffle_channels", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "claim_emoji", "TEXT DEFAULT '💖'")?;
+        Self::add_column_if_missing(conn, "config", "track_seen_characters", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "max_session_hours", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "session_limit_action", "TEXT DEFAULT 'stop'")?;
+        Self::add_column_if_missing(conn, "config", "include_threads", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "remote_control_user_ids", "TEXT DEFAULT ''")?;
+        Self::add_column_if_missing(conn, "config", "max_consecutive_failures", "INTEGER DEFAULT 5")?;
+        Self::add_column_if_missing(conn, "config", "claim_confirm_timeout_seconds", "INTEGER DEFAULT 15")?;
+        Self::add_column_if_missing(conn, "config", "fuzzy_dedup", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "roll_command_yields", "TEXT DEFAULT ''")?;
+        Self::add_column_if_missing(conn, "config", "channel_refresh_minutes", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "auth_error_halt_count", "INTEGER DEFAULT 5")?;
+        Self::add_column_if_missing(conn, "config", "claim_grace_seconds", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "config", "delay_distribution", "TEXT DEFAULT 'uniform'")?;
+        Self::add_column_if_missing(conn, "config", "include_dms", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(conn, "stats", "kakera_received", "INTEGER DEFAULT 0")?;
         Ok(())
     }
 
@@ -77,6 +216,31 @@ impl Database {
         Ok(())
     }
 
+    pub fn save_token_encrypted(&self, token: &str, passphrase: &str) -> Result<()> {
+        let encrypted = crypto::encrypt(token, passphrase)?;
+        self.save_token(&encrypted)
+    }
+
+    pub fn get_token_encrypted(&self, passphrase: &str) -> Result<Option<String>> {
+        self.get_token()?
+            .map(|stored| crypto::decrypt(&stored, passphrase))
+            .transpose()
+    }
+
+    pub fn token_fingerprint(&self) -> Result<Option<String>> {
+        Ok(self.get_token()?.map(|stored| {
+            if crypto::is_encrypted(&stored) {
+                "encrypted".to_string()
+            } else {
+                crypto::fingerprint(&stored)
+            }
+        }))
+    }
+
+    pub fn is_token_encrypted(&self) -> bool {
+        self.get_token().ok().flatten().is_some_and(|stored| crypto::is_encrypted(&stored))
+    }
+
     pub fn save_user_info(&self, username: &str, user_id: u64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -87,7 +251,7 @@ impl Database {
     }
 
     pub fn get_token(&self) -> Result<Option<String>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.read_conn();
         let mut stmt = conn.prepare("SELECT token FROM credentials WHERE id = 1")?;
         let result = stmt.query_row([], |row| row.get(0));
         match result {
@@ -98,7 +262,7 @@ impl Database {
     }
 
     pub fn get_username(&self) -> Result<Option<String>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.read_conn();
         let mut stmt = conn.prepare("SELECT username FROM credentials WHERE id = 1")?;
         let result = stmt.query_row([], |row| row.get(0));
         match result {
@@ -120,6 +284,22 @@ impl Database {
         Ok(())
     }
 
+    pub fn set_channel_roll_commands(&self, channel_id: u64, commands: Option<&[String]>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE channels SET roll_commands = ? WHERE channel_id = ?",
+            params![commands.map(|c| c.join(",")), channel_id as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_channel_roll_commands(&self) -> Result<HashMap<u64, Vec<String>>> {
+        Ok(self.get_channels_with_names()?
+            .into_iter()
+            .filter_map(|c| Some((c.id, c.roll_commands?)))
+            .collect())
+    }
+
     pub fn save_channel_with_name(&self, channel_id: u64, name: &str, guild: Option<&str>) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -140,7 +320,7 @@ impl Database {
     }
 
     pub fn get_channels(&self) -> Result<Vec<u64>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.read_conn();
         let mut stmt = conn.prepare("SELECT channel_id FROM channels ORDER BY id")?;
         let rows = stmt.query_map([], |row| {
             let id: i64 = row.get(0)?;
@@ -155,13 +335,16 @@ impl Database {
     }
 
     pub fn get_channels_with_names(&self) -> Result<Vec<ChannelInfo>> {
-        let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare("SELECT channel_id, channel_name, guild_name FROM channels ORDER BY id")?;
+        let conn = self.read_conn();
+        let mut stmt = conn.prepare("SELECT channel_id, channel_name, guild_name, roll_commands FROM channels ORDER BY id")?;
         let rows = stmt.query_map([], |row| {
             Ok(ChannelInfo {
                 id: row.get::<_, i64>(0)? as u64,
                 name: row.get(1)?,
                 guild: row.get(2)?,
+                roll_commands: row.get::<_, Option<String>>(3)?
+                    .map(|s| parse_command_list(&s))
+                    .filter(|c| !c.is_empty()),
             })
         })?;
         
@@ -193,6 +376,11 @@ impl Database {
     pub fn save_config(&self, config: &SavedConfig) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         let roll_commands = config.roll_commands.join(",");
+        let on_reconnect_commands = config.on_reconnect_commands.join(",");
+        let scheduled_commands = config.scheduled_commands.join(",");
+        let roll_command_cooldowns = config.roll_command_cooldowns.join(",");
+        let daily_commands = config.daily_commands.join(",");
+        let auto_divorce_commands = config.auto_divorce_commands.join(",");
         conn.execute(
             "UPDATE config SET 
                 roll_commands = ?,
@@ -203,7 +391,62 @@ impl Database {
                 daily_time = ?,
                 wishlist_enabled = ?,
                 fuzzy_match = ?,
-                fuzzy_threshold = ?
+                fuzzy_threshold = ?,
+                on_reconnect_commands = ?,
+                scheduled_commands = ?,
+                claim_priority_weight = ?,
+                claim_kakera_weight = ?,
+                auto_idle = ?,
+                strict_wishlist_load = ?,
+                default_rolls = ?,
+                claim_gender_filter = ?,
+                claim_kakera_min = ?,
+                claim_kakera_max = ?,
+                show_user_messages = ?,
+                verify_delay_min_ms = ?,
+                verify_delay_max_ms = ?,
+                claim_rules = ?,
+                roll_spread = ?,
+                halt_key = ?,
+                encrypt_token = ?,
+                attempt_claim_on_belongs_to = ?,
+                display_timezone = ?,
+                roll_command_cooldowns = ?,
+                catchup_messages = ?,
+                daily_commands = ?,
+                kakera_power_min = ?,
+                claim_retries = ?,
+                claim_retry_window_ms = ?,
+                auto_divorce_non_wished = ?,
+                auto_divorce_max_kakera = ?,
+                auto_divorce_commands = ?,
+                stats_save_interval_seconds = ?,
+                tui_tick_ms = ?,
+                mudae_language = ?,
+                ascii_mode = ?,
+                auto_verify_on_start = ?,
+                daily_kakera_react = ?,
+                mark_wished_emoji = ?,
+                trust_mudae_wish = ?,
+                startup_roll_delay_seconds = ?,
+                post_reset_roll_delay_seconds = ?,
+                inter_channel_delay_ms = ?,
+                shuffle_channels = ?,
+                claim_emoji = ?,
+                track_seen_characters = ?,
+                max_session_hours = ?,
+                session_limit_action = ?,
+                include_threads = ?,
+                remote_control_user_ids = ?,
+                max_consecutive_failures = ?,
+                claim_confirm_timeout_seconds = ?,
+                fuzzy_dedup = ?,
+                roll_command_yields = ?,
+                channel_refresh_minutes = ?,
+                auth_error_halt_count = ?,
+                claim_grace_seconds = ?,
+                delay_distribution = ?,
+                include_dms = ?
             WHERE id = 1",
             params![
                 roll_commands,
@@ -215,26 +458,182 @@ impl Database {
                 config.wishlist_enabled as i32,
                 config.fuzzy_match as i32,
                 config.fuzzy_threshold,
+                on_reconnect_commands,
+                scheduled_commands,
+                config.claim_priority_weight,
+                config.claim_kakera_weight,
+                config.auto_idle as i32,
+                config.strict_wishlist_load as i32,
+                config.default_rolls as i64,
+                config.claim_gender_filter,
+                config.claim_kakera_min as i64,
+                config.claim_kakera_max as i64,
+                config.show_user_messages as i32,
+                config.verify_delay_min_ms as i64,
+                config.verify_delay_max_ms as i64,
+                config.claim_rules,
+                config.roll_spread,
+                config.halt_key,
+                config.encrypt_token as i32,
+                config.attempt_claim_on_belongs_to as i32,
+                config.display_timezone,
+                roll_command_cooldowns,
+                config.catchup_messages as i64,
+                daily_commands,
+                config.kakera_power_min as i64,
+                config.claim_retries as i64,
+                config.claim_retry_window_ms as i64,
+                config.auto_divorce_non_wished as i32,
+                config.auto_divorce_max_kakera as i64,
+                auto_divorce_commands,
+                config.stats_save_interval_seconds as i64,
+                config.tui_tick_ms as i64,
+                config.mudae_language,
+                config.ascii_mode as i32,
+                config.auto_verify_on_start as i32,
+                config.daily_kakera_react as i32,
+                config.mark_wished_emoji,
+                config.trust_mudae_wish as i32,
+                config.startup_roll_delay_seconds as i64,
+                config.post_reset_roll_delay_seconds as i64,
+                config.inter_channel_delay_ms as i64,
+                config.shuffle_channels as i32,
+                config.claim_emoji,
+                config.track_seen_characters as i32,
+                config.max_session_hours as i64,
+                config.session_limit_action,
+                config.include_threads as i32,
+                config.remote_control_user_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(","),
+                config.max_consecutive_failures as i64,
+                config.claim_confirm_timeout_seconds as i64,
+                config.fuzzy_dedup as i32,
+                config.roll_command_yields.join(","),
+                config.channel_refresh_minutes as i64,
+                config.auth_error_halt_count as i64,
+                config.claim_grace_seconds as i64,
+                config.delay_distribution,
+                config.include_dms as i32,
             ],
         )?;
         Ok(())
     }
 
     pub fn load_config(&self) -> Result<SavedConfig> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.read_conn();
         let mut stmt = conn.prepare(
             "SELECT roll_commands, roll_cooldown_seconds, auto_roll, auto_react_kakera,
-                    auto_daily, daily_time, wishlist_enabled, fuzzy_match, fuzzy_threshold
+                    auto_daily, daily_time, wishlist_enabled, fuzzy_match, fuzzy_threshold,
+                    on_reconnect_commands, scheduled_commands, claim_priority_weight, claim_kakera_weight,
+                    auto_idle, strict_wishlist_load, default_rolls,
+                    claim_gender_filter,
+                    claim_kakera_min,
+                    claim_kakera_max,
+                    show_user_messages,
+                    verify_delay_min_ms,
+                    verify_delay_max_ms,
+                    claim_rules,
+                    roll_spread,
+                    halt_key,
+                    encrypt_token,
+                    attempt_claim_on_belongs_to,
+                    display_timezone,
+                    roll_command_cooldowns,
+                    catchup_messages,
+                    daily_commands,
+                    kakera_power_min,
+                    claim_retries,
+                    claim_retry_window_ms,
+                    auto_divorce_non_wished,
+                    auto_divorce_max_kakera,
+                    auto_divorce_commands,
+                    stats_save_interval_seconds,
+                    tui_tick_ms,
+                    mudae_language,
+                    ascii_mode,
+                    auto_verify_on_start,
+                    daily_kakera_react,
+                    mark_wished_emoji,
+                    trust_mudae_wish,
+                    startup_roll_delay_seconds,
+                    post_reset_roll_delay_seconds,
+                    inter_channel_delay_ms,
+                    shuffle_channels,
+                    claim_emoji,
+                    track_seen_characters,
+                    max_session_hours,
+                    session_limit_action,
+                    include_threads,
+                    remote_control_user_ids,
+                    max_consecutive_failures,
+                    claim_confirm_timeout_seconds,
+                    fuzzy_dedup,
+                    roll_command_yields,
+                    channel_refresh_minutes,
+                    auth_error_halt_count,
+                    claim_grace_seconds,
+                    delay_distribution,
+                    include_dms
              FROM config WHERE id = 1"
         )?;
         
         let result = stmt.query_row([], |row| {
             let roll_commands_str: String = row.get(0)?;
-            let roll_commands: Vec<String> = roll_commands_str
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
+            let roll_commands = parse_command_list(&roll_commands_str);
+            let on_reconnect_str: Option<String> = row.get(9)?;
+            let scheduled_str: Option<String> = row.get(10)?;
+            let claim_priority_weight: Option<f64> = row.get(11)?;
+            let claim_kakera_weight: Option<f64> = row.get(12)?;
+            let auto_idle: Option<i32> = row.get(13)?;
+            let strict_wishlist_load: Option<i32> = row.get(14)?;
+            let default_rolls: Option<i64> = row.get(15)?;
+            let claim_gender_filter: Option<String> = row.get(16)?;
+            let claim_kakera_min: Option<i64> = row.get(17)?;
+            let claim_kakera_max: Option<i64> = row.get(18)?;
+            let show_user_messages: Option<i32> = row.get(19)?;
+            let verify_delay_min_ms: Option<i64> = row.get(20)?;
+            let verify_delay_max_ms: Option<i64> = row.get(21)?;
+            let claim_rules: Option<String> = row.get(22)?;
+            let roll_spread: Option<String> = row.get(23)?;
+            let halt_key: Option<String> = row.get(24)?;
+            let encrypt_token: Option<i32> = row.get(25)?;
+            let attempt_claim_on_belongs_to: Option<i32> = row.get(26)?;
+            let display_timezone: Option<String> = row.get(27)?;
+            let roll_command_cooldowns: Option<String> = row.get(28)?;
+            let catchup_messages: Option<i64> = row.get(29)?;
+            let kakera_power_min: Option<i64> = row.get(31)?;
+            let claim_retries: Option<i64> = row.get(32)?;
+            let claim_retry_window_ms: Option<i64> = row.get(33)?;
+            let auto_divorce_non_wished: Option<i32> = row.get(34)?;
+            let auto_divorce_max_kakera: Option<i64> = row.get(35)?;
+            let stats_save_interval_seconds: Option<i64> = row.get(37)?;
+            let tui_tick_ms: Option<i64> = row.get(38)?;
+            let mudae_language: Option<String> = row.get(39)?;
+            let ascii_mode: Option<i32> = row.get(40)?;
+            let auto_verify_on_start: Option<i32> = row.get(41)?;
+            let daily_kakera_react: Option<i32> = row.get(42)?;
+            let mark_wished_emoji: Option<String> = row.get(43)?;
+            let trust_mudae_wish: Option<i32> = row.get(44)?;
+            let startup_roll_delay_seconds: Option<i64> = row.get(45)?;
+            let post_reset_roll_delay_seconds: Option<i64> = row.get(46)?;
+            let inter_channel_delay_ms: Option<i64> = row.get(47)?;
+            let shuffle_channels: Option<i32> = row.get(48)?;
+            let claim_emoji: Option<String> = row.get(49)?;
+            let track_seen_characters: Option<i32> = row.get(50)?;
+            let max_session_hours: Option<i64> = row.get(51)?;
+            let session_limit_action: Option<String> = row.get(52)?;
+            let include_threads: Option<i32> = row.get(53)?;
+            let remote_control_user_ids: Option<String> = row.get(54)?;
+            let max_consecutive_failures: Option<i64> = row.get(55)?;
+            let claim_confirm_timeout_seconds: Option<i64> = row.get(56)?;
+            let fuzzy_dedup: Option<i32> = row.get(57)?;
+            let roll_command_yields: Option<String> = row.get(58)?;
+            let channel_refresh_minutes: Option<i64> = row.get(59)?;
+            let auth_error_halt_count: Option<i64> = row.get(60)?;
+            let claim_grace_seconds: Option<i64> = row.get(61)?;
+            let delay_distribution: Option<String> = row.get(62)?;
+            let include_dms: Option<i32> = row.get(63)?;
+            let auto_divorce_commands = parse_command_list(row.get::<_, Option<String>>(36)?.as_deref().unwrap_or(""));
+            let daily_commands = parse_command_list(row.get::<_, Option<String>>(30)?.as_deref().unwrap_or(""));
             
             Ok(SavedConfig {
                 roll_commands: if roll_commands.is_empty() {
@@ -250,6 +649,69 @@ impl Database {
                 wishlist_enabled: row.get::<_, i32>(6)? != 0,
                 fuzzy_match: row.get::<_, i32>(7)? != 0,
                 fuzzy_threshold: row.get(8)?,
+                on_reconnect_commands: parse_command_list(on_reconnect_str.as_deref().unwrap_or("")),
+                scheduled_commands: parse_command_list(scheduled_str.as_deref().unwrap_or("")),
+                claim_priority_weight: claim_priority_weight.unwrap_or(10000.0),
+                claim_kakera_weight: claim_kakera_weight.unwrap_or(1.0),
+                auto_idle: auto_idle.unwrap_or(0) != 0,
+                strict_wishlist_load: strict_wishlist_load.unwrap_or(0) != 0,
+                default_rolls: default_rolls.unwrap_or(10) as u64,
+                claim_gender_filter: claim_gender_filter.unwrap_or_default(),
+                claim_kakera_min: claim_kakera_min.unwrap_or(0) as u32,
+                claim_kakera_max: claim_kakera_max.unwrap_or(0) as u32,
+                show_user_messages: show_user_messages.unwrap_or(1) != 0,
+                verify_delay_min_ms: verify_delay_min_ms.unwrap_or(1500) as u64,
+                verify_delay_max_ms: verify_delay_max_ms.unwrap_or(15000) as u64,
+                claim_rules: claim_rules.unwrap_or_else(|| "wished".to_string()),
+                roll_spread: roll_spread.unwrap_or_else(|| "burst".to_string()),
+                halt_key: halt_key.unwrap_or_else(|| "F9".to_string()),
+                encrypt_token: encrypt_token.unwrap_or(0) != 0,
+                attempt_claim_on_belongs_to: attempt_claim_on_belongs_to.unwrap_or(0) != 0,
+                display_timezone: display_timezone.unwrap_or_else(|| "local".to_string()),
+                roll_command_cooldowns: parse_command_list(roll_command_cooldowns.as_deref().unwrap_or("")),
+                catchup_messages: catchup_messages.unwrap_or(0) as u32,
+                daily_commands: if daily_commands.is_empty() {
+                    vec!["$daily".to_string(), "$dk".to_string()]
+                } else {
+                    daily_commands
+                },
+                kakera_power_min: kakera_power_min.unwrap_or(0) as u32,
+                claim_retries: claim_retries.unwrap_or(2) as u32,
+                claim_retry_window_ms: claim_retry_window_ms.unwrap_or(5000) as u64,
+                auto_divorce_non_wished: auto_divorce_non_wished.unwrap_or(0) != 0,
+                auto_divorce_max_kakera: auto_divorce_max_kakera.unwrap_or(100) as u32,
+                auto_divorce_commands: if auto_divorce_commands.is_empty() {
+                    vec!["$divorce {name}".to_string(), "y".to_string()]
+                } else {
+                    auto_divorce_commands
+                },
+                stats_save_interval_seconds: stats_save_interval_seconds.unwrap_or(60).max(1) as u64,
+                tui_tick_ms: tui_tick_ms.unwrap_or(100).max(crate::config::MIN_TUI_TICK_MS as i64) as u64,
+                mudae_language: mudae_language.unwrap_or_else(|| "en".to_string()),
+                ascii_mode: ascii_mode.unwrap_or(0) != 0,
+                auto_verify_on_start: auto_verify_on_start.unwrap_or(0) != 0,
+                daily_kakera_react: daily_kakera_react.unwrap_or(0) != 0,
+                mark_wished_emoji: mark_wished_emoji.unwrap_or_default(),
+                trust_mudae_wish: trust_mudae_wish.unwrap_or(1) != 0,
+                startup_roll_delay_seconds: startup_roll_delay_seconds.unwrap_or(5) as u64,
+                post_reset_roll_delay_seconds: post_reset_roll_delay_seconds.unwrap_or(10) as u64,
+                inter_channel_delay_ms: inter_channel_delay_ms.unwrap_or(2000) as u64,
+                shuffle_channels: shuffle_channels.unwrap_or(0) != 0,
+                claim_emoji: claim_emoji.filter(|e| !e.is_empty()).unwrap_or_else(|| "💖".to_string()),
+                track_seen_characters: track_seen_characters.unwrap_or(0) != 0,
+                max_session_hours: max_session_hours.unwrap_or(0) as u64,
+                session_limit_action: session_limit_action.unwrap_or_else(|| "stop".to_string()),
+                include_threads: include_threads.unwrap_or(0) != 0,
+                remote_control_user_ids: parse_id_list(remote_control_user_ids.as_deref().unwrap_or("")),
+                max_consecutive_failures: max_consecutive_failures.unwrap_or(5) as u32,
+                claim_confirm_timeout_seconds: claim_confirm_timeout_seconds.unwrap_or(15) as u64,
+                fuzzy_dedup: fuzzy_dedup.unwrap_or(0) != 0,
+                roll_command_yields: parse_command_list(roll_command_yields.as_deref().unwrap_or("")),
+                channel_refresh_minutes: channel_refresh_minutes.unwrap_or(0) as u64,
+                auth_error_halt_count: auth_error_halt_count.unwrap_or(5) as u32,
+                claim_grace_seconds: claim_grace_seconds.unwrap_or(0) as u64,
+                delay_distribution: delay_distribution.unwrap_or_else(|| "uniform".to_string()),
+                include_dms: include_dms.unwrap_or(0) != 0,
             })
         });
 
@@ -269,6 +731,7 @@ impl Database {
                 kakera_collected = ?,
                 rolls_executed = ?,
                 total_uptime_seconds = ?,
+                kakera_received = ?,
                 updated_at = CURRENT_TIMESTAMP
             WHERE id = 1",
             params![
@@ -278,16 +741,18 @@ impl Database {
                 stats.kakera_collected as i64,
                 stats.rolls_executed as i64,
                 stats.total_uptime_seconds as i64,
+                stats.kakera_received as i64,
             ],
         )?;
         Ok(())
     }
 
     pub fn load_stats(&self) -> Result<SavedStats> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.read_conn();
         let mut stmt = conn.prepare(
             "SELECT characters_rolled, characters_claimed, wishlist_matches, 
-                    kakera_collected, rolls_executed, total_uptime_seconds 
+                    kakera_collected, rolls_executed, total_uptime_seconds,
+                    kakera_received
              FROM stats WHERE id = 1"
         )?;
         
@@ -299,6 +764,7 @@ impl Database {
                 kakera_collected: row.get::<_, i64>(3)? as u64,
                 rolls_executed: row.get::<_, i64>(4)? as u64,
                 total_uptime_seconds: row.get::<_, i64>(5)? as u64,
+                kakera_received: row.get::<_, Option<i64>>(6)?.unwrap_or(0) as u64,
             })
         });
 
@@ -308,6 +774,183 @@ impl Database {
         }
     }
 
+    pub fn add_claim(&self, claim: &ClaimRecord) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO claims (character_name, series, kakera_value, channel_id, claimed_at)
+             VALUES (?, ?, ?, ?, ?)",
+            params![
+                claim.character_name,
+                claim.series,
+                claim.kakera_value.map(|v| v as i64),
+                claim.channel_id as i64,
+                claim.claimed_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_claims(&self) -> Result<Vec<ClaimRecord>> {
+        let conn = self.read_conn();
+        let mut stmt = conn.prepare(
+            "SELECT character_name, series, kakera_value, channel_id, claimed_at
+             FROM claims ORDER BY id DESC"
+        )?;
+        let rows = stmt.query_map([], Self::claim_from_row)?;
+
+        let mut claims = Vec::new();
+        for row in rows {
+            claims.push(row?);
+        }
+        Ok(claims)
+    }
+
+    fn claim_from_row(row: &rusqlite::Row) -> rusqlite::Result<ClaimRecord> {
+        let claimed_at: String = row.get(4)?;
+        Ok(ClaimRecord {
+            character_name: row.get(0)?,
+            series: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            kakera_value: row.get::<_, Option<i64>>(2)?.map(|v| v as u32),
+            channel_id: row.get::<_, i64>(3)? as u64,
+            claimed_at: DateTime::parse_from_rfc3339(&claimed_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn get_last_claim(&self) -> Result<Option<ClaimRecord>> {
+        let conn = self.read_conn();
+        let claim = conn.query_row(
+            "SELECT character_name, series, kakera_value, channel_id, claimed_at
+             FROM claims ORDER BY id DESC LIMIT 1",
+            [],
+            Self::claim_from_row,
+        );
+        match claim {
+            Ok(claim) => Ok(Some(claim)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn remove_latest_claim(&self, character_name: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute(
+            "DELETE FROM claims WHERE id = (SELECT MAX(id) FROM claims WHERE character_name = ?1)",
+            params![character_name],
+        )?;
+        Ok(removed > 0)
+    }
+
+    pub fn add_owned_characters(&self, names: &[String]) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        for name in names {
+            conn.execute(
+                "INSERT OR REPLACE INTO owned_characters (name, synced_at) VALUES (?1, ?2)",
+                params![name, now],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn replace_owned_characters(&self, names: &[String]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        tx.execute("DELETE FROM owned_characters", [])?;
+        for name in names {
+            tx.execute(
+                "INSERT OR REPLACE INTO owned_characters (name, synced_at) VALUES (?1, ?2)",
+                params![name, now],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_owned_characters(&self) -> Result<Vec<String>> {
+        let conn = self.read_conn();
+        let mut stmt = conn.prepare("SELECT name FROM owned_characters ORDER BY name")?;
+        let names = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(names)
+    }
+
+    pub fn record_seen_character(&self, name: &str, series: &str, seen_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let seen_at = seen_at.to_rfc3339();
+        conn.execute(
+            "INSERT INTO seen_characters (name, series, first_seen, last_seen) VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT(name, series) DO UPDATE SET last_seen = excluded.last_seen, roll_count = roll_count + 1",
+            params![name.trim(), series.trim(), seen_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_seen_characters(&self, series_query: &str) -> Result<Vec<SeenCharacter>> {
+        let conn = self.read_conn();
+        let mut stmt = conn.prepare(
+            "SELECT name, series, first_seen, last_seen, roll_count FROM seen_characters
+             WHERE series LIKE '%' || ?1 || '%'
+             ORDER BY series ASC, roll_count DESC, name ASC"
+        )?;
+        let rows = stmt.query_map(params![series_query.trim()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+
+        let mut seen = Vec::new();
+        for row in rows {
+            let (name, series, first_seen, last_seen, roll_count) = row?;
+            seen.push(SeenCharacter {
+                name,
+                series,
+                first_seen: DateTime::parse_from_rfc3339(&first_seen)?.with_timezone(&Utc),
+                last_seen: DateTime::parse_from_rfc3339(&last_seen)?.with_timezone(&Utc),
+                roll_count: roll_count as u64,
+            });
+        }
+        Ok(seen)
+    }
+
+    pub fn get_claimed_value(&self) -> Result<ClaimedValue> {
+        let conn = self.read_conn();
+        let value = conn.query_row(
+            "SELECT COALESCE(SUM(kakera_value), 0), COUNT(*) - COUNT(kakera_value) FROM claims",
+            [],
+            |row| Ok(ClaimedValue {
+                total: row.get::<_, i64>(0)? as u64,
+                unknown: row.get::<_, i64>(1)? as u64,
+            }),
+        )?;
+        Ok(value)
+    }
+
+    pub fn get_claims_per_series(&self, limit: usize) -> Result<Vec<(String, u64)>> {
+        let conn = self.read_conn();
+        let mut stmt = conn.prepare(
+            "SELECT TRIM(series) AS name, COUNT(*) AS total FROM claims
+             WHERE series IS NOT NULL AND TRIM(series) != ''
+             GROUP BY name ORDER BY total DESC, name ASC LIMIT ?"
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+
+        let mut series = Vec::new();
+        for row in rows {
+            series.push(row?);
+        }
+        Ok(series)
+    }
+
     pub fn has_credentials(&self) -> bool {
         self.get_token().ok().flatten().is_some()
     }
@@ -321,14 +964,66 @@ impl Database {
     }
 }
 
+fn parse_command_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_id_list(value: &str) -> Vec<u64> {
+    value.split(',').filter_map(|s| s.trim().parse().ok()).collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct ChannelInfo {
     pub id: u64,
     pub name: Option<String>,
     pub guild: Option<String>,
+    pub roll_commands: Option<Vec<String>>,
 }
 
 impl ChannelInfo {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            name: None,
+            guild: None,
+            roll_commands: None,
+        }
+    }
+
+    pub fn parse_entries(input: &str) -> Result<Vec<ChannelInfo>, String> {
+        let mut channels = Vec::new();
+        for entry in input.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match entry.split_once('=') {
+                Some((id, commands)) => {
+                    let id = id.trim().parse::<u64>().map_err(|_| format!("Invalid channel ID in '{}'", entry))?;
+                    let commands: Vec<String> = commands.split_whitespace().map(str::to_string).collect();
+                    if commands.is_empty() {
+                        return Err(format!("Missing roll commands in '{}'", entry));
+                    }
+                    channels.push(ChannelInfo { roll_commands: Some(commands), ..ChannelInfo::new(id) });
+                }
+                None => {
+                    for id in entry.split_whitespace() {
+                        let id = id.parse::<u64>().map_err(|_| format!("Invalid channel ID '{}'", id))?;
+                        channels.push(ChannelInfo::new(id));
+                    }
+                }
+            }
+        }
+        Ok(channels)
+    }
+
+    pub fn entry(&self) -> String {
+        match &self.roll_commands {
+            Some(commands) => format!("{}={}", self.id, commands.join(" ")),
+            None => self.id.to_string(),
+        }
+    }
+
     pub fn display_name(&self) -> String {
         match (&self.name, &self.guild) {
             (Some(name), Some(guild)) => format!("#{} ({})", name, guild),
@@ -338,6 +1033,30 @@ impl ChannelInfo {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ClaimRecord {
+    pub character_name: String,
+    pub series: String,
+    pub kakera_value: Option<u32>,
+    pub channel_id: u64,
+    pub claimed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClaimedValue {
+    pub total: u64,
+    pub unknown: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeenCharacter {
+    pub name: String,
+    pub series: String,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub roll_count: u64,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SavedStats {
     pub characters_rolled: u64,
@@ -346,6 +1065,33 @@ pub struct SavedStats {
     pub kakera_collected: u64,
     pub rolls_executed: u64,
     pub total_uptime_seconds: u64,
+    pub kakera_received: u64,
+}
+
+impl SavedStats {
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Characters rolled", self.characters_rolled.to_string()),
+            ("Characters claimed", self.characters_claimed.to_string()),
+            ("Wishlist matches", self.wishlist_matches.to_string()),
+            ("Kakera collected", self.kakera_collected.to_string()),
+            ("Kakera received", self.kakera_received.to_string()),
+            ("Rolls executed", self.rolls_executed.to_string()),
+            (
+                "Total uptime",
+                crate::utils::format_duration(chrono::Duration::seconds(self.total_uptime_seconds as i64)),
+            ),
+        ]
+    }
+
+    pub fn to_table(&self) -> String {
+        let rows = self.rows();
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        rows.iter()
+            .map(|(label, value)| format!("{:<width$}  {}", label, value, width = width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -359,6 +1105,61 @@ pub struct SavedConfig {
     pub wishlist_enabled: bool,
     pub fuzzy_match: bool,
     pub fuzzy_threshold: f64,
+    pub on_reconnect_commands: Vec<String>,
+    pub scheduled_commands: Vec<String>,
+    pub claim_priority_weight: f64,
+    pub claim_kakera_weight: f64,
+    pub auto_idle: bool,
+    pub strict_wishlist_load: bool,
+    pub default_rolls: u64,
+    pub claim_gender_filter: String,
+    pub claim_kakera_min: u32,
+    pub claim_kakera_max: u32,
+    pub show_user_messages: bool,
+    pub verify_delay_min_ms: u64,
+    pub verify_delay_max_ms: u64,
+    pub claim_rules: String,
+    pub roll_spread: String,
+    pub halt_key: String,
+    pub encrypt_token: bool,
+    pub attempt_claim_on_belongs_to: bool,
+    pub display_timezone: String,
+    pub roll_command_cooldowns: Vec<String>,
+    pub catchup_messages: u32,
+    pub daily_commands: Vec<String>,
+    pub kakera_power_min: u32,
+    pub claim_retries: u32,
+    pub claim_retry_window_ms: u64,
+    pub auto_divorce_non_wished: bool,
+    pub auto_divorce_max_kakera: u32,
+    pub auto_divorce_commands: Vec<String>,
+    pub stats_save_interval_seconds: u64,
+    pub tui_tick_ms: u64,
+    pub mudae_language: String,
+    pub ascii_mode: bool,
+    pub auto_verify_on_start: bool,
+    pub daily_kakera_react: bool,
+    pub mark_wished_emoji: String,
+    pub trust_mudae_wish: bool,
+    pub startup_roll_delay_seconds: u64,
+    pub post_reset_roll_delay_seconds: u64,
+    pub inter_channel_delay_ms: u64,
+    pub shuffle_channels: bool,
+    pub claim_emoji: String,
+    pub track_seen_characters: bool,
+    pub max_session_hours: u64,
+    pub session_limit_action: String,
+    pub include_threads: bool,
+    pub remote_control_user_ids: Vec<u64>,
+    pub max_consecutive_failures: u32,
+    pub claim_confirm_timeout_seconds: u64,
+    pub fuzzy_dedup: bool,
+    pub roll_command_yields: Vec<String>,
+    pub channel_refresh_minutes: u64,
+    pub auth_error_halt_count: u32,
+    pub claim_grace_seconds: u64,
+    pub delay_distribution: String,
+    pub include_dms: bool,
 }
 
 impl Default for SavedConfig {
@@ -373,6 +1174,236 @@ impl Default for SavedConfig {
             wishlist_enabled: true,
             fuzzy_match: true,
             fuzzy_threshold: 0.8,
+            on_reconnect_commands: Vec::new(),
+            scheduled_commands: Vec::new(),
+            claim_priority_weight: 10000.0,
+            claim_kakera_weight: 1.0,
+            auto_idle: false,
+            strict_wishlist_load: false,
+            default_rolls: 10,
+            claim_gender_filter: String::new(),
+            claim_kakera_min: 0,
+            claim_kakera_max: 0,
+            show_user_messages: true,
+            verify_delay_min_ms: 1500,
+            verify_delay_max_ms: 15000,
+            claim_rules: "wished".to_string(),
+            roll_spread: "burst".to_string(),
+            halt_key: "F9".to_string(),
+            encrypt_token: false,
+            attempt_claim_on_belongs_to: false,
+            display_timezone: "local".to_string(),
+            roll_command_cooldowns: Vec::new(),
+            catchup_messages: 0,
+            daily_commands: vec!["$daily".to_string(), "$dk".to_string()],
+            kakera_power_min: 0,
+            claim_retries: 2,
+            claim_retry_window_ms: 5000,
+            auto_divorce_non_wished: false,
+            auto_divorce_max_kakera: 100,
+            auto_divorce_commands: vec!["$divorce {name}".to_string(), "y".to_string()],
+            stats_save_interval_seconds: 60,
+            tui_tick_ms: 100,
+            mudae_language: "en".to_string(),
+            ascii_mode: false,
+            auto_verify_on_start: false,
+            daily_kakera_react: false,
+            mark_wished_emoji: String::new(),
+            trust_mudae_wish: true,
+            startup_roll_delay_seconds: 5,
+            post_reset_roll_delay_seconds: 10,
+            inter_channel_delay_ms: 2000,
+            shuffle_channels: false,
+            claim_emoji: "💖".to_string(),
+            track_seen_characters: false,
+            max_session_hours: 0,
+            session_limit_action: "stop".to_string(),
+            include_threads: false,
+            remote_control_user_ids: Vec::new(),
+            max_consecutive_failures: 5,
+            claim_confirm_timeout_seconds: 15,
+            fuzzy_dedup: false,
+            roll_command_yields: Vec::new(),
+            channel_refresh_minutes: 0,
+            auth_error_halt_count: 5,
+            claim_grace_seconds: 0,
+            delay_distribution: "uniform".to_string(),
+            include_dms: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_database_uses_wal() {
+        let path = std::env::temp_dir().join(format!("mudae-wal-{}.db", std::process::id()));
+        {
+            let db = Database::open_at(&path).unwrap();
+            let conn = db.conn.lock().unwrap();
+            let mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+            assert_eq!(mode.to_lowercase(), "wal");
+        }
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[test]
+    fn test_reads_use_separate_connection_while_writer_is_busy() {
+        let path = std::env::temp_dir().join(format!("mudae-reader-{}.db", std::process::id()));
+        {
+            let db = Database::open_at(&path).unwrap();
+            db.save_channels(&[100, 200]).unwrap();
+
+            let writer = db.conn.lock().unwrap();
+            assert_eq!(db.get_channels().unwrap(), vec![100, 200]);
+            drop(writer);
+
+            let reader = db.read_conn();
+            assert!(reader.execute("DELETE FROM channels", []).is_err());
+        }
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[test]
+    fn test_ensure_writable() {
+        let dir = std::env::temp_dir().join(format!("mudae-probe-{}", std::process::id()));
+        Database::ensure_writable(&dir).unwrap();
+        assert!(!dir.join(WRITE_PROBE_FILE).exists());
+
+        let blocker = dir.join("file");
+        std::fs::write(&blocker, b"").unwrap();
+        let err = Database::ensure_writable(&blocker.join("sub")).unwrap_err().to_string();
+        assert!(err.contains("--data-dir"), "{}", err);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_root_path() {
+        let dir = Path::new("/srv/mudae");
+        assert_eq!(Database::root_path(Some(dir), Path::new("wishlist.json")), dir.join("wishlist.json"));
+        assert_eq!(Database::root_path(Some(dir), Path::new("/tmp/wishlist.json")), Path::new("/tmp/wishlist.json"));
+        assert_eq!(Database::root_path(None, Path::new("wishlist.json")), Path::new("wishlist.json"));
+    }
+
+    #[test]
+    fn test_remove_latest_claim() {
+        let db = Database::open_in_memory().unwrap();
+        for (name, series) in [("Rem", "Re:Zero"), ("Emilia", "Re:Zero"), ("Rem", "Re:Zero")] {
+            db.add_claim(&ClaimRecord {
+                character_name: name.to_string(),
+                series: series.to_string(),
+                kakera_value: None,
+                channel_id: 1,
+                claimed_at: Utc::now(),
+            }).unwrap();
+        }
+
+        assert_eq!(db.get_last_claim().unwrap().unwrap().character_name, "Rem");
+        assert!(db.remove_latest_claim("Rem").unwrap());
+        assert_eq!(db.get_last_claim().unwrap().unwrap().character_name, "Emilia");
+        assert_eq!(db.get_claims().unwrap().len(), 2);
+        assert!(!db.remove_latest_claim("Ram").unwrap());
+    }
+
+    #[test]
+    fn test_claimed_value() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(db.get_claimed_value().unwrap(), ClaimedValue::default());
+
+        for kakera_value in [Some(120), None, Some(80)] {
+            db.add_claim(&ClaimRecord {
+                character_name: "Rem".to_string(),
+                series: "Re:Zero".to_string(),
+                kakera_value,
+                channel_id: 1,
+                claimed_at: Utc::now(),
+            }).unwrap();
+        }
+
+        assert_eq!(db.get_claimed_value().unwrap(), ClaimedValue { total: 200, unknown: 1 });
+    }
+
+    #[test]
+    fn test_seen_characters() {
+        let db = Database::open_in_memory().unwrap();
+        let first = Utc::now() - chrono::Duration::hours(2);
+        let later = Utc::now();
+
+        db.record_seen_character("Rem", "Re:Zero", first).unwrap();
+        db.record_seen_character("rem", "re:zero", later).unwrap();
+        db.record_seen_character("Emilia", "Re:Zero", later).unwrap();
+        db.record_seen_character("Asuna", "Sword Art Online", later).unwrap();
+
+        let seen = db.get_seen_characters("zero").unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].name, "Rem");
+        assert_eq!(seen[0].roll_count, 2);
+        assert_eq!(seen[0].first_seen.timestamp(), first.timestamp());
+        assert_eq!(seen[0].last_seen.timestamp(), later.timestamp());
+        assert_eq!(seen[1].name, "Emilia");
+        assert_eq!(db.get_seen_characters("").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_replace_owned_characters() {
+        let db = Database::open_in_memory().unwrap();
+        db.add_owned_characters(&["Rem".to_string(), "Ram".to_string()]).unwrap();
+        db.add_owned_characters(&["rem".to_string()]).unwrap();
+        assert_eq!(db.get_owned_characters().unwrap().len(), 2);
+
+        db.replace_owned_characters(&["Emilia".to_string()]).unwrap();
+        assert_eq!(db.get_owned_characters().unwrap(), vec!["Emilia".to_string()]);
+    }
+
+    #[test]
+    fn test_stats_readout() {
+        let db = Database::open_in_memory().unwrap();
+        db.save_stats(&SavedStats {
+            characters_rolled: 42,
+            characters_claimed: 3,
+            total_uptime_seconds: 3725,
+            ..SavedStats::default()
+        }).unwrap();
+
+        let stats = db.load_stats().unwrap();
+        assert!(stats.to_table().contains("Characters rolled   42"));
+        assert!(stats.to_table().contains("Total uptime        1h 2m 5s"));
+        let snapshot = serde_json::to_value(crate::stats::Stats::from_saved(stats).stats_snapshot()).unwrap();
+        assert_eq!(snapshot["characters_claimed"], 3);
+        assert_eq!(snapshot["total_uptime_seconds"], 3725);
+    }
+
+    #[test]
+    fn test_parse_channel_entries() {
+        let channels = ChannelInfo::parse_entries("111, 222=$w $wg, 333 444").unwrap();
+        let ids: Vec<u64> = channels.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![111, 222, 333, 444]);
+        assert_eq!(channels[1].roll_commands, Some(vec!["$w".to_string(), "$wg".to_string()]));
+        assert_eq!(channels[1].entry(), "222=$w $wg");
+        assert_eq!(channels[0].entry(), "111");
+
+        assert!(ChannelInfo::parse_entries("abc").is_err());
+        assert!(ChannelInfo::parse_entries("111=").is_err());
+    }
+
+    #[test]
+    fn test_channel_roll_commands_round_trip() {
+        let db = Database::open_in_memory().unwrap();
+        db.save_channels(&[111, 222]).unwrap();
+        db.set_channel_roll_commands(222, Some(&["$w".to_string()])).unwrap();
+
+        let overrides = db.get_channel_roll_commands().unwrap();
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[&222], vec!["$w".to_string()]);
+
+        db.set_channel_roll_commands(222, None).unwrap();
+        assert!(db.get_channel_roll_commands().unwrap().is_empty());
+    }
+}
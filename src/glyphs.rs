@@ -0,0 +1,199 @@
+use ratatui::symbols::border;
+use ratatui::widgets::Block;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+
+pub struct Glyphs {
+    pub separator: &'static str,
+    pub connected: &'static str,
+    pub connecting: &'static str,
+    pub disconnected: &'static str,
+    pub halted: &'static str,
+    pub paused: &'static str,
+    pub idle: &'static str,
+    pub uptime: &'static str,
+    pub info: &'static str,
+    pub success: &'static str,
+    pub warning: &'static str,
+    pub error: &'static str,
+    pub roll: &'static str,
+    pub claim: &'static str,
+    pub kakera: &'static str,
+    pub wish: &'static str,
+    pub power: &'static str,
+    pub divorce: &'static str,
+    pub heart: &'static str,
+    pub ring: &'static str,
+    pub books: &'static str,
+    pub search: &'static str,
+    pub pointer: &'static str,
+    pub bullet: &'static str,
+    pub cursor: &'static str,
+    pub arrows: &'static str,
+    pub rule: &'static str,
+}
+
+const UNICODE: Glyphs = Glyphs {
+    separator: "│",
+    connected: "●",
+    connecting: "◐",
+    disconnected: "○",
+    halted: "■",
+    paused: "⏸",
+    idle: "☾",
+    uptime: "⏱",
+    info: "ℹ",
+    success: "✓",
+    warning: "⚠",
+    error: "✗",
+    roll: "🎲",
+    claim: "💖",
+    kakera: "💎",
+    wish: "⭐",
+    power: "⚡",
+    divorce: "💔",
+    heart: "♥",
+    ring: "💍",
+    books: "📚",
+    search: "🔍",
+    pointer: "►",
+    bullet: "•",
+    cursor: "▌",
+    arrows: "↑↓",
+    rule: "─",
+};
+
+const ASCII: Glyphs = Glyphs {
+    separator: "|",
+    connected: "*",
+    connecting: "~",
+    disconnected: "o",
+    halted: "#",
+    paused: "||",
+    idle: "z",
+    uptime: "T",
+    info: "i",
+    success: "+",
+    warning: "!",
+    error: "x",
+    roll: "[R]",
+    claim: "[C]",
+    kakera: "[K]",
+    wish: "*",
+    power: "[P]",
+    divorce: "[D]",
+    heart: "<3",
+    ring: "[C]",
+    books: "[S]",
+    search: "?",
+    pointer: ">",
+    bullet: "-",
+    cursor: "_",
+    arrows: "Up/Dn",
+    rule: "-",
+};
+
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+pub fn set_ascii_mode(enabled: bool) {
+    ASCII_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn ascii_mode() -> bool {
+    ASCII_MODE.load(Ordering::Relaxed)
+}
+
+pub fn current() -> &'static Glyphs {
+    if ascii_mode() {
+        &ASCII
+    } else {
+        &UNICODE
+    }
+}
+
+pub fn border_set() -> border::Set {
+    if ascii_mode() {
+        ASCII_BORDER
+    } else {
+        border::PLAIN
+    }
+}
+
+pub fn block<'a>() -> Block<'a> {
+    Block::default().border_set(border_set())
+}
+
+pub fn text(value: &str) -> Cow<'_, str> {
+    if !ascii_mode() || value.is_ascii() {
+        return Cow::Borrowed(value);
+    }
+
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            c if c.is_ascii() => out.push(c),
+            '│' | '┃' => out.push('|'),
+            '─' | '━' | '–' | '—' => out.push('-'),
+            '×' => out.push('x'),
+            '≤' => out.push_str("<="),
+            '≥' => out.push_str(">="),
+            '•' | '·' => out.push('-'),
+            '★' | '⭐' => out.push('*'),
+            '⚠' => out.push('!'),
+            '✓' => out.push('+'),
+            '✗' => out.push('x'),
+            '►' | '▶' | '⇒' | '→' => out.push('>'),
+            '💖' | '❤' | '♥' => out.push_str("<3"),
+            c if c.is_alphanumeric() => out.push(c),
+            _ => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
+pub fn terminal_supports_unicode() -> bool {
+    let term = std::env::var("TERM").unwrap_or_default();
+    if matches!(term.as_str(), "dumb" | "linux" | "vt100" | "vt220" | "ansi") {
+        return false;
+    }
+    if cfg!(windows) {
+        return std::env::var_os("WT_SESSION").is_some() || std::env::var_os("TERM_PROGRAM").is_some();
+    }
+
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+        .map(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_falls_back_to_ascii() {
+        set_ascii_mode(false);
+        assert_eq!(text("⚠ Rem ×2"), "⚠ Rem ×2");
+
+        set_ascii_mode(true);
+        assert_eq!(text("⚠ Rem ×2 – 5 ≤ 6"), "! Rem x2 - 5 <= 6");
+        assert_eq!(text("Café 🎲"), "Café ");
+        assert_eq!(current().roll, "[R]");
+        set_ascii_mode(false);
+    }
+}
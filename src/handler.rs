@@ -1,27 +1,97 @@
-use crate::client::{DiscordClient, DiscordMessage, GatewayEvent};
+use crate::client::{client_error, ClientError, DiscordMessage, GatewayEvent, MessageSender};
 use crate::commands::CommandExecutor;
 use crate::config::Config;
-use crate::parser::{MudaeMessage, MudaeParser, ParsedCharacter};
+use crate::database::{ClaimRecord, Database};
+use crate::parser::{Gender, HaremPage, MudaeMessage, MudaeParser, ParsedCharacter};
+use crate::roll_advisor::RollAdvisor;
 use crate::search::{SearchRequest, SearchRequestReceiver, SearchResult};
-use crate::stats::{ChannelActivity, EventType, RollEntry, Stats};
+use crate::stats::{ChannelActivity, ClaimDecision, EventType, KakeraPower, PendingClaim, RollEntry, Stats};
+use crate::utils::truncate_string;
 use crate::verifier::CharacterVerifier;
 use crate::wishlist::WishlistManager;
-use chrono::Utc;
-use std::sync::Arc;
-use tokio::sync::{mpsc, oneshot, RwLock};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
 use tracing::{debug, warn};
 
+const CLAIM_WINDOW_MS: u64 = 100;
+const ROLL_CLAIM_WINDOW_SECS: u64 = 45;
+const CATCHUP_MAX_AGE_SECS: i64 = 60;
+const SEEN_MESSAGES_LIMIT: usize = 500;
+const CLAIM_RETRY_BACKOFF_MS: u64 = 400;
+const TAKEN_CHARACTERS_LIMIT: usize = 50;
+const FEED_CONTENT_LEN: usize = 50;
+
+static RESET_HOURS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(\d+)\s*(?:hours?|hrs?|h)\s*(?:(\d+)\s*(?:minutes?|mins?|m)?)?").unwrap()
+});
+
+static RESET_MINUTES_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(\d+)\s*(?:minutes?|mins?|m)\b").unwrap()
+});
+
+#[derive(Debug, Clone)]
+struct ClaimCandidate {
+    name: String,
+    series: String,
+    kakera_value: Option<u32>,
+    message_id: u64,
+    channel_id: u64,
+    guild_id: Option<u64>,
+    application_id: u64,
+    claim_button_id: Option<String>,
+    claim_emoji: Option<String>,
+    score: f64,
+    fuzzy_match: bool,
+    opens_at: Option<DateTime<Utc>>,
+    expires_at: tokio::time::Instant,
+}
+
+struct OwnedSync {
+    message_id: u64,
+    total_pages: u32,
+    pages: HashSet<u32>,
+    names: Vec<String>,
+}
+
+struct WishMatch {
+    priority: u8,
+    soft: bool,
+    fuzzy: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RemoteCommand {
+    Pause,
+    Resume,
+    Stop,
+}
+
 pub struct MessageHandler {
     config: Config,
     executor: Arc<CommandExecutor>,
     wishlist: Arc<WishlistManager>,
     verifier: Arc<CharacterVerifier>,
     stats: Arc<Stats>,
-    client: DiscordClient,
+    client: Arc<dyn MessageSender>,
+    db: Arc<Database>,
     user_id: u64,
     target_channels: Vec<u64>,
     pending_search: Arc<RwLock<Option<(u64, oneshot::Sender<Option<SearchResult>>)>>>,
     search_rx: SearchRequestReceiver,
+    claim_candidates: Arc<Mutex<Vec<ClaimCandidate>>>,
+    seen_messages: Mutex<VecDeque<u64>>,
+    taken_characters: Arc<Mutex<VecDeque<String>>>,
+    owned_characters: RwLock<HashSet<String>>,
+    owned_sync: Mutex<Option<OwnedSync>>,
+    halt_marker: PathBuf,
+    roll_advisor: Mutex<RollAdvisor>,
+    mudae_dm_channels: RwLock<HashSet<u64>>,
+    catch_up_tx: mpsc::Sender<Vec<DiscordMessage>>,
+    catch_up_rx: mpsc::Receiver<Vec<DiscordMessage>>,
 }
 
 impl MessageHandler {
@@ -32,20 +102,42 @@ impl MessageHandler {
         verifier: Arc<CharacterVerifier>,
         stats: Arc<Stats>,
         target_channels: Vec<u64>,
-        client: DiscordClient,
+        client: impl MessageSender + 'static,
+        db: Arc<Database>,
         search_rx: SearchRequestReceiver,
     ) -> Self {
+        let owned_characters = db
+            .get_owned_characters()
+            .unwrap_or_else(|e| {
+                warn!("Failed to load owned characters: {}", e);
+                Vec::new()
+            })
+            .into_iter()
+            .map(|name| name.to_lowercase())
+            .collect();
+        let (catch_up_tx, catch_up_rx) = mpsc::channel(1);
         Self {
             config,
             executor,
             wishlist,
             verifier,
             stats,
-            client,
+            client: Arc::new(client),
+            db,
             user_id: 0,
             target_channels,
             pending_search: Arc::new(RwLock::new(None)),
             search_rx,
+            claim_candidates: Arc::new(Mutex::new(Vec::new())),
+            seen_messages: Mutex::new(VecDeque::new()),
+            taken_characters: Arc::new(Mutex::new(VecDeque::new())),
+            owned_characters: RwLock::new(owned_characters),
+            owned_sync: Mutex::new(None),
+            halt_marker: Database::halt_marker_path(),
+            roll_advisor: Mutex::new(RollAdvisor::default()),
+            mudae_dm_channels: RwLock::new(HashSet::new()),
+            catch_up_tx,
+            catch_up_rx,
         }
     }
 
@@ -63,10 +155,15 @@ impl MessageHandler {
                 self.stats.set_username(username.clone()).await;
                 self.stats.set_connection_status(crate::stats::ConnectionStatus::Connected).await;
                 self.stats.log_event(EventType::Success, format!("Connected as {}", username)).await;
+                self.start_catch_up();
             }
             GatewayEvent::MessageCreate(message) => {
                 debug!("MessageCreate event received");
-                self.handle_message(message).await;
+                if self.mark_seen(message.id).await {
+                    self.handle_message(message).await;
+                } else {
+                    debug!("Message {} already handled by catch-up, skipping", message.id);
+                }
             }
             GatewayEvent::MessageUpdate(message) => {
                 debug!("MessageUpdate event received");
@@ -77,6 +174,10 @@ impl MessageHandler {
                     message_id, channel_id, user_id, emoji);
                 self.handle_reaction(message_id, channel_id, user_id, &emoji).await;
             }
+            GatewayEvent::Reconnected { resumed } => {
+                debug!("Reconnected event received (resumed={})", resumed);
+                self.handle_reconnect();
+            }
             GatewayEvent::Unknown(event_type) => {
                 debug!("Unknown event type: {}", event_type);
             }
@@ -85,13 +186,20 @@ impl MessageHandler {
 
     async fn handle_message(&self, message: DiscordMessage) {
         debug!("Handling message: channel={}, author={}, is_target={}", 
-            message.channel_id, message.author.username, self.is_target_channel(message.channel_id));
+            message.channel_id, message.author.username, self.is_target_channel(message.channel_id, message.parent_channel_id));
         
-        if !self.is_target_channel(message.channel_id) {
+        let remote_dm = message.guild_id.is_none() && self.config.remote_control_user_ids.contains(&message.author.id);
+        let mudae_dm = self.is_mudae_dm(&message).await;
+        if !remote_dm && !mudae_dm && !self.is_target_channel(message.channel_id, message.parent_channel_id) {
             debug!("Message from non-target channel {}, ignoring", message.channel_id);
             return;
         }
 
+        if message.is_webhook_or_system() {
+            debug!("Ignoring webhook/system message {} in channel {}", message.id, message.channel_id);
+            return;
+        }
+
         if self.is_mudae_message(&message) {
             debug!("Detected Mudae message from {}", message.author.username);
             self.handle_mudae_message(&message).await;
@@ -101,22 +209,113 @@ impl MessageHandler {
         }
     }
 
+    async fn mark_seen(&self, message_id: u64) -> bool {
+        let mut seen = self.seen_messages.lock().await;
+        if seen.contains(&message_id) {
+            return false;
+        }
+        if seen.len() >= SEEN_MESSAGES_LIMIT {
+            seen.pop_front();
+        }
+        seen.push_back(message_id);
+        true
+    }
+
+    fn start_catch_up(&self) {
+        if self.config.catchup_messages == 0 {
+            return;
+        }
+
+        let limit = self.config.catchup_messages.min(100) as u8;
+        let client = self.client.clone();
+        let channels = self.target_channels.clone();
+        let tx = self.catch_up_tx.clone();
+        tokio::spawn(async move {
+            let mut fetched = Vec::new();
+            for channel_id in channels {
+                match client.get_messages(channel_id, limit).await {
+                    Ok(messages) => fetched.extend(messages.into_iter().rev()),
+                    Err(e) => warn!("Catch-up scan failed for channel {}: {}", channel_id, e),
+                }
+            }
+            let _ = tx.send(fetched).await;
+        });
+    }
+
+    async fn handle_catch_up(&self, messages: Vec<DiscordMessage>) {
+        let username = self.stats.get_username().await;
+        let now = Utc::now();
+        let mut found = 0;
+
+        for message in messages {
+            let recent = crate::utils::snowflake_timestamp(message.id)
+                .is_some_and(|ts| (now - ts).num_seconds() <= CATCHUP_MAX_AGE_SECS);
+            if !recent || !self.is_mudae_message(&message) {
+                continue;
+            }
+
+            let MudaeMessage::CharacterRoll { character, message_id, channel_id, guild_id, application_id, claim_button_id, .. } =
+                MudaeParser::parse(&message, username.as_deref(), self.config.mudae_language)
+            else {
+                continue;
+            };
+            if character.is_claimed || self.wish_match(&character).await.is_none() {
+                continue;
+            }
+            if !self.mark_seen(message_id).await {
+                continue;
+            }
+
+            found += 1;
+            self.stats.log_event(
+                EventType::Wishlist,
+                format!("Catch-up found unclaimed roll: {} ({})", character.name, character.series),
+            ).await;
+            let name = character.name.clone();
+            let decision = self.handle_character_roll(character, message_id, channel_id, guild_id, application_id, claim_button_id).await;
+            debug!("Catch-up claim decision for {}: {}", name, decision);
+        }
+
+        debug!("Catch-up scan finished, {} wished rolls found", found);
+    }
+
+    fn handle_reconnect(&self) {
+        if self.config.on_reconnect_commands.is_empty() {
+            return;
+        }
+
+        let Some(&channel_id) = self.target_channels.first() else {
+            debug!("No target channel for reconnect commands");
+            return;
+        };
+
+        let executor = self.executor.clone();
+        let stats = self.stats.clone();
+        tokio::spawn(async move {
+            if let Err(e) = executor.execute_reconnect_commands(channel_id).await {
+                warn!("Failed to run reconnect commands: {}", e);
+                stats.log_event(EventType::Error, format!("Reconnect commands failed: {}", e)).await;
+            }
+        });
+    }
+
     async fn handle_user_message(&self, message: &DiscordMessage) {
         if message.content.is_empty() {
             debug!("User message has empty content, skipping");
             return;
         }
 
-        if message.author.id == self.user_id {
-            debug!("Skipping bot's own message to prevent duplicate logging");
+        if message.author.id == self.user_id && self.executor.is_recent_command(message.channel_id, &message.content).await {
+            debug!("Skipping echo of a recently sent command: {}", message.content);
             return;
         }
 
-        let content = if message.content.len() > 50 {
-            format!("{}...", &message.content[..47])
-        } else {
-            message.content.clone()
-        };
+        if let Some(command) = self.remote_command(message) {
+            self.handle_remote_command(message, command).await;
+            return;
+        }
+
+        let content = truncate_string(&message.content, FEED_CONTENT_LEN);
 
         debug!("Adding user message to channel activity: {}: {}", message.author.username, content);
         self.stats.add_channel_activity(ChannelActivity::UserMessage {
@@ -126,36 +325,89 @@ impl MessageHandler {
         debug!("Channel activity added successfully");
     }
 
+    fn remote_command(&self, message: &DiscordMessage) -> Option<RemoteCommand> {
+        if !self.config.remote_control_user_ids.contains(&message.author.id) {
+            return None;
+        }
+
+        let mut content = message.content.trim();
+        if message.guild_id.is_some() {
+            let mentions = [format!("<@{}>", self.user_id), format!("<@!{}>", self.user_id)];
+            content = mentions.iter().find_map(|mention| content.strip_prefix(mention.as_str()))?.trim();
+        }
+
+        match content.to_lowercase().as_str() {
+            "pause" => Some(RemoteCommand::Pause),
+            "resume" => Some(RemoteCommand::Resume),
+            "stop" => Some(RemoteCommand::Stop),
+            _ => None,
+        }
+    }
+
+    async fn handle_remote_command(&self, message: &DiscordMessage, command: RemoteCommand) {
+        let from = format!("{} ({})", message.author.username, message.author.id);
+        match command {
+            RemoteCommand::Pause => {
+                self.stats.set_paused(true);
+                self.stats.log_event(EventType::Warning, format!("Remote pause from {}", from)).await;
+            }
+            RemoteCommand::Resume => {
+                self.stats.set_paused(false);
+                if let Err(e) = self.stats.clear_halt(&self.halt_marker) {
+                    warn!("Failed to clear halt marker {}: {}", self.halt_marker.display(), e);
+                }
+                self.stats.log_event(EventType::Warning, format!("Remote resume from {}", from)).await;
+            }
+            RemoteCommand::Stop => {
+                if let Err(e) = self.stats.halt(&self.halt_marker) {
+                    warn!("Failed to write halt marker {}: {}", self.halt_marker.display(), e);
+                }
+                self.stats.log_event(EventType::Error, format!("Remote stop from {}: all outbound actions halted", from)).await;
+            }
+        }
+    }
+
     async fn handle_mudae_message(&self, message: &DiscordMessage) {
         debug!("Processing Mudae message: embeds={}, components={}", 
                message.embeds.len(), message.components.len());
 
         let username = self.stats.get_username().await;
-        let parsed = MudaeParser::parse(message, username.as_deref());
+        let parsed = MudaeParser::parse(message, username.as_deref(), self.config.mudae_language);
         
         debug!("Parsed message result: {:?}", std::mem::discriminant(&parsed));
         
         match parsed {
-            MudaeMessage::CharacterRoll { character, message_id, channel_id, has_claim_button, claim_button_id } => {
+            MudaeMessage::CharacterRoll { character, message_id, channel_id, guild_id, application_id, claim_button_id, .. } => {
                 debug!("Character roll detected: {} from {}", character.name, character.series);
 
-                self.stats.add_channel_activity(ChannelActivity::Roll {
-                    character_name: character.name.clone(),
-                    kakera_value: character.kakera_value,
-                    is_wished: character.is_wished,
-                    claimed: character.is_claimed,
-                }).await;
+                let activity_name = character.name.clone();
+                let kakera_value = character.kakera_value;
+                let is_wished = character.is_wished;
+                let claimed = character.is_claimed;
 
-                self.handle_character_roll(
-                    character,
+                let decision = self.handle_character_roll(
+                    character.clone(),
                     message_id,
                     channel_id,
-                    has_claim_button,
+                    guild_id,
+                    application_id,
                     claim_button_id,
                 ).await;
+                debug!("Claim decision for {}: {}", activity_name, decision);
+                if !decision.is_claim() && decision != ClaimDecision::Paused {
+                    self.mark_missed_wish(&character, channel_id, message_id).await;
+                }
+
+                self.stats.add_channel_activity(ChannelActivity::Roll {
+                    character_name: activity_name,
+                    kakera_value,
+                    is_wished,
+                    claimed,
+                    decision,
+                }).await;
             }
-            MudaeMessage::KakeraLoot { message_id, channel_id, kakera_type: _, button_id } => {
-                self.handle_kakera_loot(message_id, channel_id, button_id).await;
+            MudaeMessage::KakeraLoot { message_id, channel_id, guild_id, application_id, kakera_type: _, button_id } => {
+                self.handle_kakera_loot(message_id, channel_id, guild_id, application_id, button_id).await;
             }
             MudaeMessage::CharacterInfo { name, series, exists } => {
                 let mut pending = self.pending_search.write().await;
@@ -213,19 +465,108 @@ impl MessageHandler {
                 self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: msg.clone() }).await;
                 self.stats.log_event(EventType::Info, msg).await;
                 debug!("Rolls remaining: {}, reset: {:?}", count, reset_time);
+
+                let tip = {
+                    let mut advisor = self.roll_advisor.lock().await;
+                    advisor.observe(count, reset_datetime);
+                    advisor.tip_for(self.config.roll_cooldown_seconds)
+                };
+                if let Some(tip) = tip {
+                    self.stats.log_event(EventType::Info, tip).await;
+                }
             }
             MudaeMessage::ClaimAvailable { available, reset_time } => {
-                self.executor.set_claim_available(available).await;
-                self.stats.set_claim_available(available);
+                let claim_reset = reset_time.as_deref().and_then(Self::parse_reset_time);
+                self.stats.record_claim_report(available, claim_reset);
                 let status = if available { "Claim available!" } else { "Claim on cooldown" };
                 self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: status.to_string() }).await;
                 self.stats.log_event(EventType::Info, format!("Claim status: {}", status)).await;
                 debug!("Claim available: {}, reset: {:?}", available, reset_time);
             }
+            MudaeMessage::KakeraTransfer { from, to, amount } => {
+                let info_msg = format!("{} → {}: {} kakera", from, to, amount);
+                self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: info_msg }).await;
+
+                let is_recipient = username
+                    .as_deref()
+                    .map(|me| me.eq_ignore_ascii_case(&to))
+                    .unwrap_or(false);
+                if is_recipient {
+                    self.stats.add_kakera_received(amount as u64);
+                    self.stats.log_event(EventType::Kakera, format!("Received {} kakera from {}", amount, from)).await;
+                }
+            }
+            MudaeMessage::KakeraPower { power, can_react, is_me } => {
+                if is_me {
+                    self.stats.set_kakera_power(KakeraPower { power, can_react }).await;
+                }
+                self.stats.add_channel_activity(ChannelActivity::KakeraPower { power, can_react }).await;
+                debug!("Kakera power: {}% (can react: {})", power, can_react);
+            }
+            MudaeMessage::Divorce { character, is_me } => {
+                if is_me {
+                    self.stats.log_event(EventType::Info, format!("Divorced {}", character)).await;
+                }
+                self.stats.add_channel_activity(ChannelActivity::Divorce { character_name: character }).await;
+            }
+            MudaeMessage::Marriage { user, character } => {
+                let is_me = username.as_deref().is_some_and(|me| me.eq_ignore_ascii_case(&user));
+                if is_me {
+                    self.stats.record_successful_claim();
+                    let pending = self.stats.take_pending_claim(&character).await;
+                    if let Some(pending) = pending {
+                        self.record_confirmed_claim(&pending).await;
+                        self.spawn_auto_divorce(pending);
+                    }
+                } else {
+                    let mut taken = self.taken_characters.lock().await;
+                    if taken.len() >= TAKEN_CHARACTERS_LIMIT {
+                        taken.pop_front();
+                    }
+                    taken.push_back(character.to_lowercase());
+                }
+                self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: format!("{} married {}", user, character) }).await;
+            }
+            MudaeMessage::HaremPage(page) => {
+                if username.as_deref().is_some_and(|me| me.eq_ignore_ascii_case(&page.owner)) {
+                    self.handle_harem_page(page).await;
+                }
+            }
             MudaeMessage::DailyReady => {
                 self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: "Daily commands ready!".to_string() }).await;
                 self.stats.log_event(EventType::Info, "Daily commands ready".to_string()).await;
             }
+            MudaeMessage::DailyKakera { message_id, is_me: false, .. } => {
+                debug!("Ignoring someone else's daily kakera message {}", message_id);
+            }
+            MudaeMessage::DailyKakera { message_id, channel_id, guild_id, application_id, button_id, .. } => {
+                let executor = self.executor.clone();
+                let stats = self.stats.clone();
+                tokio::spawn(async move {
+                    match executor
+                        .execute_daily_kakera(channel_id, message_id, guild_id, application_id, button_id.as_deref())
+                        .await
+                    {
+                        Ok(true) => stats.log_event(EventType::Kakera, "Collected daily kakera".to_string()).await,
+                        Ok(false) => debug!("Ignoring daily kakera message {}", message_id),
+                        Err(e) => warn!("Failed to collect daily kakera: {}", e),
+                    }
+                });
+            }
+            MudaeMessage::ServerConfigNotice { text } => {
+                self.stats.log_event(EventType::Warning, format!("Mudae server settings changed: {}", text)).await;
+                self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: truncate_string(&text, FEED_CONTENT_LEN) }).await;
+            }
+            MudaeMessage::CaptchaPrompt { text } => {
+                if self.stats.pause_for_captcha() {
+                    warn!("Captcha prompt in channel {}, pausing: {}", message.channel_id, text);
+                    self.stats.log_event(
+                        EventType::Error,
+                        format!("CAPTCHA in channel {}: all activity paused, solve it in Discord then resume manually", message.channel_id),
+                    ).await;
+                }
+                self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: truncate_string(&text, FEED_CONTENT_LEN) }).await;
+            }
             MudaeMessage::Unknown => {
                 let mut pending = self.pending_search.write().await;
                 if let Some((expected_channel, _)) = pending.as_ref() {
@@ -275,11 +616,7 @@ impl MessageHandler {
                             self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: info_msg }).await;
                         }
                     } else if !message.content.is_empty() {
-                        let content = if message.content.len() > 50 {
-                            format!("{}...", &message.content[..47])
-                        } else {
-                            message.content.clone()
-                        };
+                        let content = truncate_string(&message.content, FEED_CONTENT_LEN);
                         self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: content }).await;
                     }
                     
@@ -288,11 +625,7 @@ impl MessageHandler {
                            embed.description.is_some(),
                            embed.image.is_some());
                 } else if !message.content.is_empty() {
-                    let content = if message.content.len() > 50 {
-                        format!("{}...", &message.content[..47])
-                    } else {
-                        message.content.clone()
-                    };
+                    let content = truncate_string(&message.content, FEED_CONTENT_LEN);
                     self.stats.add_channel_activity(ChannelActivity::MudaeInfo { message: content }).await;
                 } else {
                     debug!("Unknown Mudae message format (no embeds, no content)");
@@ -307,7 +640,12 @@ impl MessageHandler {
         *self.pending_search.write().await = Some((channel_id, response_tx));
         
         let search_cmd = format!("$im {}", query);
-        if let Err(e) = self.client.send_message(channel_id, &search_cmd).await {
+        let sent = if self.stats.is_halted() {
+            Err(anyhow::anyhow!("Outbound actions are halted"))
+        } else {
+            self.client.send_message(channel_id, &search_cmd).await
+        };
+        if let Err(e) = sent {
             warn!("Failed to send search command: {}", e);
             if let Some((_, tx)) = self.pending_search.write().await.take() {
                 let _ = tx.send(None);
@@ -328,16 +666,12 @@ impl MessageHandler {
         character: ParsedCharacter,
         message_id: u64,
         channel_id: u64,
-        has_claim_button: bool,
+        guild_id: Option<u64>,
+        application_id: u64,
         claim_button_id: Option<String>,
-    ) {
+    ) -> ClaimDecision {
         self.stats.increment_rolled();
-        
-        let current_rolls = self.stats.get_rolls_remaining();
-        if current_rolls > 0 {
-            self.stats.set_rolls_remaining(current_rolls - 1);
-        }
-        
+
         let roll_entry = RollEntry {
             timestamp: Utc::now(),
             character_name: character.name.clone(),
@@ -345,90 +679,413 @@ impl MessageHandler {
             kakera_value: character.kakera_value,
             claimed: character.is_claimed,
             is_wished: character.is_wished,
+            message_id,
+            channel_id,
+            guild_id,
+            application_id,
+            claim_button_id: claim_button_id.clone(),
+            claim_emoji: character.claim_emoji.clone(),
         };
         self.stats.add_roll(roll_entry).await;
 
+        if self.config.track_seen_characters {
+            if let Err(e) = self.db.record_seen_character(&character.name, &character.series, Utc::now()) {
+                warn!("Failed to record seen character {}: {}", character.name, e);
+            }
+        }
+
         if character.is_claimed {
-            debug!("Character already claimed, skipping");
-            return;
+            if !self.config.attempt_claim_on_belongs_to || claim_button_id.is_none() {
+                return ClaimDecision::AlreadyClaimed;
+            }
+            debug!("{} belongs to someone but still has a claim button, evaluating", character.name);
         }
 
         if self.stats.is_paused() {
-            debug!("Bot is paused, skipping claim");
-            return;
+            return ClaimDecision::Paused;
         }
 
-        if !self.executor.is_claim_available().await {
-            debug!("Claim not available, skipping");
-            return;
+        let opens_at = if self.stats.is_claim_available() {
+            None
+        } else {
+            match self.claim_grace_opening() {
+                Some(opens_at) => Some(opens_at),
+                None => return ClaimDecision::ClaimOnCooldown,
+            }
+        };
+
+        if self.is_owned(&character.name).await {
+            return ClaimDecision::AlreadyOwned;
         }
 
-        let should_claim = self.should_claim_character(&character).await;
-        
-        if should_claim {
+        let wish = self.wish_match(&character).await;
+        let priority = wish.as_ref().map(|w| w.priority);
+        let fuzzy_match = wish.as_ref().is_some_and(|w| w.fuzzy);
+        let is_wished = priority.is_some();
+        let soft_wish = wish.as_ref().is_some_and(|w| w.soft);
+        let decision = self.config.should_claim_character(&character, is_wished, soft_wish);
+        if decision == ClaimDecision::SoftWish {
+            self.stats.increment_wishlist_matches();
+            self.stats.log_event(
+                EventType::Wishlist,
+                format!("★ Soft wish rolled: {} ({}) - not claiming", character.name, character.series),
+            ).await;
+        }
+        if !decision.is_claim() {
+            return decision;
+        }
+
+        if !self.matches_gender_filter(&character, channel_id).await {
+            return ClaimDecision::GenderFilter;
+        }
+
+        if is_wished {
             self.stats.log_event(EventType::Wishlist, format!("Match found: {}", character.name)).await;
             self.stats.increment_wishlist_matches();
-            
-            let delay = 100 + rand::random::<u64>() % 500;
-            tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
-
-            let claim_result = if let Some(button_id) = claim_button_id {
-                match self.executor.execute_button_claim(channel_id, message_id, &button_id).await {
-                    Ok(_) => Ok(()),
-                    Err(e) => {
-                        warn!("Failed to click claim button: {}", e);
-                        self.executor.execute_claim(channel_id, message_id).await
-                    }
-                }
-            } else if has_claim_button {
-                self.executor.execute_claim(channel_id, message_id).await
-            } else {
-                self.executor.execute_claim(channel_id, message_id).await
+        } else {
+            self.stats.log_event(EventType::Info, format!("{} matches a claim rule", character.name)).await;
+        }
+
+        let candidate = ClaimCandidate {
+            name: character.name.clone(),
+            series: character.series.clone(),
+            kakera_value: character.kakera_value,
+            message_id,
+            channel_id,
+            guild_id,
+            application_id,
+            claim_button_id,
+            claim_emoji: character.claim_emoji.clone(),
+            score: self.config.claim_score(is_wished, priority.unwrap_or(0), character.kakera_value),
+            fuzzy_match,
+            opens_at,
+            expires_at: tokio::time::Instant::now() + tokio::time::Duration::from_secs(ROLL_CLAIM_WINDOW_SECS),
+        };
+        self.queue_claim(candidate).await;
+        decision
+    }
+
+    fn claim_grace_opening(&self) -> Option<DateTime<Utc>> {
+        let opens_at = self.stats.get_next_claim_reset()?;
+        let grace = self.config.claim_grace_seconds.min(ROLL_CLAIM_WINDOW_SECS) as i64;
+        (opens_at - Utc::now() <= chrono::Duration::seconds(grace)).then_some(opens_at)
+    }
+
+    async fn handle_harem_page(&self, page: HaremPage) {
+        let mut sync = self.owned_sync.lock().await;
+        if page.page == 1 && self.stats.take_owned_sync_request() {
+            *sync = Some(OwnedSync {
+                message_id: page.message_id,
+                total_pages: page.total_pages,
+                pages: HashSet::new(),
+                names: Vec::new(),
+            });
+        }
+
+        let Some(state) = sync.as_mut().filter(|state| state.message_id == page.message_id) else {
+            if let Err(e) = self.db.add_owned_characters(&page.characters) {
+                warn!("Failed to store owned characters: {}", e);
+            }
+            self.owned_characters.write().await.extend(page.characters.iter().map(|name| name.to_lowercase()));
+            return;
+        };
+
+        if state.pages.insert(page.page) {
+            state.names.extend(page.characters);
+        }
+        debug!("Owned sync: page {}/{} ({} names so far)", page.page, state.total_pages, state.names.len());
+
+        if state.pages.len() as u32 >= state.total_pages {
+            let names = std::mem::take(&mut state.names);
+            *sync = None;
+            if let Err(e) = self.db.replace_owned_characters(&names) {
+                self.stats.log_event(EventType::Error, format!("Failed to save owned characters: {}", e)).await;
+                return;
+            }
+            *self.owned_characters.write().await = names.iter().map(|name| name.to_lowercase()).collect();
+            self.stats.log_event(EventType::Success, format!("Synced {} owned characters", names.len())).await;
+            return;
+        }
+
+        let executor = self.executor.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(crate::utils::random_delay(1500, 3000)).await;
+            if let Err(e) = executor
+                .turn_page(page.channel_id, page.message_id, page.guild_id, page.application_id, page.next_button_id.as_deref())
+                .await
+            {
+                warn!("Failed to turn harem page: {}", e);
+            }
+        });
+    }
+
+    async fn mark_missed_wish(&self, character: &ParsedCharacter, channel_id: u64, message_id: u64) {
+        let Some(emoji) = self.config.mark_wished_emoji.as_deref() else {
+            return;
+        };
+        if self.wish_match(character).await.is_none() {
+            return;
+        }
+
+        let executor = self.executor.clone();
+        let emoji = emoji.to_string();
+        let name = character.name.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(crate::utils::random_delay(300, 1000)).await;
+            if let Err(e) = executor.execute_mark_wished(channel_id, message_id, &emoji).await {
+                warn!("Failed to mark missed wish {}: {}", name, e);
+            }
+        });
+    }
+
+    async fn is_owned(&self, name: &str) -> bool {
+        self.owned_characters.read().await.contains(&name.to_lowercase())
+    }
+
+    async fn wish_match(&self, character: &ParsedCharacter) -> Option<WishMatch> {
+        let wished = if self.config.wishlist_enabled {
+            self.wishlist.is_wished(&character.name, Some(&character.series)).await
+        } else {
+            None
+        };
+
+        match wished {
+            Some(wished) => Some(WishMatch {
+                priority: wished.priority,
+                soft: wished.soft,
+                fuzzy: !wished.name.eq_ignore_ascii_case(&character.name),
+            }),
+            None if character.is_wished && self.config.trust_mudae_wish => Some(WishMatch { priority: 0, soft: false, fuzzy: false }),
+            None => None,
+        }
+    }
+
+    async fn matches_gender_filter(&self, character: &ParsedCharacter, channel_id: u64) -> bool {
+        let Some(filter) = self.config.claim_gender_filter else {
+            return true;
+        };
+
+        let gender = match character.gender {
+            Some(gender) => Some(gender),
+            None => self.executor
+                .recent_roll_command(channel_id)
+                .await
+                .and_then(|cmd| Gender::from_roll_command(&cmd)),
+        };
+        !matches!(gender, Some(g) if g != filter)
+    }
+
+    async fn queue_claim(&self, candidate: ClaimCandidate) {
+        let opens_at = candidate.opens_at;
+        let mut candidates = self.claim_candidates.lock().await;
+        candidates.push(candidate);
+        self.stats.add_queued_actions(1);
+        if candidates.len() > 1 {
+            return;
+        }
+        drop(candidates);
+
+        let candidates = self.claim_candidates.clone();
+        let executor = self.executor.clone();
+        let stats = self.stats.clone();
+        let taken = self.taken_characters.clone();
+        let confirm_timeout = tokio::time::Duration::from_secs(self.config.claim_confirm_timeout_seconds);
+        let retries = self.config.claim_retries;
+        let retry_window = tokio::time::Duration::from_millis(self.config.claim_retry_window_ms);
+        let grace_wait = opens_at.and_then(|opens_at| (opens_at - Utc::now()).to_std().ok());
+        tokio::spawn(async move {
+            let mut wait = crate::utils::random_delay(CLAIM_WINDOW_MS, CLAIM_WINDOW_MS + 500);
+            if let Some(grace_wait) = grace_wait.filter(|grace_wait| *grace_wait > wait) {
+                stats.log_event(
+                    EventType::Info,
+                    format!("Claim opens in {}s, waiting to claim", grace_wait.as_secs().max(1)),
+                ).await;
+                wait = grace_wait;
+            }
+            tokio::time::sleep(wait).await;
+
+            let (best, skipped) = {
+                let mut candidates = candidates.lock().await;
+                stats.finish_queued_actions(candidates.len() as u64);
+                let now = tokio::time::Instant::now();
+                let open: Vec<ClaimCandidate> = candidates.drain(..).filter(|c| c.expires_at > now).collect();
+                let skipped = open.len().saturating_sub(1);
+                let best = open
+                    .into_iter()
+                    .reduce(|best, c| if c.score > best.score { c } else { best });
+                (best, skipped)
+            };
+            let Some(best) = best else {
+                debug!("Claim window closed before the claim opened");
+                return;
             };
 
-            match claim_result {
+            let claim_open = match best.opens_at {
+                Some(opens_at) => stats.get_next_claim_reset().is_none_or(|next| next <= opens_at),
+                None => stats.is_claim_available(),
+            };
+            if stats.is_paused() || !claim_open {
+                debug!("Claim no longer possible, dropping {}", best.name);
+                return;
+            }
+            if best.opens_at.is_some() && taken.lock().await.contains(&best.name.to_lowercase()) {
+                stats.log_event(EventType::Info, format!("{} was claimed by someone else while waiting", best.name)).await;
+                return;
+            }
+
+            if skipped > 0 {
+                stats.log_event(
+                    EventType::Info,
+                    format!("Picked {} over {} other candidate(s)", best.name, skipped),
+                ).await;
+            }
+
+            stats.add_pending_claim(PendingClaim {
+                name: best.name.clone(),
+                series: best.series.clone(),
+                message_id: best.message_id,
+                channel_id: best.channel_id,
+                kakera_value: best.kakera_value,
+                fuzzy_match: best.fuzzy_match,
+            }).await;
+            match Self::claim_with_retry(&executor, &stats, &taken, retries, retry_window, &best).await {
                 Ok(_) => {
-                    self.stats.increment_claimed();
-                    self.stats.log_event(EventType::Claim, format!("Claimed: {}", character.name)).await;
+                    debug!("Claim of {} sent, waiting for Mudae to confirm", best.name);
+                    stats.expire_pending_claim(&best.name, best.message_id, confirm_timeout).await;
                 }
                 Err(e) => {
-                    self.stats.log_event(EventType::Error, format!("Failed to claim {}: {}", character.name, e)).await;
+                    stats.cancel_pending_claim(&best.name, best.message_id).await;
+                    stats.log_event(EventType::Error, format!("Failed to claim {}: {}", best.name, e)).await;
                     warn!("Failed to claim: {}", e);
                 }
             }
-        }
+        });
     }
 
-    async fn should_claim_character(&self, character: &ParsedCharacter) -> bool {
-        if character.is_wished {
-            return true;
+    async fn record_confirmed_claim(&self, pending: &PendingClaim) {
+        self.stats.increment_claimed();
+        self.stats.mark_roll_claimed(pending.message_id).await;
+        self.stats.log_event(EventType::Claim, format!("Claimed: {}", pending.name)).await;
+        let record = ClaimRecord {
+            character_name: pending.name.clone(),
+            series: pending.series.clone(),
+            kakera_value: pending.kakera_value,
+            channel_id: pending.channel_id,
+            claimed_at: Utc::now(),
+        };
+        if let Err(e) = self.db.add_claim(&record) {
+            warn!("Failed to record claim: {}", e);
         }
+        self.stats.refresh_claimed_value(&self.db);
+    }
 
-        if self.config.wishlist_enabled {
-            if let Some(_wished) = self.wishlist.is_wished(&character.name, Some(&character.series)).await {
-                return true;
+    fn spawn_auto_divorce(&self, pending: PendingClaim) {
+        let executor = self.executor.clone();
+        let stats = self.stats.clone();
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            match executor
+                .execute_auto_divorce(pending.channel_id, &pending.name, pending.fuzzy_match, pending.kakera_value)
+                .await
+            {
+                Ok(true) => {
+                    if let Err(e) = db.remove_latest_claim(&pending.name) {
+                        warn!("Failed to remove divorced claim: {}", e);
+                    }
+                    stats.refresh_claimed_value(&db);
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    stats.log_event(EventType::Error, format!("Auto-divorce of {} failed: {}", pending.name, e)).await;
+                }
             }
-        }
+        });
+    }
 
-        false
+    async fn claim_with_retry(
+        executor: &CommandExecutor,
+        stats: &Stats,
+        taken: &Mutex<VecDeque<String>>,
+        retries: u32,
+        retry_window: tokio::time::Duration,
+        candidate: &ClaimCandidate,
+    ) -> anyhow::Result<()> {
+        let started = tokio::time::Instant::now();
+        let mut attempt = 0;
+        loop {
+            let error = match executor
+                .claim_message(
+                    candidate.channel_id,
+                    candidate.message_id,
+                    candidate.guild_id,
+                    candidate.application_id,
+                    candidate.claim_button_id.as_deref(),
+                    candidate.claim_emoji.as_deref(),
+                )
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            let kind = client_error(&error);
+            if attempt >= retries
+                || started.elapsed() >= retry_window
+                || stats.is_halted()
+                || !stats.is_claim_available()
+                || kind.is_some_and(ClientError::is_permanent)
+            {
+                return Err(error);
+            }
+
+            attempt += 1;
+            let backoff = match kind {
+                Some(ClientError::RateLimited { retry_after: Some(wait) }) if started.elapsed() + *wait >= retry_window => {
+                    return Err(error);
+                }
+                Some(ClientError::RateLimited { retry_after: Some(wait) }) => *wait,
+                _ => tokio::time::Duration::from_millis(CLAIM_RETRY_BACKOFF_MS * attempt as u64),
+            };
+            stats.increment_claim_retries();
+            stats.log_event(
+                EventType::Warning,
+                format!("Claim of {} failed ({}), retrying {}/{}", candidate.name, error, attempt, retries),
+            ).await;
+            tokio::time::sleep(backoff).await;
+
+            if taken.lock().await.contains(&candidate.name.to_lowercase()) {
+                anyhow::bail!("{} was claimed by someone else", candidate.name);
+            }
+        }
     }
 
     async fn handle_kakera_loot(
         &self,
         message_id: u64,
         channel_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
         button_id: Option<String>,
     ) {
-        if !self.config.auto_react_kakera {
+        if !self.config.auto_react_kakera || self.stats.is_paused() {
+            return;
+        }
+
+        let power = self.stats.get_kakera_power().await;
+        if !self.config.kakera_react_allowed(power) {
+            let power = power.map(|p| format!("{}%", p.power)).unwrap_or_default();
+            self.stats.log_event(EventType::Kakera, format!("Kakera detected, conserving power ({})", power)).await;
             return;
         }
 
         self.stats.log_event(EventType::Kakera, "Kakera detected".to_string()).await;
-        
-        let delay = 50 + rand::random::<u64>() % 200;
-        tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+        self.stats.add_queued_actions(1);
+
+        tokio::time::sleep(crate::utils::random_delay(50, 250)).await;
 
-        match self.executor.execute_kakera_react(channel_id, message_id, button_id.as_deref()).await {
+        let reacted = self.executor.execute_kakera_react(channel_id, message_id, guild_id, application_id, button_id.as_deref()).await;
+        self.stats.finish_queued_actions(1);
+        match reacted {
             Ok(_) => {
                 self.stats.increment_kakera();
                 self.stats.log_event(EventType::Success, "Kakera collected".to_string()).await;
@@ -457,11 +1114,25 @@ impl MessageHandler {
         );
     }
 
-    fn is_target_channel(&self, channel_id: u64) -> bool {
+    fn is_target_channel(&self, channel_id: u64, parent_channel_id: Option<u64>) -> bool {
         if self.target_channels.is_empty() {
             return true;
         }
         self.target_channels.contains(&channel_id)
+            || (self.config.include_threads && parent_channel_id.is_some_and(|parent| self.target_channels.contains(&parent)))
+    }
+
+    async fn is_mudae_dm(&self, message: &DiscordMessage) -> bool {
+        if !self.config.include_dms || message.guild_id.is_some() {
+            return false;
+        }
+        if message.author.id == Config::mudae_bot_id() {
+            if self.mudae_dm_channels.write().await.insert(message.channel_id) {
+                debug!("Monitoring DM channel {} with Mudae", message.channel_id);
+            }
+            return true;
+        }
+        self.mudae_dm_channels.read().await.contains(&message.channel_id)
     }
 
     fn is_mudae_message(&self, message: &DiscordMessage) -> bool {
@@ -470,21 +1141,19 @@ impl MessageHandler {
     }
 
     fn parse_reset_time(reset_time_str: &str) -> Option<chrono::DateTime<Utc>> {
-        use regex::Regex;
-        use chrono::Utc;
-        
-        let hours_regex = Regex::new(r"(\d+)\s*h(?:our|ours|r|rs)?\s*(?:(\d+)\s*m(?:in|inute|inutes)?)?").ok()?;
-        let minutes_regex = Regex::new(r"(\d+)\s*m(?:in|inute|inutes)?").ok()?;
-        
-        if let Some(caps) = hours_regex.captures(reset_time_str) {
+        Self::parse_reset_duration(reset_time_str).map(|duration| Utc::now() + duration)
+    }
+
+    fn parse_reset_duration(reset_time_str: &str) -> Option<chrono::Duration> {
+        if let Some(caps) = RESET_HOURS_REGEX.captures(reset_time_str) {
             let hours: i64 = caps.get(1)?.as_str().parse().ok()?;
             let minutes: i64 = caps.get(2)
                 .and_then(|m| m.as_str().parse().ok())
                 .unwrap_or(0);
-            Some(Utc::now() + chrono::Duration::hours(hours) + chrono::Duration::minutes(minutes))
-        } else if let Some(caps) = minutes_regex.captures(reset_time_str) {
+            Some(chrono::Duration::hours(hours) + chrono::Duration::minutes(minutes))
+        } else if let Some(caps) = RESET_MINUTES_REGEX.captures(reset_time_str) {
             let minutes: i64 = caps.get(1)?.as_str().parse().ok()?;
-            Some(Utc::now() + chrono::Duration::minutes(minutes))
+            Some(chrono::Duration::minutes(minutes))
         } else {
             None
         }
@@ -509,6 +1178,10 @@ pub async fn run_event_loop(
                 debug!("Received search request: {}", search_req.query);
                 handler.handle_search_request(search_req).await;
             }
+            Some(messages) = handler.catch_up_rx.recv() => {
+                debug!("Received {} catch-up messages", messages.len());
+                handler.handle_catch_up(messages).await;
+            }
             else => {
                 debug!("Event loop ending - channel closed");
                 break;
@@ -518,3 +1191,840 @@ pub async fn run_event_loop(
     
     stats.log_event(EventType::Warning, "Event loop ended".to_string()).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::{MockSender, SentCall};
+    use crate::client::{Author, Button, ButtonEmoji, Component, DiscordClient, Embed, EmbedAuthor, EmbedFooter};
+    use crate::search::create_search_channel;
+    use crate::wishlist::WishedCharacter;
+
+    const CHANNEL_ID: u64 = 100;
+    const GUILD_ID: u64 = 200;
+
+    fn test_handler(sender: MockSender) -> MessageHandler {
+        test_handler_with_wishlist(sender, Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true)))
+    }
+
+    fn test_handler_with_wishlist(sender: MockSender, wishlist: Arc<WishlistManager>) -> MessageHandler {
+        test_handler_with_config(sender, wishlist, Config::default())
+    }
+
+    fn test_handler_with_config(sender: MockSender, wishlist: Arc<WishlistManager>, config: Config) -> MessageHandler {
+        let stats = Stats::new();
+        let executor = Arc::new(CommandExecutor::new(sender.clone(), config.clone(), stats.clone()));
+        let verifier = Arc::new(CharacterVerifier::new(DiscordClient::new("test-token".to_string()), CHANNEL_ID));
+        let db = Arc::new(Database::open_in_memory().unwrap());
+        let (_search_tx, search_rx) = create_search_channel();
+        MessageHandler::new(config, executor, wishlist, verifier, stats, vec![CHANNEL_ID], sender, db, search_rx)
+    }
+
+    fn roll_message(id: u64, name: &str, description: &str) -> DiscordMessage {
+        DiscordMessage {
+            id,
+            channel_id: CHANNEL_ID,
+            parent_channel_id: None,
+            guild_id: Some(GUILD_ID),
+            author: Author {
+                id: Config::mudae_bot_id(),
+                username: "Mudae".to_string(),
+                bot: true,
+            },
+            content: String::new(),
+            embeds: vec![Embed {
+                author: Some(EmbedAuthor { name: name.to_string() }),
+                description: Some(description.to_string()),
+                ..Default::default()
+            }],
+            components: vec![Component {
+                component_type: 1,
+                components: vec![Button {
+                    button_type: 2,
+                    style: Some(2),
+                    label: None,
+                    custom_id: Some(format!("marry-{}", id)),
+                    emoji: Some(ButtonEmoji { name: Some("💖".to_string()), id: None }),
+                }],
+            }],
+            webhook_id: None,
+            message_type: 0,
+        }
+    }
+
+    fn user_message(id: u64, username: &str, content: &str) -> DiscordMessage {
+        DiscordMessage {
+            id,
+            channel_id: CHANNEL_ID,
+            parent_channel_id: None,
+            guild_id: Some(GUILD_ID),
+            author: Author {
+                id: 300,
+                username: username.to_string(),
+                bot: false,
+            },
+            content: content.to_string(),
+            embeds: Vec::new(),
+            components: Vec::new(),
+            webhook_id: None,
+            message_type: 0,
+        }
+    }
+
+    async fn settle_claim_window() {
+        tokio::time::sleep(tokio::time::Duration::from_millis(CLAIM_WINDOW_MS + 1000)).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wished_roll_claims_once() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        assert_eq!(
+            sender.calls(),
+            vec![SentCall::Button {
+                message_id: 1,
+                channel_id: CHANNEL_ID,
+                guild_id: Some(GUILD_ID),
+                application_id: Config::mudae_bot_id(),
+                custom_id: "marry-1".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unwished_roll_is_not_claimed() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Subaru", "Re:Zero"))).await;
+        settle_claim_window().await;
+
+        assert!(sender.calls().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_roll_feed_records_claim_decision() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Subaru", "Re:Zero"))).await;
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        let decisions: Vec<ClaimDecision> = handler
+            .stats
+            .get_channel_activity()
+            .await
+            .into_iter()
+            .filter_map(|activity| match activity {
+                ChannelActivity::Roll { decision, .. } => Some(decision),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(decisions, vec![ClaimDecision::NotWished, ClaimDecision::Claiming { wished: true }]);
+        assert_eq!(ClaimDecision::ClaimOnCooldown.to_string(), "skipped (claim on cooldown)");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_only_one_claim_per_window() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Ram", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        let claims: Vec<_> = sender.calls().into_iter().filter(|c| matches!(c, SentCall::Button { .. })).collect();
+        assert_eq!(claims.len(), 1);
+    }
+
+    fn claimed_roll_message(id: u64, name: &str) -> DiscordMessage {
+        roll_message(id, name, "Re:Zero 💖\nBelongs to alice")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_belongs_to_is_not_claimed_by_default() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        handler.handle_event(GatewayEvent::MessageCreate(claimed_roll_message(1, "Rem"))).await;
+        settle_claim_window().await;
+
+        assert!(sender.calls().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_belongs_to_claimed_when_enabled_and_button_present() {
+        let config = Config {
+            attempt_claim_on_belongs_to: true,
+            ..Config::default()
+        };
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let sender = MockSender::default();
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+
+        let mut without_button = claimed_roll_message(2, "Ram");
+        without_button.components.clear();
+        handler.handle_event(GatewayEvent::MessageCreate(without_button)).await;
+        settle_claim_window().await;
+        assert!(sender.calls().is_empty());
+
+        handler.handle_event(GatewayEvent::MessageCreate(claimed_roll_message(1, "Rem"))).await;
+        settle_claim_window().await;
+        assert!(matches!(sender.calls().as_slice(), [SentCall::Button { message_id: 1, .. }]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_claim_is_retried() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+        sender.fail_next(2);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        assert!(matches!(sender.calls().as_slice(), [SentCall::Button { message_id: 1, .. }]));
+        assert_eq!(handler.stats.get_claim_retries(), 1);
+        assert_eq!(handler.stats.get_claimed(), 0);
+
+        let mut married = user_message(2, "Mudae", "💖 **me** and **Rem** are now married! 💖");
+        married.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(married)).await;
+        assert_eq!(handler.stats.get_claimed(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_forbidden_claim_is_not_retried() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        sender.fail_next_with_status(2, 403);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        assert!(sender.calls().is_empty());
+        assert_eq!(handler.stats.get_claim_retries(), 0);
+        let log = handler.stats.get_activity_log().await;
+        assert!(log.iter().any(|e| e.message.contains("Failed to claim Rem: No access to channel 100 (HTTP 403)")));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_claim_retry_stops_when_taken() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        sender.fail_next(2);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        let mut married = user_message(2, "Mudae", "💖 **alice** and **Rem** are now married! 💖");
+        married.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(married)).await;
+        settle_claim_window().await;
+
+        assert!(sender.calls().is_empty());
+        assert_eq!(handler.stats.get_claim_retries(), 1);
+        assert_eq!(handler.stats.get_claimed(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_halt_blocks_claims() {
+        let marker = std::env::temp_dir().join(format!("mudae-halt-handler-{}", std::process::id()));
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.stats.halt(&marker).unwrap();
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        assert!(sender.calls().is_empty());
+        handler.stats.clear_halt(&marker).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_own_marriage_uses_up_claim() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+
+        let mut married = user_message(1, "Mudae", "💖 **me** and **Emilia** are now married! 💖");
+        married.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(married)).await;
+        assert!(!handler.stats.is_claim_available());
+        assert!(handler.stats.get_next_claim_reset().is_some());
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+        assert!(sender.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_only_own_divorces_are_logged() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+
+        for (id, content) in [(1, "💔 **alice** and **Ram** are now divorced."), (2, "💔 **me** and **Rem** are now divorced.")] {
+            let mut divorce = user_message(id, "Mudae", content);
+            divorce.author.id = Config::mudae_bot_id();
+            handler.handle_event(GatewayEvent::MessageCreate(divorce)).await;
+        }
+
+        let log = handler.stats.get_activity_log().await;
+        let divorces: Vec<_> = log.iter().filter(|e| e.message.starts_with("Divorced")).map(|e| e.message.as_str()).collect();
+        assert_eq!(divorces, vec!["Divorced Rem"]);
+        let feed = handler.stats.get_channel_activity().await;
+        assert_eq!(feed.iter().filter(|a| matches!(a, ChannelActivity::Divorce { .. })).count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_only_own_kakera_power_is_tracked() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+
+        let mut theirs = user_message(1, "Mudae", "**someone**, you can't react to kakera for **1h 20** min. Power: 10%");
+        theirs.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(theirs)).await;
+        assert_eq!(handler.stats.get_kakera_power().await, None);
+
+        let mut mine = user_message(2, "Mudae", "**me**, you __can__ react to kakera right now! Power: **110%**");
+        mine.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(mine)).await;
+        assert_eq!(handler.stats.get_kakera_power().await, Some(KakeraPower { power: 110, can_react: true }));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_claim_grace_waits_for_claim_to_open() {
+        let sender = MockSender::default();
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let config = Config { claim_grace_seconds: 10, ..Config::default() };
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+        let drift = crate::claim_state::RESET_DRIFT_SECS;
+
+        handler.stats.record_claim_report(false, Some(Utc::now() - chrono::Duration::seconds(drift - 20)));
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Ram", "Re:Zero 💖"))).await;
+        tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+        assert!(sender.calls().is_empty());
+
+        handler.stats.record_claim_report(false, Some(Utc::now() - chrono::Duration::seconds(drift - 5)));
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+        assert!(sender.calls().is_empty());
+        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+        assert_eq!(sender.calls().len(), 1);
+
+        handler.stats.record_claim_report(false, Some(Utc::now() - chrono::Duration::seconds(drift - 5)));
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(3, "Emilia", "Re:Zero 💖"))).await;
+        handler.stats.set_paused(true);
+        tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+        assert_eq!(sender.calls().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unconfirmed_claim_warns_after_timeout() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+        let timeout = tokio::time::Duration::from_secs(handler.config.claim_confirm_timeout_seconds);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+        let mut married = user_message(2, "Mudae", "💖 **me** and **Rem** are now married! 💖");
+        married.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(married)).await;
+        tokio::time::sleep(timeout).await;
+
+        let log = handler.stats.get_activity_log().await;
+        assert!(!log.iter().any(|e| e.message.contains("unconfirmed")));
+
+        handler.stats.record_claim_report(true, None);
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(3, "Ram", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+        tokio::time::sleep(timeout).await;
+
+        let log = handler.stats.get_activity_log().await;
+        let warnings: Vec<_> = log.iter().filter(|e| e.message.contains("unconfirmed")).collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].event_type, EventType::Warning);
+        assert_eq!(warnings[0].message, "Claim of Ram unconfirmed — likely lost");
+        assert_eq!(handler.stats.get_claimed(), 1);
+        assert_eq!(handler.db.get_claims().unwrap().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_manual_claim_counts_only_once_confirmed() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero"))).await;
+        settle_claim_window().await;
+        let entry = handler.stats.find_claimable_roll("rem").await.unwrap();
+        handler.executor.claim_roll(&entry).await.unwrap();
+        assert_eq!(sender.calls().len(), 1);
+        assert_eq!(handler.stats.get_claimed(), 0);
+        assert!(handler.db.get_claims().unwrap().is_empty());
+        assert!(handler.stats.find_claimable_roll("rem").await.is_some());
+
+        let mut married = user_message(2, "Mudae", "💖 **me** and **Rem** are now married! 💖");
+        married.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(married)).await;
+        assert_eq!(handler.stats.get_claimed(), 1);
+        assert_eq!(handler.db.get_claims().unwrap().len(), 1);
+        assert!(handler.stats.find_claimable_roll("rem").await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rolls_remaining_counts_each_roll_once() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.stats.set_rolls_remaining(5);
+
+        handler.executor.execute_manual_roll(CHANNEL_ID).await.unwrap();
+        assert_eq!(handler.stats.get_rolls_remaining(), 4);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Subaru", "Re:Zero"))).await;
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Beatrice", "Re:Zero"))).await;
+        assert_eq!(handler.stats.get_rolls_remaining(), 4);
+
+        let mut rolls_left = user_message(3, "Mudae", "7 rolls left, next reset in 45 min");
+        rolls_left.author.id = Config::mudae_bot_id();
+        rolls_left.author.bot = true;
+        handler.handle_event(GatewayEvent::MessageCreate(rolls_left)).await;
+        assert_eq!(handler.stats.get_rolls_remaining(), 7);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_claims_count_as_pending_actions() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Ram", "Re:Zero 💖"))).await;
+        assert_eq!(handler.stats.get_pending_actions(), 2);
+
+        settle_claim_window().await;
+        assert_eq!(handler.stats.get_pending_actions(), 0);
+        assert_eq!(handler.stats.stats_snapshot().pending_actions, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_captcha_prompt_pauses_until_resumed() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        let mut captcha = user_message(1, "Mudae", "Human verification required: solve the captcha to keep using commands.");
+        captcha.author.id = Config::mudae_bot_id();
+        captcha.author.bot = true;
+        handler.handle_event(GatewayEvent::MessageCreate(captcha)).await;
+
+        assert!(handler.stats.is_paused());
+        assert!(handler.stats.has_captcha_alert());
+        let log = handler.stats.get_activity_log().await;
+        assert!(log.iter().any(|e| e.event_type == EventType::Error && e.message.contains("CAPTCHA")));
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+        assert!(sender.calls().is_empty());
+
+        assert!(!handler.stats.toggle_paused());
+        assert!(!handler.stats.has_captcha_alert());
+    }
+
+    #[tokio::test]
+    async fn test_rolls_remaining_suggests_cooldown_once() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        for (id, content) in [(1, "10 rolls left, next reset in 58 min"), (2, "9 rolls left, next reset in 57 min")] {
+            let mut message = user_message(id, "Mudae", content);
+            message.author.id = Config::mudae_bot_id();
+            message.author.bot = true;
+            handler.handle_event(GatewayEvent::MessageCreate(message)).await;
+        }
+
+        let log = handler.stats.get_activity_log().await;
+        let tips: Vec<_> = log.iter().filter(|e| e.message.starts_with("Tip:")).collect();
+        assert_eq!(tips.len(), 1);
+        assert_eq!(tips[0].message, "Tip: set cooldown to ~360s to spread 10 rolls/hour");
+        assert_eq!(tips[0].event_type, EventType::Info);
+    }
+
+    fn recent_id(offset: u64) -> u64 {
+        let millis = Utc::now().timestamp_millis() as u64 - 1_420_070_400_000;
+        (millis << 22) + offset
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_catch_up_claims_recent_wished_roll_once() {
+        let config = Config {
+            catchup_messages: 10,
+            ..Config::default()
+        };
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let sender = MockSender::default();
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+
+        let wished = roll_message(recent_id(1), "Rem", "Re:Zero 💖");
+        sender.set_history(vec![
+            wished.clone(),
+            roll_message(recent_id(2), "Subaru", "Re:Zero"),
+            roll_message(3, "Ram", "Re:Zero 💖"),
+        ]);
+
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+        handler.handle_event(GatewayEvent::MessageCreate(wished.clone())).await;
+        let messages = handler.catch_up_rx.recv().await.unwrap();
+        assert_eq!(messages.len(), 3);
+        handler.handle_catch_up(messages).await;
+        settle_claim_window().await;
+
+        assert!(matches!(sender.calls().as_slice(), [SentCall::Button { message_id, .. }] if *message_id == wished.id));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_message_is_ignored() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        let mut webhook = user_message(1, "GitHub", "New commit pushed");
+        webhook.webhook_id = Some(400);
+        handler.handle_event(GatewayEvent::MessageCreate(webhook)).await;
+
+        let mut pin_notice = user_message(2, "alice", "pinned a message");
+        pin_notice.message_type = 6;
+        handler.handle_event(GatewayEvent::MessageCreate(pin_notice)).await;
+
+        assert!(handler.stats.get_channel_activity().await.is_empty());
+
+        handler.handle_event(GatewayEvent::MessageCreate(user_message(3, "alice", "$wa"))).await;
+        assert_eq!(handler.stats.get_channel_activity().await.len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_mudae_wish_ignored_when_untrusted() {
+        let sender = MockSender::default();
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let config = Config { trust_mudae_wish: false, ..Config::default() };
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        assert!(sender.calls().is_empty());
+        assert_eq!(handler.stats.get_wishlist_matches(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_remote_control_from_authorized_user() {
+        let sender = MockSender::default();
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let config = Config { remote_control_user_ids: vec![300], ..Config::default() };
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+        handler.halt_marker = std::env::temp_dir().join(format!("mudae-halt-remote-{}", std::process::id()));
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+
+        let dm = |id, author, content: &str| {
+            let mut message = user_message(id, "owner", content);
+            message.author.id = author;
+            message.channel_id = 555;
+            message.guild_id = None;
+            message
+        };
+
+        handler.handle_event(GatewayEvent::MessageCreate(dm(1, 400, "pause"))).await;
+        assert!(!handler.stats.is_paused());
+
+        handler.handle_event(GatewayEvent::MessageCreate(dm(2, 300, "Pause"))).await;
+        assert!(handler.stats.is_paused());
+
+        handler.handle_event(GatewayEvent::MessageCreate(user_message(3, "owner", "resume"))).await;
+        assert!(handler.stats.is_paused());
+        handler.handle_event(GatewayEvent::MessageCreate(user_message(4, "owner", "<@1> resume"))).await;
+        assert!(!handler.stats.is_paused());
+
+        handler.handle_event(GatewayEvent::MessageCreate(dm(5, 300, "stop"))).await;
+        assert!(handler.stats.is_halted());
+        assert!(handler.halt_marker.exists());
+        handler.handle_event(GatewayEvent::MessageCreate(dm(6, 300, "resume"))).await;
+        assert!(!handler.stats.is_halted());
+        assert!(!handler.halt_marker.exists());
+
+        let log = handler.stats.get_activity_log().await;
+        assert_eq!(log.iter().filter(|e| e.message.starts_with("Remote ")).count(), 4);
+        assert!(sender.calls().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_thread_rolls_follow_include_threads() {
+        const THREAD_ID: u64 = 999;
+        let thread_roll = |id| {
+            let mut message = roll_message(id, "Rem", "Re:Zero 💖");
+            message.channel_id = THREAD_ID;
+            message.parent_channel_id = Some(CHANNEL_ID);
+            message
+        };
+
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::MessageCreate(thread_roll(1))).await;
+        settle_claim_window().await;
+        assert!(sender.calls().is_empty());
+
+        let sender = MockSender::default();
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let config = Config { include_threads: true, ..Config::default() };
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+        handler.handle_event(GatewayEvent::MessageCreate(thread_roll(2))).await;
+        settle_claim_window().await;
+        assert!(matches!(sender.calls().as_slice(), [SentCall::Button { message_id: 2, channel_id: THREAD_ID, .. }]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_mudae_dm_rolls_follow_include_dms() {
+        const DM_CHANNEL_ID: u64 = 555;
+        let dm_roll = |id, author_id| {
+            let mut message = roll_message(id, "Rem", "Re:Zero 💖");
+            message.channel_id = DM_CHANNEL_ID;
+            message.guild_id = None;
+            message.author.id = author_id;
+            message
+        };
+
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::MessageCreate(dm_roll(1, Config::mudae_bot_id()))).await;
+        settle_claim_window().await;
+        assert!(sender.calls().is_empty());
+
+        let sender = MockSender::default();
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let config = Config { include_dms: true, ..Config::default() };
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+        handler.handle_event(GatewayEvent::MessageCreate(dm_roll(2, 42))).await;
+        settle_claim_window().await;
+        assert!(sender.calls().is_empty());
+
+        handler.handle_event(GatewayEvent::MessageCreate(dm_roll(3, Config::mudae_bot_id()))).await;
+        settle_claim_window().await;
+        assert!(matches!(
+            sender.calls().as_slice(),
+            [SentCall::Button { message_id: 3, channel_id: DM_CHANNEL_ID, guild_id: None, .. }]
+        ));
+        assert!(handler.mudae_dm_channels.read().await.contains(&DM_CHANNEL_ID));
+    }
+
+    #[test]
+    fn test_parse_reset_duration_phrasings() {
+        let cases = [
+            ("45m", Some(45)),
+            ("5m", Some(5)),
+            ("90 minutes", Some(90)),
+            ("12 min", Some(12)),
+            ("12 mins", Some(12)),
+            ("1 minute", Some(1)),
+            ("2h", Some(120)),
+            ("1 hour", Some(60)),
+            ("3 hrs", Some(180)),
+            ("1h 23m", Some(83)),
+            ("2h 05m", Some(125)),
+            ("1h23m", Some(83)),
+            ("1h 23", Some(83)),
+            ("2 hours 5 minutes", Some(125)),
+            ("1 hour 30 min", Some(90)),
+            ("1H 5M", Some(65)),
+            ("0m", Some(0)),
+            ("soon", None),
+            ("", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                MessageHandler::parse_reset_duration(input).map(|d| d.num_minutes()),
+                expected,
+                "{}",
+                input,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_other_users_repeating_a_command_stay_in_feed() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+        handler.executor.execute_scheduled_command(CHANNEL_ID, "$wa").await.unwrap();
+
+        let mut echo = user_message(1, "me", "$wa");
+        echo.author.id = 1;
+        handler.handle_event(GatewayEvent::MessageCreate(echo)).await;
+        handler.handle_event(GatewayEvent::MessageCreate(user_message(2, "someone", "$wa"))).await;
+        let mut typed = user_message(3, "me", "$mu");
+        typed.author.id = 1;
+        handler.handle_event(GatewayEvent::MessageCreate(typed)).await;
+
+        let feed = handler.stats.get_channel_activity().await;
+        let user_messages: Vec<_> = feed
+            .iter()
+            .filter_map(|activity| match activity {
+                ChannelActivity::UserMessage { username, .. } => Some(username.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(user_messages, vec!["someone", "me"]);
+    }
+
+    #[tokio::test]
+    async fn test_server_config_notice_is_logged_as_warning() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+
+        let mut notice = user_message(1, "Mudae", "Kakera rolls are now disabled on this server.");
+        notice.author.id = Config::mudae_bot_id();
+        notice.author.bot = true;
+        handler.handle_event(GatewayEvent::MessageCreate(notice)).await;
+
+        let log = handler.stats.get_activity_log().await;
+        assert!(log.iter().any(|e| e.event_type == EventType::Warning && e.message.contains("now disabled")));
+        assert!(sender.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_multibyte_content_is_truncated_safely() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        let content = format!("{}{}", "a".repeat(46), "日本語のメッセージ 💖💖💖");
+
+        handler.handle_event(GatewayEvent::MessageCreate(user_message(1, "alice", &content))).await;
+
+        let mut mudae = user_message(2, "Mudae", &content);
+        mudae.author.id = Config::mudae_bot_id();
+        mudae.author.bot = true;
+        handler.handle_event(GatewayEvent::MessageCreate(mudae)).await;
+
+        let activity = handler.stats.get_channel_activity().await;
+        assert_eq!(activity.len(), 2);
+        let expected = format!("{}日...", "a".repeat(46));
+        assert!(activity.iter().all(|a| match a {
+            ChannelActivity::UserMessage { content, .. } | ChannelActivity::MudaeInfo { message: content } => content == &expected,
+            _ => false,
+        }));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_soft_wish_is_not_claimed() {
+        let path = std::env::temp_dir().join(format!("wishlist-soft-{}.json", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+        let wishlist = Arc::new(WishlistManager::new(path_str.clone(), 0.8, true, true));
+        wishlist.add_character(WishedCharacter::new("Rem".to_string()).with_soft(true)).await.unwrap();
+
+        let sender = MockSender::default();
+        let mut handler = test_handler_with_wishlist(sender.clone(), wishlist);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Rem", "Re:Zero"))).await;
+        settle_claim_window().await;
+
+        assert!(sender.calls().is_empty());
+        assert_eq!(handler.stats.get_wishlist_matches(), 1);
+        let feed = handler.stats.get_channel_activity().await;
+        assert!(feed.iter().any(|a| matches!(a, ChannelActivity::Roll { decision: ClaimDecision::SoftWish, .. })));
+
+        handler.stats.set_paused(true);
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Rem", "Re:Zero"))).await;
+        assert_eq!(handler.stats.get_wishlist_matches(), 1);
+
+        for suffix in ["", ".bak"] {
+            let _ = std::fs::remove_file(format!("{}{}", path_str, suffix));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_missed_wish_is_marked() {
+        let config = Config { mark_wished_emoji: Some("⭐".to_string()), ..Config::default() };
+        let sender = MockSender::default();
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+        handler.stats.record_claim_report(false, None);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Subaru", "Re:Zero"))).await;
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(2, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+
+        assert_eq!(
+            sender.calls(),
+            vec![SentCall::Reaction { channel_id: CHANNEL_ID, message_id: 2, emoji: "⭐".to_string() }]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fuzzy_claim_is_auto_divorced() {
+        let path = std::env::temp_dir().join(format!("wishlist-divorce-{}.json", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+        let wishlist = Arc::new(WishlistManager::new(path_str.clone(), 0.8, true, true));
+        wishlist.add_character(WishedCharacter::new("Emilia".to_string())).await.unwrap();
+
+        let config = Config {
+            auto_divorce_non_wished: true,
+            ..Config::default()
+        };
+        let sender = MockSender::default();
+        let mut handler = test_handler_with_config(sender.clone(), wishlist, config);
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(1, "Emilla", "Re:Zero\n**50**<:kakera:1>"))).await;
+        settle_claim_window().await;
+        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+        assert_eq!(sender.calls().len(), 1);
+
+        let mut married = user_message(2, "Mudae", "💖 **me** and **Emilla** are now married! 💖");
+        married.author.id = Config::mudae_bot_id();
+        handler.handle_event(GatewayEvent::MessageCreate(married)).await;
+        tokio::time::sleep(tokio::time::Duration::from_secs(15)).await;
+
+        let calls = sender.calls();
+        assert!(matches!(calls.first(), Some(SentCall::Button { .. })));
+        assert_eq!(
+            calls[1..].to_vec(),
+            vec![
+                SentCall::Message { channel_id: CHANNEL_ID, content: "$divorce Emilla".to_string() },
+                SentCall::Message { channel_id: CHANNEL_ID, content: "y".to_string() },
+            ]
+        );
+
+        for suffix in ["", ".bak"] {
+            let _ = std::fs::remove_file(format!("{}{}", path_str, suffix));
+        }
+    }
+
+    fn harem_message(id: u64, names: &str, page: u32, total: u32) -> DiscordMessage {
+        let mut message = roll_message(id, "me's harem", names);
+        message.embeds[0].footer = Some(EmbedFooter { text: format!("{} / {}", page, total) });
+        message.components[0].components[0].custom_id = Some(format!("next-{}", id));
+        message.components[0].components[0].emoji = Some(ButtonEmoji { name: Some("➡️".to_string()), id: None });
+        message
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_owned_sync_pages_and_skips_owned_claims() {
+        let sender = MockSender::default();
+        let mut handler = test_handler(sender.clone());
+        handler.handle_event(GatewayEvent::Ready { user_id: 1, username: "me".to_string(), session_id: "s".to_string() }).await;
+        handler.stats.request_owned_sync();
+
+        handler.handle_event(GatewayEvent::MessageCreate(harem_message(5, "**Rem** => 300 ka\nLevi", 1, 2))).await;
+        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+        assert!(matches!(sender.calls().as_slice(), [SentCall::Button { custom_id, .. }] if custom_id == "next-5"));
+
+        handler.handle_event(GatewayEvent::MessageUpdate(harem_message(5, "Mikasa", 2, 2))).await;
+        let mut owned = handler.db.get_owned_characters().unwrap();
+        owned.sort();
+        assert_eq!(owned, vec!["Levi".to_string(), "Mikasa".to_string(), "Rem".to_string()]);
+
+        handler.handle_event(GatewayEvent::MessageCreate(roll_message(6, "Rem", "Re:Zero 💖"))).await;
+        settle_claim_window().await;
+        assert_eq!(sender.calls().len(), 1);
+    }
+}
@@ -0,0 +1,94 @@
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+pub type LogHandle = reload::Handle<EnvFilter, Registry>;
+
+pub const LOG_FILE: &str = "mudae-selfbot.log";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    pub fn next(self) -> Self {
+        match self {
+            LogLevel::Warn => LogLevel::Info,
+            LogLevel::Info => LogLevel::Debug,
+            LogLevel::Debug => LogLevel::Warn,
+        }
+    }
+
+    pub fn from_filter(filter: Option<LevelFilter>) -> Option<Self> {
+        match filter? {
+            LevelFilter::OFF | LevelFilter::ERROR | LevelFilter::WARN => Some(LogLevel::Warn),
+            LevelFilter::INFO => Some(LogLevel::Info),
+            _ => Some(LogLevel::Debug),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+        }
+    }
+}
+
+pub fn init(default_level: LogLevel, log_file: Option<&Path>) -> (LogHandle, LogLevel) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_level.as_str()));
+    let level = LogLevel::from_filter(filter.max_level_hint()).unwrap_or(default_level);
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let (writer, ansi) = match log_file {
+        None => (BoxMakeWriter::new(std::io::stdout), true),
+        Some(path) => match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => (BoxMakeWriter::new(Mutex::new(file)), false),
+            Err(_) => (BoxMakeWriter::new(std::io::sink), false),
+        },
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            fmt::layer()
+                .with_target(false)
+                .with_thread_ids(false)
+                .with_file(false)
+                .with_line_number(false)
+                .with_ansi(ansi)
+                .with_writer(writer)
+                .compact(),
+        )
+        .init();
+
+    (handle, level)
+}
+
+pub fn set_level(handle: &LogHandle, level: LogLevel) -> anyhow::Result<()> {
+    handle.reload(EnvFilter::new(level.as_str()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level_follows_filter() {
+        assert_eq!(LogLevel::from_filter(EnvFilter::new("debug").max_level_hint()), Some(LogLevel::Debug));
+        assert_eq!(LogLevel::from_filter(EnvFilter::new("mudae_selfbot=info").max_level_hint()), Some(LogLevel::Info));
+        assert_eq!(LogLevel::from_filter(EnvFilter::new("error").max_level_hint()), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::from_filter(None), None);
+    }
+}
@@ -1,10 +1,17 @@
+mod claim_state;
 mod client;
 mod commands;
 mod config;
+mod crypto;
 mod database;
+mod glyphs;
 mod handler;
+mod logging;
 mod parser;
+mod roll_advisor;
+mod rules;
 mod search;
+mod self_test;
 mod setup;
 mod stats;
 mod tui;
@@ -12,23 +19,25 @@ mod utils;
 mod verifier;
 mod wishlist;
 
-use crate::client::{DiscordClient, EventHandler};
+use crate::client::{ClientError, DiscordClient, EventHandler};
 use crate::commands::{CommandExecutor, RollScheduler};
-use crate::config::Config;
+use crate::config::{Config, SessionLimitAction};
 use crate::database::{ChannelInfo, Database};
 use crate::handler::{run_event_loop, MessageHandler};
+use crate::logging::LogLevel;
 use crate::search::create_search_channel;
 use crate::stats::Stats;
-use crate::verifier::CharacterVerifier;
+use crate::utils::Humanizer;
+use crate::verifier::{CharacterVerifier, WishlistVerifier};
 use crate::wishlist::WishlistManager;
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use serenity_self::model::gateway::GatewayIntents;
 use serenity_self::Client;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::{mpsc, watch};
 use tracing::{error, info};
-use tracing_subscriber::{FmtSubscriber, EnvFilter};
 
 #[derive(Parser, Debug)]
 #[command(name = "mudae-selfbot")]
@@ -37,6 +46,9 @@ struct Args {
     #[arg(short, long, help = "Your Discord user token (optional, can be set via TUI)")]
     token: Option<String>,
     
+    #[arg(long, help = "Read the Discord token from a file instead of the database (never saved)")]
+    token_file: Option<PathBuf>,
+
     #[arg(short, long, help = "Channel IDs (comma-separated, optional, can be set via TUI)", value_delimiter = ',')]
     channels: Option<Vec<u64>>,
 
@@ -45,38 +57,271 @@ struct Args {
 
     #[arg(long, help = "Force setup wizard even if already configured")]
     setup: bool,
+
+    #[arg(long, help = "Directory for the database, wishlist and backups (default: MUDAE_DATA_DIR or the platform data directory)")]
+    data_dir: Option<PathBuf>,
+
+    #[arg(long, help = "Check the database, token, channels and wishlist, then exit")]
+    self_test: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[command(long_flag = "stats", about = "Print the saved stats and exit")]
+    Stats {
+        #[arg(long, help = "Print as JSON")]
+        json: bool,
+    },
+    #[command(long_flag = "list-channels", about = "List the configured channels and exit")]
+    ListChannels,
+    #[command(long_flag = "add-channel", about = "Add a channel by ID and exit")]
+    AddChannel { channel_id: u64 },
+    #[command(long_flag = "remove-channel", about = "Remove a channel by ID and exit")]
+    RemoveChannel { channel_id: u64 },
+    #[command(long_flag = "seen", about = "Show the distinct characters seen from a series and exit")]
+    Seen { series: String },
+    #[command(long_flag = "token-fingerprint", about = "Show a masked fingerprint of the configured token and exit")]
+    TokenFingerprint,
+}
+
+const TOKEN_ENV_VAR: &str = "MUDAE_TOKEN";
+const DATA_DIR_ENV_VAR: &str = "MUDAE_DATA_DIR";
+const STARTUP_VERIFY_DELAY_SECS: u64 = 10;
+
+fn external_token(token_file: Option<&Path>) -> Result<Option<String>> {
+    if let Some(path) = token_file {
+        let token = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read token file {}", path.display()))?;
+        let token = token.trim();
+        if token.is_empty() {
+            anyhow::bail!("Token file {} is empty", path.display());
+        }
+        return Ok(Some(token.to_string()));
+    }
+
+    Ok(std::env::var(TOKEN_ENV_VAR)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty()))
+}
+
+fn load_stored_token(db: &Database, encrypt: bool) -> Result<Option<String>> {
+    let Some(stored) = db.get_token()? else {
+        return Ok(None);
+    };
+
+    if !crypto::is_encrypted(&stored) {
+        if encrypt {
+            let passphrase = setup::prompt_passphrase("Choose a passphrase to encrypt the stored token")?;
+            if setup::prompt_passphrase("Repeat the passphrase")? != passphrase {
+                anyhow::bail!("Passphrases do not match, token left unencrypted");
+            }
+            db.save_token_encrypted(&stored, &passphrase)?;
+        }
+        return Ok(Some(stored));
+    }
+
+    let passphrase = setup::prompt_passphrase("Passphrase for the stored token")?;
+    let token = db
+        .get_token_encrypted(&passphrase)
+        .context("Failed to decrypt the stored token")?;
+    if let (Some(token), false) = (&token, encrypt) {
+        db.save_token(token)?;
+    }
+    Ok(token)
+}
+
+fn run_command(db: &Database, command: Command, token: Option<String>, token_file: Option<&Path>) -> Result<()> {
+    match command {
+        Command::Stats { json } => {
+            let saved = db.load_stats()?;
+            if json {
+                let stats = Stats::from_saved(saved);
+                stats.refresh_claimed_value(db);
+                println!("{}", serde_json::to_string_pretty(&stats.stats_snapshot())?);
+            } else {
+                println!("{}", saved.to_table());
+            }
+        }
+        Command::ListChannels => {
+            let channels = db.get_channels_with_names()?;
+            if channels.is_empty() {
+                println!("No channels configured");
+            }
+            for channel in channels {
+                let mut line = channel.id.to_string();
+                if channel.name.is_some() {
+                    line.push_str(&format!("  {}", channel.display_name()));
+                }
+                if let Some(ref commands) = channel.roll_commands {
+                    line.push_str(&format!("  [{}]", commands.join(" ")));
+                }
+                println!("{}", line);
+            }
+        }
+        Command::AddChannel { channel_id } => {
+            if db.add_channel(channel_id)? {
+                println!("Added channel {}", channel_id);
+            } else {
+                println!("Channel {} is already configured", channel_id);
+            }
+        }
+        Command::RemoveChannel { channel_id } => {
+            if db.remove_channel(channel_id)? {
+                println!("Removed channel {}", channel_id);
+            } else {
+                println!("Channel {} is not configured", channel_id);
+            }
+        }
+        Command::TokenFingerprint => {
+            let (token, source) = match token {
+                Some(token) => (Some(token), "--token"),
+                None => match external_token(token_file)? {
+                    Some(token) if token_file.is_some() => (Some(token), "--token-file"),
+                    Some(token) => (Some(token), TOKEN_ENV_VAR),
+                    None if db.is_token_encrypted() => {
+                        let passphrase = setup::prompt_passphrase("Passphrase for the stored token")?;
+                        let token = db
+                            .get_token_encrypted(&passphrase)
+                            .context("Failed to decrypt the stored token")?;
+                        (token, "database, encrypted")
+                    }
+                    None => (db.get_token()?, "database"),
+                },
+            };
+            match token {
+                Some(token) => println!("Token fingerprint: {} (from {})", crypto::fingerprint(&token), source),
+                None => println!("No token configured"),
+            }
+        }
+        Command::Seen { series } => {
+            let seen = db.get_seen_characters(&series)?;
+            if seen.is_empty() {
+                println!("No characters seen from a series matching \"{}\"", series);
+            }
+            for (index, character) in seen.iter().enumerate() {
+                if index == 0 || seen[index - 1].series != character.series {
+                    let from_series: Vec<_> = seen.iter().filter(|c| c.series == character.series).collect();
+                    let rolls: u64 = from_series.iter().map(|c| c.roll_count).sum();
+                    println!("{}: {} distinct characters seen, {} rolls", character.series, from_series.len(), rolls);
+                }
+                println!(
+                    "  {:<30} x{:<4} first seen {}  last seen {}",
+                    character.name,
+                    character.roll_count,
+                    character.first_seen.format("%Y-%m-%d %H:%M"),
+                    character.last_seen.format("%Y-%m-%d %H:%M")
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_self_test(args: &Args) -> Result<()> {
+    let db = Database::new().map(Arc::new).context("Failed to initialize database");
+    let config = db.as_ref().map(Config::load_from_db).unwrap_or_default();
+    let token = match args.token.clone() {
+        Some(token) => Ok(Some(token)),
+        None => match external_token(args.token_file.as_deref()) {
+            Ok(Some(token)) => Ok(Some(token)),
+            Ok(None) => match &db {
+                Ok(db) => load_stored_token(db, config.encrypt_token),
+                Err(_) => Ok(None),
+            },
+            Err(e) => Err(e),
+        },
+    };
+
+    let results = self_test::run(db.as_deref(), &config, token).await;
+    println!("{}", self_test::report(&results));
+    if results.iter().any(|r| !r.passed()) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn enforce_session_limit(config: Config, stats: Arc<Stats>) {
+    let Some(hours) = config.max_session_hours else {
+        return std::future::pending().await;
+    };
+
+    while let Some(left) = config
+        .session_time_left(stats.start_time, chrono::Utc::now())
+        .filter(|left| *left > chrono::Duration::zero())
+    {
+        tokio::time::sleep(left.to_std().unwrap_or_default()).await;
+    }
+
+    match config.session_limit_action {
+        SessionLimitAction::Stop => {
+            info!("Session limit of {}h reached, shutting down", hours);
+            stats.log_event(crate::stats::EventType::Warning, format!("Session limit of {}h reached, shutting down", hours)).await;
+        }
+        SessionLimitAction::Pause => {
+            stats.set_paused(true);
+            info!("Session limit of {}h reached, pausing", hours);
+            stats.log_event(crate::stats::EventType::Warning, format!("Session limit of {}h reached, all activity paused", hours)).await;
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+async fn resume_verification(verifier: Arc<WishlistVerifier>, wishlist: Arc<WishlistManager>, stats: Arc<Stats>) {
+    let pending = wishlist.get_unverified().await.len();
+    if pending == 0 {
+        return;
+    }
+
+    while stats.get_connection_status().await != crate::stats::ConnectionStatus::Connected {
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    }
+    tokio::time::sleep(tokio::time::Duration::from_secs(STARTUP_VERIFY_DELAY_SECS)).await;
+
+    stats.log_event(crate::stats::EventType::Wishlist, format!("Resuming verification of {} unverified characters", pending)).await;
+    match verifier.verify_unverified_characters().await {
+        Ok(report) => {
+            let outcome = if report.cancelled { "cancelled" } else { "finished" };
+            stats.log_event(
+                crate::stats::EventType::Wishlist,
+                format!("Verification {}: {} verified, {} not found", outcome, report.verified, report.failed),
+            ).await;
+        }
+        Err(e) => {
+            stats.log_event(crate::stats::EventType::Error, format!("Verification failed: {}", e)).await;
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    let data_dir = args.data_dir.clone().or_else(|| {
+        std::env::var_os(DATA_DIR_ENV_VAR).filter(|v| !v.is_empty()).map(PathBuf::from)
+    });
+    if let Some(dir) = data_dir {
+        Database::set_data_dir(dir);
+    }
     
+    if args.self_test {
+        return run_self_test(&args).await;
+    }
+
     let db = Arc::new(Database::new().context("Failed to initialize database")?);
 
-    if args.no_tui {
-        let filter = EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| EnvFilter::new("info"));
-        let _subscriber = FmtSubscriber::builder()
-            .with_env_filter(filter)
-            .with_target(false)
-            .with_thread_ids(false)
-            .with_file(false)
-            .with_line_number(false)
-            .compact()
-            .init();
-    } else {
-        let filter = EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| EnvFilter::new("warn"));
-        let _subscriber = FmtSubscriber::builder()
-            .with_env_filter(filter)
-            .with_target(false)
-            .with_thread_ids(false)
-            .with_file(false)
-            .with_line_number(false)
-            .compact()
-            .init();
+    if let Some(command) = args.command {
+        return run_command(&db, command, args.token, args.token_file.as_deref());
     }
 
+    let (log_handle, log_level) = if args.no_tui {
+        logging::init(LogLevel::Info, None)
+    } else {
+        logging::init(LogLevel::Warn, Some(&Database::data_dir().join(logging::LOG_FILE)))
+    };
+
     if let Some(ref token) = args.token {
         db.save_token(token)?;
     }
@@ -86,7 +331,12 @@ async fn main() -> Result<()> {
         }
     }
 
-    let needs_setup = args.setup || !db.is_configured();
+    let external_token = match args.token {
+        Some(_) => None,
+        None => external_token(args.token_file.as_deref())?,
+    };
+    let has_token = external_token.is_some() || db.has_credentials();
+    let needs_setup = args.setup || !has_token || !db.has_channels();
     
     if needs_setup && !args.no_tui {
         let completed = setup::run_setup(db.clone())?;
@@ -96,11 +346,14 @@ async fn main() -> Result<()> {
         }
     }
 
-    let token = match db.get_token()? {
+    let config = Config::load_from_db(&db);
+    Humanizer::set_current(config.delay_distribution);
+
+    let token = match external_token.map_or_else(|| load_stored_token(&db, config.encrypt_token), |t| Ok(Some(t)))? {
         Some(t) => t,
         None => {
             if args.no_tui {
-                anyhow::bail!("No token configured. Run without --no-tui to set up, or use --token");
+                anyhow::bail!("No token configured. Run without --no-tui to set up, or use --token, --token-file or {}", TOKEN_ENV_VAR);
             }
             anyhow::bail!("No token configured");
         }
@@ -114,71 +367,93 @@ async fn main() -> Result<()> {
         anyhow::bail!("No channels configured");
     }
 
-    let config = Config::load_from_db(&db);
-
     let saved_stats = db.load_stats()?;
     let stats = Stats::from_saved(saved_stats);
-    stats.set_rolls_remaining(10);
+    stats.refresh_claimed_value(&db);
+    stats.set_rolls_remaining(config.default_rolls);
+    if stats.restore_halt(&Database::halt_marker_path()) {
+        stats.log_event(crate::stats::EventType::Error, "HALTED: outbound actions are blocked until the halt is cleared".to_string()).await;
+    }
 
-    let client = DiscordClient::new(token.clone()).with_stats(stats.clone());
+    let client = DiscordClient::new(token.clone())
+        .with_stats(stats.clone())
+        .with_auth_error_halt(config.auth_error_halt_count);
 
-    if let Ok(user) = client.get_current_user().await {
-        let username = user.username.clone();
-        let display_name = user.global_name.unwrap_or(username.clone());
-        stats.set_username(username.clone()).await;
-        let _ = db.save_user_info(&display_name, user.id);
-    } else if let Ok(Some(username)) = db.get_username() {
-        stats.set_username(username).await;
+    match client.get_current_user().await {
+        Ok(user) => {
+            let username = user.username.clone();
+            let display_name = user.global_name.unwrap_or(username.clone());
+            stats.set_username(username.clone()).await;
+            let _ = db.save_user_info(&display_name, user.id);
+        }
+        Err(e) => {
+            if matches!(e, ClientError::Unauthorized) {
+                stats.log_event(crate::stats::EventType::Error, format!("{}: update the token in the settings", e)).await;
+            }
+            if let Ok(Some(username)) = db.get_username() {
+                stats.set_username(username).await;
+            }
+        }
     }
 
     let mut channel_infos = db.get_channels_with_names()?;
     
-    let channels_clone = channels.clone();
-    let client_for_channels = client.clone();
-    let db_for_channels = db.clone();
-    tokio::spawn(async move {
-        for channel_id in channels_clone.iter() {
-            if let Ok(channel) = client_for_channels.get_channel(*channel_id).await {
-                let guild_name = if let Some(guild_id_str) = &channel.guild_id {
-                    if let Ok(guild_id) = guild_id_str.parse::<u64>() {
-                        client_for_channels.get_guild(guild_id).await.ok().map(|g| g.name)
+    if args.no_tui {
+        let channels_clone = channels.clone();
+        let client_for_channels = client.clone();
+        let db_for_channels = db.clone();
+        tokio::spawn(async move {
+            for channel_id in channels_clone.iter() {
+                if let Ok(channel) = client_for_channels.get_channel(*channel_id).await {
+                    let guild_name = if let Some(guild_id_str) = &channel.guild_id {
+                        if let Ok(guild_id) = guild_id_str.parse::<u64>() {
+                            client_for_channels.get_guild(guild_id).await.ok().map(|g| g.name)
+                        } else {
+                            None
+                        }
                     } else {
                         None
+                    };
+                    
+                    if let Err(e) = db_for_channels.update_channel_name(
+                        *channel_id,
+                        channel.name.as_deref().unwrap_or("Unknown"),
+                        guild_name.as_deref(),
+                    ) {
+                        error!("Failed to update channel name: {}", e);
+                    } else {
+                        info!("Updated channel info for {}", channel_id);
                     }
-                } else {
-                    None
-                };
-                
-                if let Err(e) = db_for_channels.update_channel_name(
-                    *channel_id,
-                    channel.name.as_deref().unwrap_or("Unknown"),
-                    guild_name.as_deref(),
-                ) {
-                    error!("Failed to update channel name: {}", e);
-                } else {
-                    info!("Updated channel info for {}", channel_id);
                 }
             }
-        }
-    });
+        });
+    }
 
     if channel_infos.iter().all(|c| c.name.is_none()) {
         channel_infos = channels.iter().map(|&id| ChannelInfo {
-            id,
-            name: None,
-            guild: None,
+            roll_commands: channel_infos.iter().find(|c| c.id == id).and_then(|c| c.roll_commands.clone()),
+            ..ChannelInfo::new(id)
         }).collect();
     }
 
+    let wishlist_file = config.wishlist_path().to_string_lossy().to_string();
+    let wishlist_dir = Path::new(&wishlist_file).parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    if config.wishlist_enabled {
+        Database::ensure_writable(wishlist_dir)?;
+    }
     let wishlist = Arc::new(WishlistManager::new(
-        config.wishlist_file.clone(),
+        wishlist_file,
         config.fuzzy_threshold,
         config.fuzzy_match,
         true,
-    ));
+    ).with_fuzzy_dedup(config.fuzzy_dedup));
 
     if config.wishlist_enabled {
-        wishlist.load().await.context("Failed to load wishlist")?;
+        if config.strict_wishlist_load {
+            wishlist.load().await.context("Failed to load wishlist")?;
+        } else if !wishlist.load_or_recover().await {
+            stats.log_event(crate::stats::EventType::Warning, "Wishlist file was unreadable, loaded backup or empty wishlist".to_string()).await;
+        }
     }
 
     let verification_channel = channels
@@ -189,9 +464,17 @@ async fn main() -> Result<()> {
     let verifier = Arc::new(CharacterVerifier::new(
         client.clone(),
         verification_channel,
+    ).with_delay_bounds(
+        tokio::time::Duration::from_millis(config.verify_delay_min_ms),
+        tokio::time::Duration::from_millis(config.verify_delay_max_ms),
     ));
 
-    let executor = Arc::new(CommandExecutor::new(client.clone(), config.clone(), stats.clone()));
+    let wishlist_verifier = Arc::new(WishlistVerifier::new(verifier.clone(), wishlist.clone()));
+
+    let executor = Arc::new(
+        CommandExecutor::new(client.clone(), config.clone(), stats.clone())
+            .with_channel_roll_commands(db.get_channel_roll_commands()?),
+    );
 
     let (search_tx, search_rx) = create_search_channel();
 
@@ -203,6 +486,7 @@ async fn main() -> Result<()> {
         stats.clone(),
         channels.clone(),
         client.clone(),
+        db.clone(),
         search_rx,
     );
 
@@ -235,6 +519,7 @@ async fn main() -> Result<()> {
                 Ok(client) => client,
                 Err(e) => {
                     error!("Failed to create Discord client: {}", e);
+                    stats_for_error.set_last_disconnect_reason(e.to_string()).await;
                     stats_for_error.set_connection_status(crate::stats::ConnectionStatus::Disconnected).await;
                     return;
                 }
@@ -242,6 +527,7 @@ async fn main() -> Result<()> {
 
             if let Err(e) = client.start().await {
                 error!("Client connection error: {}", e);
+                stats_for_error.set_last_disconnect_reason(e.to_string()).await;
                 stats_for_error.set_connection_status(crate::stats::ConnectionStatus::Disconnected).await;
             }
         })
@@ -262,13 +548,23 @@ async fn main() -> Result<()> {
         scheduler.run().await;
     });
 
+    let session_limit_handle = tokio::spawn(enforce_session_limit(config.clone(), stats.clone()));
+
+    if config.auto_verify_on_start {
+        tokio::spawn(resume_verification(wishlist_verifier.clone(), wishlist.clone(), stats.clone()));
+    }
+
     let stats_save_handle = {
         let stats = stats.clone();
         let db = db.clone();
+        let save_interval = config.stats_save_interval_seconds.max(1);
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(save_interval));
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = stats.save_requested() => {}
+                }
                 if let Err(e) = stats.save_to_db(&db) {
                     error!("Failed to save stats: {}", e);
                 }
@@ -282,8 +578,9 @@ async fn main() -> Result<()> {
         let db = db.clone();
         let wishlist = wishlist.clone();
         let client_for_tui = client.clone();
+        let executor = executor.clone();
         Some(tokio::spawn(async move {
-            if let Err(e) = tui::run_tui(stats, config, db, wishlist, search_tx, shutdown_rx, channel_infos, Some(client_for_tui)).await {
+            if let Err(e) = tui::run_tui(stats, config, db, wishlist, wishlist_verifier, search_tx, shutdown_rx, channel_infos, Some(client_for_tui), executor, log_handle, log_level).await {
                 error!("TUI error: {}", e);
             }
         }))
@@ -309,6 +606,7 @@ async fn main() -> Result<()> {
                     error!("Scheduler task panicked: {}", e);
                 }
             }
+            _ = session_limit_handle => {}
         }
     } else {
         tokio::select! {
@@ -339,6 +637,9 @@ async fn main() -> Result<()> {
                     error!("Scheduler task panicked: {}", e);
                 }
             }
+            _ = session_limit_handle => {
+                let _ = shutdown_tx.send(true);
+            }
         }
     }
 
@@ -1,17 +1,73 @@
 #![allow(dead_code)]
 
-use crate::client::{DiscordMessage, Embed};
+use crate::client::{Button, DiscordMessage, Embed};
 use regex::Regex;
 use std::sync::LazyLock;
 
 static KAKERA_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(\d+)\s*<:kakera").unwrap()
+    Regex::new(r"(\d+)\*{0,2}\s*<:kakera").unwrap()
 });
 
 static CLAIM_EMOJI_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^(💖|❤️|💕|💗|💘|💝)$").unwrap()
 });
 
+static CLAIM_HEART_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"💖|❤️|💕|💗|💘|💝").unwrap()
+});
+
+static KAKERA_GIVE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*]+)\*\*\s+(?:gave|gives|has given|sent)\s+\*\*(\d+)\*\*.*?\bto\s+\*\*([^*]+)\*\*").unwrap()
+});
+
+static KAKERA_STEAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*]+)\*\*\s+(?:stole|steals|has stolen)\s+\*\*(\d+)\*\*.*?\bfrom\s+\*\*([^*]+)\*\*").unwrap()
+});
+
+static KAKERA_POWER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)power:\s*\**(\d+)%").unwrap()
+});
+
+static LEADING_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*\*\*([^*]+)\*\*").unwrap()
+});
+
+static CLAIM_COOLDOWN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\*\*([^*]+)\*\*,?\s+you (?:can't|cannot|can not) (?:claim|marry)(?: for)?(?: another)?\s+\*\*(?:(\d+)h\s*)?(\d+)\*\*\s*min").unwrap()
+});
+
+static MARRIAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*]+)\*\*\s+and\s+\*\*([^*]+)\*\*\s+are now married").unwrap()
+});
+
+static DIVORCE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*]+)\*\*\s+and\s+\*\*([^*]+)\*\*\s+are now divorced").unwrap()
+});
+
+static HAREM_AUTHOR_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(.+?)(?:'s|’s)?\s+harem\b").unwrap()
+});
+
+static PAGE_FOOTER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d+)\s*/\s*(\d+)").unwrap()
+});
+
+static DAILY_KAKERA_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bdaily kakera\b").unwrap()
+});
+
+static SERVER_CONFIG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\$toggle\w+|\b(?:is|are) now (?:disabled|enabled)\b|\bsettings? (?:updated|changed)\b|\b(?:claim|roll)s? (?:interval|reset) (?:is now|has been|set to)\b").unwrap()
+});
+
+static CAPTCHA_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bcaptcha\b|\bhuman verification\b|\bverification (?:is )?(?:required|needed)\b|\bverify (?:that )?you(?:'re|’re| are) (?:a )?human\b|\bprove (?:that )?you(?:'re|’re| are) (?:a human|human|not a (?:ro)?bot)\b|\bare you (?:a )?(?:human|(?:ro)?bot)\b").unwrap()
+});
+
+const NEXT_PAGE_EMOJIS: &[&str] = &["➡️", "➡", "▶️", "▶"];
+
+const CLAIM_BUTTON_EXCLUDED_LABELS: &[&str] = &["info", "divorce", "series"];
+
 #[derive(Debug, Clone)]
 pub struct ParsedCharacter {
     pub name: String,
@@ -21,6 +77,21 @@ pub struct ParsedCharacter {
     pub is_claimed: bool,
     pub claim_rank: Option<u32>,
     pub is_wished: bool,
+    pub gender: Option<Gender>,
+    pub claim_emoji: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HaremPage {
+    pub owner: String,
+    pub characters: Vec<String>,
+    pub page: u32,
+    pub total_pages: u32,
+    pub message_id: u64,
+    pub channel_id: u64,
+    pub guild_id: Option<u64>,
+    pub application_id: u64,
+    pub next_button_id: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -29,12 +100,16 @@ pub enum MudaeMessage {
         character: ParsedCharacter,
         message_id: u64,
         channel_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
         has_claim_button: bool,
         claim_button_id: Option<String>,
     },
     KakeraLoot {
         message_id: u64,
         channel_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
         kakera_type: KakeraType,
         button_id: Option<String>,
     },
@@ -51,7 +126,40 @@ pub enum MudaeMessage {
         available: bool,
         reset_time: Option<String>,
     },
+    KakeraTransfer {
+        from: String,
+        to: String,
+        amount: u32,
+    },
+    KakeraPower {
+        power: u32,
+        can_react: bool,
+        is_me: bool,
+    },
+    Divorce {
+        character: String,
+        is_me: bool,
+    },
+    Marriage {
+        user: String,
+        character: String,
+    },
+    HaremPage(HaremPage),
     DailyReady,
+    DailyKakera {
+        message_id: u64,
+        channel_id: u64,
+        guild_id: Option<u64>,
+        application_id: u64,
+        button_id: Option<String>,
+        is_me: bool,
+    },
+    ServerConfigNotice {
+        text: String,
+    },
+    CaptchaPrompt {
+        text: String,
+    },
     Unknown,
 }
 
@@ -88,30 +196,231 @@ impl KakeraType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    Female,
+    Male,
+}
+
+impl Gender {
+    pub fn from_roll_command(command: &str) -> Option<Self> {
+        let command = command.trim().to_lowercase();
+        match command.strip_prefix('$')? {
+            "w" | "wa" | "wg" => Some(Gender::Female),
+            "h" | "ha" | "hg" => Some(Gender::Male),
+            _ => None,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "female" => Some(Gender::Female),
+            "male" => Some(Gender::Male),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Gender::Female => "female",
+            Gender::Male => "male",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MudaeLanguage {
+    #[default]
+    English,
+    French,
+    Spanish,
+}
+
+impl MudaeLanguage {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "en" | "english" => Some(MudaeLanguage::English),
+            "fr" | "french" => Some(MudaeLanguage::French),
+            "es" | "spanish" => Some(MudaeLanguage::Spanish),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MudaeLanguage::English => "en",
+            MudaeLanguage::French => "fr",
+            MudaeLanguage::Spanish => "es",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MudaeLanguage::English => "English",
+            MudaeLanguage::French => "French",
+            MudaeLanguage::Spanish => "Spanish",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            MudaeLanguage::English => MudaeLanguage::French,
+            MudaeLanguage::French => MudaeLanguage::Spanish,
+            MudaeLanguage::Spanish => MudaeLanguage::English,
+        }
+    }
+
+    fn candidates(self) -> Vec<MudaeLanguage> {
+        if self == MudaeLanguage::English {
+            vec![MudaeLanguage::English]
+        } else {
+            vec![self, MudaeLanguage::English]
+        }
+    }
+
+    fn phrases(self) -> &'static LanguagePhrases {
+        let index = match self {
+            MudaeLanguage::English => 0,
+            MudaeLanguage::French => 1,
+            MudaeLanguage::Spanish => 2,
+        };
+        &LANGUAGE_PHRASES[index]
+    }
+}
+
+struct LanguagePhrases {
+    belongs_to: &'static str,
+    rolls_left_phrase: &'static str,
+    rolls_left: Regex,
+    roll_word: &'static str,
+    reset_word: &'static str,
+    reset_hours: Regex,
+    reset_minutes: Regex,
+    roulette_limited: &'static str,
+    roulette_minutes_left: Regex,
+    claim_word: &'static str,
+    claim_context: &'static [&'static str],
+    claim_available: &'static [&'static str],
+    claim_unavailable: &'static [&'static str],
+}
+
+static LANGUAGE_PHRASES: LazyLock<[LanguagePhrases; 3]> = LazyLock::new(|| {
+    [
+        LanguagePhrases {
+            belongs_to: "Belongs to",
+            rolls_left_phrase: "rolls left",
+            rolls_left: Regex::new(r"(?i)(\d+)\**\s*rolls?\s*left").unwrap(),
+            roll_word: "roll",
+            reset_word: "reset",
+            reset_hours: Regex::new(r"(?i)reset\s+(?:in\s+)?\**(\d+)\**\s*(?:hours?|hrs?|h)(?:\s*\**(\d+)\**\s*(?:minutes?|mins?|m)\b)?").unwrap(),
+            reset_minutes: Regex::new(r"(?i)reset\s+(?:in\s+)?\**(\d+)\**\s*(?:minutes?|mins?|m)\b").unwrap(),
+            roulette_limited: "roulette is limited",
+            roulette_minutes_left: Regex::new(r"\*\*([^*]+)\*\*.*?\**(\d+)\**\s*min(?:utes?|s)?\s+left").unwrap(),
+            claim_word: "claim",
+            claim_context: &["available", "reset"],
+            claim_available: &["can claim", "claim available"],
+            claim_unavailable: &[],
+        },
+        LanguagePhrases {
+            belongs_to: "Appartient à",
+            rolls_left_phrase: "rolls restants",
+            rolls_left: Regex::new(r"(?i)(\d+)\**\s*rolls?\s*restants?").unwrap(),
+            roll_word: "roll",
+            reset_word: "reset",
+            reset_hours: Regex::new(r"(?i)reset\s+(?:dans\s+)?\**(\d+)\**\s*(?:heures?|h)(?:\s*\**(\d+)\**\s*(?:minutes?|min|m)\b)?").unwrap(),
+            reset_minutes: Regex::new(r"(?i)reset\s+(?:dans\s+)?\**(\d+)\**\s*(?:m|min|minutes?)\b").unwrap(),
+            roulette_limited: "roulette est limitée",
+            roulette_minutes_left: Regex::new(r"\*\*([^*]+)\*\*.*?\**(\d+)\**\s*min(?:utes?|utos?)?\s+restantes").unwrap(),
+            claim_word: "marier",
+            claim_context: &["maintenant", "reset"],
+            claim_available: &["pouvez vous marier", "peux te marier"],
+            claim_unavailable: &["ne pouvez pas", "ne peux pas"],
+        },
+        LanguagePhrases {
+            belongs_to: "Pertenece a",
+            rolls_left_phrase: "rolls restantes",
+            rolls_left: Regex::new(r"(?i)(\d+)\**\s*rolls?\s*restantes?").unwrap(),
+            roll_word: "roll",
+            reset_word: "reinicio",
+            reset_hours: Regex::new(r"(?i)reinicio\s+(?:en\s+)?\**(\d+)\**\s*(?:horas?|h)(?:\s*\**(\d+)\**\s*(?:minutos?|min|m)\b)?").unwrap(),
+            reset_minutes: Regex::new(r"(?i)reinicio\s+(?:en\s+)?\**(\d+)\**\s*(?:m|min|minutos?)\b").unwrap(),
+            roulette_limited: "ruleta está limitada",
+            roulette_minutes_left: Regex::new(r"\*\*([^*]+)\*\*.*?\**(\d+)\**\s*min(?:utes?|utos?)?\s+restantes").unwrap(),
+            claim_word: "casarte",
+            claim_context: &["ahora", "reinicio"],
+            claim_available: &["puedes casarte"],
+            claim_unavailable: &["no puedes casarte"],
+        },
+    ]
+});
+
 pub struct MudaeParser;
 
 impl MudaeParser {
-    pub fn parse(message: &DiscordMessage, username: Option<&str>) -> MudaeMessage {
+    pub fn parse(message: &DiscordMessage, username: Option<&str>, language: MudaeLanguage) -> MudaeMessage {
+        let languages = language.candidates();
+        if let Some(captcha) = Self::parse_captcha_prompt(message, username) {
+            return captcha;
+        }
+
         if let Some(embed) = message.embeds.first() {
+            if let Some(page) = Self::parse_harem_page(message, embed) {
+                return MudaeMessage::HaremPage(page);
+            }
+
+            if Self::is_kakera_loot(message) {
+                return Self::parse_kakera_loot(message);
+            }
+
             if Self::is_character_info(embed) {
                 return Self::parse_character_info(embed);
             }
             
             if Self::is_character_roll(embed) {
-                return Self::parse_character_roll(message, embed);
-            }
-            
-            if Self::is_kakera_loot(message) {
-                return Self::parse_kakera_loot(message);
+                return Self::parse_character_roll(message, embed, &languages);
             }
         }
         
-        if Self::is_rolls_info(&message.content) {
-            return Self::parse_rolls_info(&message.content, username);
+        if Self::is_kakera_transfer(&message.content) {
+            if let Some(transfer) = Self::parse_kakera_transfer(&message.content) {
+                return transfer;
+            }
+        }
+
+        if let Some(cooldown) = Self::parse_claim_cooldown(&message.content, username) {
+            return cooldown;
+        }
+
+        if let Some(phrases) = Self::rolls_info_phrases(&message.content, &languages) {
+            return Self::parse_rolls_info(&message.content, username, phrases);
         }
         
-        if Self::is_claim_info(&message.content) {
-            return Self::parse_claim_info(&message.content);
+        if let Some(phrases) = Self::claim_info_phrases(&message.content, &languages) {
+            return Self::parse_claim_info(&message.content, phrases);
+        }
+
+        if let Some(power) = Self::parse_kakera_power(&message.content, username) {
+            return power;
+        }
+
+        if let Some(divorce) = Self::parse_divorce(&message.content, username) {
+            return divorce;
+        }
+
+        if let Some(marriage) = Self::parse_marriage(&message.content) {
+            return marriage;
+        }
+
+        if Self::is_daily_ready(&message.content) {
+            return MudaeMessage::DailyReady;
+        }
+
+        if DAILY_KAKERA_REGEX.is_match(&message.content) {
+            return Self::parse_daily_kakera(message, username);
+        }
+
+        if SERVER_CONFIG_REGEX.is_match(&message.content) {
+            return MudaeMessage::ServerConfigNotice { text: message.content.trim().to_string() };
         }
         
         MudaeMessage::Unknown
@@ -121,7 +430,7 @@ impl MudaeParser {
         embed.author.is_some() && embed.description.is_some()
     }
 
-    fn parse_character_roll(message: &DiscordMessage, embed: &Embed) -> MudaeMessage {
+    fn parse_character_roll(message: &DiscordMessage, embed: &Embed, languages: &[MudaeLanguage]) -> MudaeMessage {
         let name = embed.author
             .as_ref()
             .map(|a| a.name.clone())
@@ -130,17 +439,22 @@ impl MudaeParser {
         let description = embed.description.as_deref().unwrap_or("");
         let series = Self::extract_series(description);
         
-        let kakera_value = embed.footer
-            .as_ref()
-            .and_then(|f| Self::extract_kakera_value(&f.text));
+        let footer = embed.footer.as_ref().map(|f| f.text.as_str()).unwrap_or("");
+        let kakera_value = Self::extract_kakera_value(footer)
+            .or_else(|| Self::extract_kakera_value(description));
         
         let image_url = embed.image.as_ref().map(|i| i.url.clone());
         
-        let is_claimed = description.contains("Belongs to");
+        let is_claimed = languages.iter().any(|language| {
+            let belongs_to = language.phrases().belongs_to;
+            description.contains(belongs_to) || footer.contains(belongs_to)
+        });
         let claim_rank = Self::extract_claim_rank(description);
         let is_wished = description.contains("💖") || description.contains("❤️");
+        let gender = Self::extract_gender(description);
         
         let (has_claim_button, claim_button_id) = Self::find_claim_button(&message.components);
+        let claim_emoji = Self::find_claim_emoji(&message.components, description);
 
         MudaeMessage::CharacterRoll {
             character: ParsedCharacter {
@@ -151,9 +465,13 @@ impl MudaeParser {
                 is_claimed,
                 claim_rank,
                 is_wished,
+                gender,
+                claim_emoji,
             },
             message_id: message.id,
             channel_id: message.channel_id,
+            guild_id: message.guild_id,
+            application_id: message.author.id,
             has_claim_button,
             claim_button_id,
         }
@@ -162,12 +480,20 @@ impl MudaeParser {
     fn extract_series(description: &str) -> String {
         let lines: Vec<&str> = description.lines().collect();
         if let Some(first_line) = lines.first() {
-            first_line.trim().to_string()
+            first_line.trim_end_matches(['♀', '♂', '\u{fe0f}', ' ']).trim().to_string()
         } else {
             String::new()
         }
     }
 
+    fn extract_gender(description: &str) -> Option<Gender> {
+        match (description.contains('♀'), description.contains('♂')) {
+            (true, false) => Some(Gender::Female),
+            (false, true) => Some(Gender::Male),
+            _ => None,
+        }
+    }
+
     fn extract_kakera_value(text: &str) -> Option<u32> {
         KAKERA_REGEX.captures(text)
             .and_then(|caps| caps.get(1))
@@ -182,23 +508,101 @@ impl MudaeParser {
     }
 
     fn find_claim_button(components: &[crate::client::Component]) -> (bool, Option<String>) {
+        let mut best: Option<(u8, &Button)> = None;
         for component in components {
             for button in &component.components {
-                if let Some(emoji) = &button.emoji {
-                    if let Some(name) = &emoji.name {
-                        if CLAIM_EMOJI_REGEX.is_match(name) {
-                            return (true, button.custom_id.clone());
-                        }
-                    }
-                }
-                if let Some(label) = &button.label {
-                    if label.contains("💖") || label.to_lowercase().contains("marry") {
-                        return (true, button.custom_id.clone());
-                    }
+                let score = Self::claim_button_score(button);
+                if score > best.map(|(s, _)| s).unwrap_or(0) {
+                    best = Some((score, button));
                 }
             }
         }
-        (false, None)
+
+        match best {
+            Some((_, button)) => (true, button.custom_id.clone()),
+            None => (false, None),
+        }
+    }
+
+    fn find_claim_emoji(components: &[crate::client::Component], description: &str) -> Option<String> {
+        components.iter()
+            .flat_map(|c| &c.components)
+            .filter(|button| Self::claim_button_score(button) > 0)
+            .find_map(|button| button.emoji.as_ref()?.name.clone().filter(|name| CLAIM_EMOJI_REGEX.is_match(name)))
+            .or_else(|| CLAIM_HEART_REGEX.find(description).map(|m| m.as_str().to_string()))
+    }
+
+    fn claim_button_score(button: &Button) -> u8 {
+        let label = button.label.as_deref().unwrap_or("").to_lowercase();
+        if CLAIM_BUTTON_EXCLUDED_LABELS.iter().any(|excluded| label.contains(excluded)) {
+            return 0;
+        }
+
+        let mut score = 0;
+        let emoji_matches = button.emoji
+            .as_ref()
+            .and_then(|e| e.name.as_deref())
+            .map(|name| CLAIM_EMOJI_REGEX.is_match(name))
+            .unwrap_or(false);
+        if emoji_matches {
+            score += 2;
+        }
+        if label.contains("marry") {
+            score += 2;
+        }
+        if label.contains("💖") {
+            score += 1;
+        }
+        score
+    }
+
+    fn parse_harem_page(message: &DiscordMessage, embed: &Embed) -> Option<HaremPage> {
+        let author = embed.author.as_ref()?;
+        let owner = HAREM_AUTHOR_REGEX.captures(author.name.trim())?.get(1)?.as_str().trim().to_string();
+        let description = embed.description.as_deref().unwrap_or("");
+
+        let (page, total_pages) = embed.footer
+            .as_ref()
+            .and_then(|f| PAGE_FOOTER_REGEX.captures(&f.text))
+            .and_then(|caps| Some((caps.get(1)?.as_str().parse().ok()?, caps.get(2)?.as_str().parse().ok()?)))
+            .unwrap_or((1, 1));
+
+        let next_button_id = message.components.iter()
+            .flat_map(|c| &c.components)
+            .find(|b| {
+                b.emoji.as_ref()
+                    .and_then(|e| e.name.as_deref())
+                    .is_some_and(|name| NEXT_PAGE_EMOJIS.contains(&name))
+            })
+            .and_then(|b| b.custom_id.clone());
+
+        Some(HaremPage {
+            owner,
+            characters: description.lines().filter_map(Self::harem_line_name).collect(),
+            page,
+            total_pages: total_pages.max(page),
+            message_id: message.id,
+            channel_id: message.channel_id,
+            guild_id: message.guild_id,
+            application_id: message.author.id,
+            next_button_id,
+        })
+    }
+
+    fn harem_line_name(line: &str) -> Option<String> {
+        let line = line.split(['⇒', '·', '|']).next()?;
+        let line = line.split("=>").next()?;
+        let name = line
+            .replace("**", "")
+            .replace('*', "")
+            .trim_end_matches(['💞', '💖', '❤', '\u{fe0f}', ' '])
+            .trim()
+            .to_string();
+        let is_value = name.chars().all(|c| c.is_ascii_digit() || c.is_whitespace() || c == ',') || name.ends_with(" ka");
+        if name.is_empty() || is_value || name.starts_with("<:") {
+            return None;
+        }
+        Some(name)
     }
 
     fn is_kakera_loot(message: &DiscordMessage) -> bool {
@@ -231,6 +635,8 @@ impl MudaeParser {
         MudaeMessage::KakeraLoot {
             message_id: message.id,
             channel_id: message.channel_id,
+            guild_id: message.guild_id,
+            application_id: message.author.id,
             kakera_type,
             button_id,
         }
@@ -239,7 +645,9 @@ impl MudaeParser {
     fn is_character_info(embed: &Embed) -> bool {
         (embed.title.is_some() || embed.author.is_some()) &&
         (embed.fields.as_ref().map(|f| !f.is_empty()).unwrap_or(false) ||
-         embed.description.is_some())
+         embed.description.as_deref()
+            .map(|d| d.contains("Claim Rank") || d.contains("Like Rank"))
+            .unwrap_or(false))
     }
 
     fn parse_character_info(embed: &Embed) -> MudaeMessage {
@@ -261,44 +669,43 @@ impl MudaeParser {
         }
     }
 
-    fn is_rolls_info(content: &str) -> bool {
-        content.contains("rolls left") 
-            || (content.contains("roll") && content.contains("reset"))
-            || content.contains("roulette is limited")
+    fn rolls_info_phrases(content: &str, languages: &[MudaeLanguage]) -> Option<&'static LanguagePhrases> {
+        let content = content.to_lowercase();
+        languages.iter().map(|language| language.phrases()).find(|phrases| {
+            content.contains(phrases.rolls_left_phrase)
+                || (content.contains(phrases.roll_word) && content.contains(phrases.reset_word))
+                || content.contains(phrases.roulette_limited)
+        })
     }
 
-    fn parse_rolls_info(content: &str, username: Option<&str>) -> MudaeMessage {
-        if content.contains("roulette is limited") {
-            let cooldown_regex = Regex::new(r"\*\*(\w+)\*\*.*?\*\*(\d+)\*\* min left").ok();
-            
-            if let Some(regex) = cooldown_regex {
-                if let Some(caps) = regex.captures(content) {
-                    let message_username = caps.get(1).map(|m| m.as_str());
-                    let minutes = caps.get(2)
-                        .and_then(|m| m.as_str().parse::<i64>().ok());
-                    
-                    if let (Some(msg_username), Some(mins)) = (message_username, minutes) {
-                        if let Some(expected_username) = username {
-                            let msg_username_lower = msg_username.to_lowercase();
-                            let expected_username_lower = expected_username.to_lowercase();
-                            
-                            tracing::debug!("Checking roulette limited message - message username: '{}', expected username: '{}', minutes: {}", 
-                                msg_username, expected_username, mins);
-                            
-                            if msg_username_lower == expected_username_lower {
-                                let reset_time = Some(format!("{}m", mins));
-                                return MudaeMessage::RollsRemaining {
-                                    count: 0,
-                                    reset_time,
-                                };
-                            } else {
-                                tracing::debug!("Roulette limited message username '{}' does not match expected '{}', ignoring", 
-                                    msg_username, expected_username);
-                                return MudaeMessage::Unknown;
-                            }
+    fn parse_rolls_info(content: &str, username: Option<&str>, phrases: &LanguagePhrases) -> MudaeMessage {
+        if content.contains(phrases.roulette_limited) {
+            if let Some(caps) = phrases.roulette_minutes_left.captures(content) {
+                let message_username = caps.get(1).map(|m| m.as_str());
+                let minutes = caps.get(2)
+                    .and_then(|m| m.as_str().parse::<i64>().ok());
+                
+                if let (Some(msg_username), Some(mins)) = (message_username, minutes) {
+                    if let Some(expected_username) = username {
+                        let msg_username_lower = msg_username.to_lowercase();
+                        let expected_username_lower = expected_username.to_lowercase();
+                        
+                        tracing::debug!("Checking roulette limited message - message username: '{}', expected username: '{}', minutes: {}", 
+                            msg_username, expected_username, mins);
+                        
+                        if msg_username_lower == expected_username_lower {
+                            let reset_time = Some(format!("{}m", mins));
+                            return MudaeMessage::RollsRemaining {
+                                count: 0,
+                                reset_time,
+                            };
                         } else {
-                            tracing::debug!("Roulette limited message detected but no username available for matching");
+                            tracing::debug!("Roulette limited message username '{}' does not match expected '{}', ignoring", 
+                                msg_username, expected_username);
+                            return MudaeMessage::Unknown;
                         }
+                    } else {
+                        tracing::debug!("Roulette limited message detected but no username available for matching");
                     }
                 }
             }
@@ -306,23 +713,24 @@ impl MudaeParser {
             return MudaeMessage::Unknown;
         }
 
-        let rolls_regex = Regex::new(r"(\d+)\s*rolls?\s*left").ok();
-        let count = rolls_regex
-            .and_then(|r| r.captures(content))
+        let count = phrases.rolls_left
+            .captures(content)
             .and_then(|caps| caps.get(1))
             .and_then(|m| m.as_str().parse().ok())
             .unwrap_or(0);
 
-        let reset_time_regex = Regex::new(r"reset\s+(?:in\s+)?(\d+)\s*(?:h|hour|hours|hr|hrs)").ok();
-        let reset_time = reset_time_regex
-            .and_then(|r| r.captures(content))
-            .and_then(|caps| caps.get(1))
-            .and_then(|m| m.as_str().parse::<i64>().ok())
-            .map(|hours| format!("{}h", hours))
+        let reset_time = phrases.reset_hours
+            .captures(content)
+            .and_then(|caps| {
+                let hours = caps.get(1)?.as_str().parse::<i64>().ok()?;
+                Some(match caps.get(2).and_then(|m| m.as_str().parse::<i64>().ok()) {
+                    Some(minutes) => format!("{}h {}m", hours, minutes),
+                    None => format!("{}h", hours),
+                })
+            })
             .or_else(|| {
-                let reset_time_regex2 = Regex::new(r"reset\s+(?:in\s+)?(\d+)\s*(?:m|min|minute|minutes)").ok();
-                reset_time_regex2
-                    .and_then(|r| r.captures(content))
+                phrases.reset_minutes
+                    .captures(content)
                     .and_then(|caps| caps.get(1))
                     .and_then(|m| m.as_str().parse::<i64>().ok())
                     .map(|minutes| format!("{}m", minutes))
@@ -334,12 +742,38 @@ impl MudaeParser {
         }
     }
 
-    fn is_claim_info(content: &str) -> bool {
-        content.contains("claim") && (content.contains("available") || content.contains("reset"))
+    fn is_kakera_transfer(content: &str) -> bool {
+        content.to_lowercase().contains("kakera")
+            && (KAKERA_GIVE_REGEX.is_match(content) || KAKERA_STEAL_REGEX.is_match(content))
+    }
+
+    fn parse_kakera_transfer(content: &str) -> Option<MudaeMessage> {
+        if let Some(caps) = KAKERA_GIVE_REGEX.captures(content) {
+            return Some(MudaeMessage::KakeraTransfer {
+                from: caps.get(1)?.as_str().trim().to_string(),
+                amount: caps.get(2)?.as_str().parse().ok()?,
+                to: caps.get(3)?.as_str().trim().to_string(),
+            });
+        }
+
+        let caps = KAKERA_STEAL_REGEX.captures(content)?;
+        Some(MudaeMessage::KakeraTransfer {
+            from: caps.get(3)?.as_str().trim().to_string(),
+            amount: caps.get(2)?.as_str().parse().ok()?,
+            to: caps.get(1)?.as_str().trim().to_string(),
+        })
+    }
+
+    fn claim_info_phrases(content: &str, languages: &[MudaeLanguage]) -> Option<&'static LanguagePhrases> {
+        languages.iter().map(|language| language.phrases()).find(|phrases| {
+            content.contains(phrases.claim_word)
+                && phrases.claim_context.iter().any(|word| content.contains(word))
+        })
     }
 
-    fn parse_claim_info(content: &str) -> MudaeMessage {
-        let available = content.contains("can claim") || content.contains("claim available");
+    fn parse_claim_info(content: &str, phrases: &LanguagePhrases) -> MudaeMessage {
+        let available = phrases.claim_available.iter().any(|phrase| content.contains(phrase))
+            && !phrases.claim_unavailable.iter().any(|phrase| content.contains(phrase));
         
         MudaeMessage::ClaimAvailable {
             available,
@@ -347,6 +781,109 @@ impl MudaeParser {
         }
     }
 
+    fn parse_claim_cooldown(content: &str, username: Option<&str>) -> Option<MudaeMessage> {
+        let caps = CLAIM_COOLDOWN_REGEX.captures(content)?;
+        let name = caps.get(1)?.as_str().trim();
+        if username.is_some_and(|me| !me.eq_ignore_ascii_case(name)) {
+            return Some(MudaeMessage::Unknown);
+        }
+
+        let minutes = caps.get(3)?.as_str();
+        let reset_time = match caps.get(2) {
+            Some(hours) => format!("{}h {}m", hours.as_str(), minutes),
+            None => format!("{}m", minutes),
+        };
+        Some(MudaeMessage::ClaimAvailable {
+            available: false,
+            reset_time: Some(reset_time),
+        })
+    }
+
+    fn parse_captcha_prompt(message: &DiscordMessage, username: Option<&str>) -> Option<MudaeMessage> {
+        let mut text = message.content.trim().to_string();
+        for embed in &message.embeds {
+            for part in [&embed.title, &embed.description].into_iter().flatten() {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(part.trim());
+            }
+        }
+
+        if !CAPTCHA_REGEX.is_match(&text) {
+            return None;
+        }
+
+        let addressed_to_someone = text.contains("<@") || text.contains("**");
+        let mentions_me = username.is_some_and(|me| text.to_lowercase().contains(&me.to_lowercase()));
+        if addressed_to_someone && username.is_some() && !mentions_me {
+            return None;
+        }
+
+        Some(MudaeMessage::CaptchaPrompt { text })
+    }
+
+    fn parse_kakera_power(content: &str, username: Option<&str>) -> Option<MudaeMessage> {
+        let power = KAKERA_POWER_REGEX.captures(content)?.get(1)?.as_str().parse().ok()?;
+        let owner = LEADING_NAME_REGEX
+            .captures(content)
+            .and_then(|caps| caps.get(1))
+            .map(|name| name.as_str().trim());
+        let lower = content.to_lowercase();
+        Some(MudaeMessage::KakeraPower {
+            power,
+            can_react: !lower.contains("can't react") && !lower.contains("cannot react"),
+            is_me: username.is_none_or(|me| owner.is_some_and(|name| name.eq_ignore_ascii_case(me))),
+        })
+    }
+
+    fn parse_divorce(content: &str, username: Option<&str>) -> Option<MudaeMessage> {
+        let caps = DIVORCE_REGEX.captures(content)?;
+        let first = caps.get(1)?.as_str().trim();
+        let second = caps.get(2)?.as_str().trim();
+        let is_me = |name: &str| username.map(|me| me.eq_ignore_ascii_case(name)).unwrap_or(false);
+        let (character, is_me) = match (is_me(first), is_me(second)) {
+            (true, _) => (second, true),
+            (_, true) => (first, true),
+            _ => (second, false),
+        };
+        Some(MudaeMessage::Divorce {
+            character: character.to_string(),
+            is_me,
+        })
+    }
+
+    fn parse_marriage(content: &str) -> Option<MudaeMessage> {
+        let caps = MARRIAGE_REGEX.captures(content)?;
+        Some(MudaeMessage::Marriage {
+            user: caps.get(1)?.as_str().trim().to_string(),
+            character: caps.get(2)?.as_str().trim().to_string(),
+        })
+    }
+
+    fn is_daily_ready(content: &str) -> bool {
+        content.contains("$daily is available")
+    }
+
+    fn parse_daily_kakera(message: &DiscordMessage, username: Option<&str>) -> MudaeMessage {
+        let button_id = message.components.iter()
+            .flat_map(|c| &c.components)
+            .find_map(|b| b.custom_id.clone());
+        let owner = LEADING_NAME_REGEX
+            .captures(&message.content)
+            .and_then(|caps| caps.get(1))
+            .map(|name| name.as_str().trim());
+
+        MudaeMessage::DailyKakera {
+            message_id: message.id,
+            channel_id: message.channel_id,
+            guild_id: message.guild_id,
+            application_id: message.author.id,
+            button_id,
+            is_me: username.is_none_or(|me| owner.is_some_and(|name| name.eq_ignore_ascii_case(me))),
+        }
+    }
+
     pub fn is_claim_emoji(emoji: &str) -> bool {
         CLAIM_EMOJI_REGEX.is_match(emoji)
     }
@@ -367,6 +904,7 @@ impl MudaeParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::Author;
 
     #[test]
     fn test_kakera_regex() {
@@ -375,6 +913,370 @@ mod tests {
         
         let caps = KAKERA_REGEX.captures(text).unwrap();
         assert_eq!(caps.get(1).unwrap().as_str(), "123");
+
+        assert_eq!(MudaeParser::extract_kakera_value("**230**<:kakera:469835869059153940>"), Some(230));
+        assert_eq!(MudaeParser::extract_kakera_value("*230*<:kakera:469835869059153940>"), Some(230));
+        assert_eq!(MudaeParser::extract_kakera_value("Claims: #88"), None);
+    }
+
+    fn button(label: &str, emoji: &str, custom_id: &str) -> Button {
+        Button {
+            button_type: 2,
+            style: Some(2),
+            label: Some(label.to_string()),
+            custom_id: Some(custom_id.to_string()),
+            emoji: Some(crate::client::ButtonEmoji {
+                name: Some(emoji.to_string()),
+                id: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_find_claim_button_prefers_marry() {
+        let components = vec![crate::client::Component {
+            component_type: 1,
+            components: vec![
+                button("Divorce", "💖", "divorce"),
+                button("Info", "ℹ️", "info"),
+                button("Marry", "💖", "marry"),
+            ],
+        }];
+
+        let (found, custom_id) = MudaeParser::find_claim_button(&components);
+        assert!(found);
+        assert_eq!(custom_id.as_deref(), Some("marry"));
+    }
+
+    #[test]
+    fn test_gender_detection() {
+        assert_eq!(MudaeParser::extract_gender("Re:Zero ♀\n**123**"), Some(Gender::Female));
+        assert_eq!(MudaeParser::extract_gender("Naruto ♂️"), Some(Gender::Male));
+        assert_eq!(MudaeParser::extract_gender("Series"), None);
+        assert_eq!(Gender::from_roll_command("$wa"), Some(Gender::Female));
+        assert_eq!(Gender::from_roll_command("$HG"), Some(Gender::Male));
+        assert_eq!(Gender::from_roll_command("$ma"), None);
+    }
+
+    #[test]
+    fn test_parse_kakera_give() {
+        let content = "**Alice** gave **150** <:kakera:469835869059153940> to **Bob**!";
+        assert!(MudaeParser::is_kakera_transfer(content));
+        match MudaeParser::parse_kakera_transfer(content) {
+            Some(MudaeMessage::KakeraTransfer { from, to, amount }) => {
+                assert_eq!(from, "Alice");
+                assert_eq!(to, "Bob");
+                assert_eq!(amount, 150);
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_kakera_steal() {
+        let content = "**Mallory** stole **42** kakera from **Bob**";
+        assert!(MudaeParser::is_kakera_transfer(content));
+        match MudaeParser::parse_kakera_transfer(content) {
+            Some(MudaeMessage::KakeraTransfer { from, to, amount }) => {
+                assert_eq!(from, "Bob");
+                assert_eq!(to, "Mallory");
+                assert_eq!(amount, 42);
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_kakera_transfer_requires_kakera() {
+        assert!(!MudaeParser::is_kakera_transfer("**Alice** gave **3** cookies to **Bob**"));
+    }
+
+    #[test]
+    fn test_parse_kakera_power() {
+        let ready = "**tester**, you __can__ react to kakera right now! Power: **110%**";
+        match MudaeParser::parse_kakera_power(ready, Some("tester")) {
+            Some(MudaeMessage::KakeraPower { power, can_react, is_me }) => {
+                assert_eq!(power, 110);
+                assert!(can_react);
+                assert!(is_me);
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+
+        let drained = "**tester**, you can't react to kakera for **1h 20** min. Power: 10%";
+        match MudaeParser::parse_kakera_power(drained, Some("tester")) {
+            Some(MudaeMessage::KakeraPower { power, can_react, is_me }) => {
+                assert_eq!(power, 10);
+                assert!(!can_react);
+                assert!(is_me);
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+
+        match MudaeParser::parse_kakera_power(ready, Some("someone")) {
+            Some(MudaeMessage::KakeraPower { is_me, .. }) => assert!(!is_me),
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_claim_cooldown() {
+        let cases = [
+            ("**tester**, you can't claim for another **1h 23** min. (`$rt` is available!)", "1h 23m"),
+            ("**Tester**, you cannot marry for another **2h 05** min.", "2h 05m"),
+            ("**tester**, you can't claim for another **45** min.", "45m"),
+        ];
+        for (content, expected) in cases {
+            match MudaeParser::parse_claim_cooldown(content, Some("tester")) {
+                Some(MudaeMessage::ClaimAvailable { available, reset_time }) => {
+                    assert!(!available);
+                    assert_eq!(reset_time.as_deref(), Some(expected));
+                }
+                other => panic!("unexpected parse result for {}: {:?}", content, other),
+            }
+        }
+
+        let other_user = "**alice**, you can't claim for another **1h 23** min.";
+        assert!(matches!(
+            MudaeParser::parse_claim_cooldown(other_user, Some("tester")),
+            Some(MudaeMessage::Unknown)
+        ));
+        assert!(MudaeParser::parse_claim_cooldown("**tester**, you can claim right now!", Some("tester")).is_none());
+    }
+
+    #[test]
+    fn test_parse_marriage() {
+        match MudaeParser::parse_marriage("💖 **alice** and **Rem** are now married! 💖") {
+            Some(MudaeMessage::Marriage { user, character }) => {
+                assert_eq!(user, "alice");
+                assert_eq!(character, "Rem");
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+        assert!(MudaeParser::parse_marriage("**alice** and **Rem** are now divorced.").is_none());
+    }
+
+    #[test]
+    fn test_parse_divorce() {
+        let content = "💔 **tester** and **Rem** are now divorced.";
+        match MudaeParser::parse_divorce(content, Some("Tester")) {
+            Some(MudaeMessage::Divorce { character, is_me }) => {
+                assert_eq!(character, "Rem");
+                assert!(is_me);
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+        match MudaeParser::parse_divorce("**Rem** and **tester** are now divorced.", Some("tester")) {
+            Some(MudaeMessage::Divorce { character, is_me }) => {
+                assert_eq!(character, "Rem");
+                assert!(is_me);
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+        match MudaeParser::parse_divorce("**alice** and **Ram** are now divorced.", Some("tester")) {
+            Some(MudaeMessage::Divorce { character, is_me }) => {
+                assert_eq!(character, "Ram");
+                assert!(!is_me);
+            }
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+        assert!(MudaeParser::parse_divorce("**tester** and **Rem** are now married", Some("tester")).is_none());
+    }
+
+    fn content_message(content: &str) -> DiscordMessage {
+        DiscordMessage {
+            id: 1,
+            channel_id: 100,
+            parent_channel_id: None,
+            guild_id: Some(200),
+            author: Author { id: 432610292342587392, username: "Mudae".to_string(), bot: true },
+            content: content.to_string(),
+            embeds: Vec::new(),
+            components: Vec::new(),
+            webhook_id: None,
+            message_type: 0,
+        }
+    }
+
+    fn embed_message(description: &str, footer: Option<&str>) -> DiscordMessage {
+        let mut message = content_message("");
+        message.embeds.push(Embed {
+            author: Some(crate::client::EmbedAuthor { name: "Levi Ackerman".to_string() }),
+            description: Some(description.to_string()),
+            footer: footer.map(|text| crate::client::EmbedFooter { text: text.to_string() }),
+            ..Embed::default()
+        });
+        message
+    }
+
+    fn parse_roll(message: &DiscordMessage) -> ParsedCharacter {
+        match MudaeParser::parse(message, Some("tester"), MudaeLanguage::English) {
+            MudaeMessage::CharacterRoll { character, .. } => character,
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_roll_kakera_value_falls_back_to_description() {
+        let in_description = embed_message("Shingeki no Kyojin\n**230**<:kakera:469835869059153940>", None);
+        assert_eq!(parse_roll(&in_description).kakera_value, Some(230));
+
+        let in_both = embed_message(
+            "Shingeki no Kyojin\n**230**<:kakera:469835869059153940>",
+            Some("**90**<:kakera:469835869059153940>"),
+        );
+        assert_eq!(parse_roll(&in_both).kakera_value, Some(90));
+    }
+
+    #[test]
+    fn test_roll_owner_in_footer_marks_claimed() {
+        assert!(!parse_roll(&embed_message("Shingeki no Kyojin", None)).is_claimed);
+        assert!(parse_roll(&embed_message("Shingeki no Kyojin\nBelongs to someone_else", None)).is_claimed);
+        assert!(parse_roll(&embed_message("Shingeki no Kyojin", Some("Belongs to someone_else"))).is_claimed);
+    }
+
+    #[test]
+    fn test_series_drops_gender_symbol() {
+        assert_eq!(MudaeParser::extract_series("Re:Zero ♀\n**123**"), "Re:Zero");
+        assert_eq!(MudaeParser::extract_series("Naruto ♂\u{fe0f}"), "Naruto");
+        assert_eq!(MudaeParser::extract_series("Fate/stay night"), "Fate/stay night");
+    }
+
+    #[test]
+    fn test_character_info_requires_rank() {
+        let roll = embed_message("Shingeki no Kyojin ♂\n**230**<:kakera:469835869059153940>", None);
+        assert!(matches!(
+            MudaeParser::parse(&roll, Some("tester"), MudaeLanguage::English),
+            MudaeMessage::CharacterRoll { .. }
+        ));
+
+        let info = embed_message("Shingeki no Kyojin ♂\nClaim Rank: #88\nLike Rank: #120", None);
+        assert!(matches!(
+            MudaeParser::parse(&info, Some("tester"), MudaeLanguage::English),
+            MudaeMessage::CharacterInfo { .. }
+        ));
+    }
+
+    #[test]
+    fn test_daily_ready_notice() {
+        let ready = content_message("**tester**, $daily is available!");
+        assert!(matches!(MudaeParser::parse(&ready, Some("tester"), MudaeLanguage::English), MudaeMessage::DailyReady));
+
+        let unrelated = content_message("**tester**, next $daily reset in **3h 12** min.");
+        assert!(!matches!(MudaeParser::parse(&unrelated, Some("tester"), MudaeLanguage::English), MudaeMessage::DailyReady));
+    }
+
+    #[test]
+    fn test_claimed_roll_with_kakera_button_is_loot() {
+        let mut loot = embed_message("Shingeki no Kyojin ♂\n**230**<:kakera:469835869059153940>", Some("Belongs to someone_else"));
+        loot.components.push(crate::client::Component {
+            component_type: 1,
+            components: vec![button("", "kakeraP", "kakera-1")],
+        });
+        match MudaeParser::parse(&loot, Some("tester"), MudaeLanguage::English) {
+            MudaeMessage::KakeraLoot { button_id, .. } => assert_eq!(button_id.as_deref(), Some("kakera-1")),
+            other => panic!("unexpected parse result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rolls_info_phrasings() {
+        let cases = [
+            (MudaeLanguage::English, "7 rolls left, next reset in 45 min", 7, Some("45m")),
+            (MudaeLanguage::English, "**tester**, you have **10** rolls left. Next rolls reset in **23** min.", 10, Some("23m")),
+            (MudaeLanguage::English, "**tester**, you have **3** rolls left. Next rolls reset in **1h 23** min.", 3, Some("1h 23m")),
+            (MudaeLanguage::English, "**tester**, you have **1** roll left. Next rolls reset in **5** min.", 1, Some("5m")),
+            (MudaeLanguage::English, "You have 0 rolls left, reset in 1 hour", 0, Some("1h")),
+            (MudaeLanguage::English, "You have 2 rolls left, reset in 2 hours 5 minutes", 2, Some("2h 5m")),
+            (MudaeLanguage::English, "You have 4 rolls left. Reset in 1h23min", 4, Some("1h 23m")),
+            (MudaeLanguage::English, "You have 9 rolls left. Rolls reset in 45 minutes", 9, Some("45m")),
+            (MudaeLanguage::English, "You have 6 rolls left. Next reset in 12 mins", 6, Some("12m")),
+            (MudaeLanguage::English, "You have 8 Rolls Left. Next reset in 3 hrs", 8, Some("3h")),
+            (MudaeLanguage::English, "You have 5 rolls left", 5, None),
+            (MudaeLanguage::English, "**tester**, the roulette is limited to 10 uses per hour. **32** min left.", 0, Some("32m")),
+            (MudaeLanguage::English, "**tester**, the roulette is limited to 10 uses per hour. 12 minutes left.", 0, Some("12m")),
+            (MudaeLanguage::French, "**tester**, il vous reste **7** rolls restants. Prochain reset dans **45** min.", 7, Some("45m")),
+            (MudaeLanguage::French, "**tester**, il vous reste **2** rolls restants. Prochain reset dans **1h 10** min.", 2, Some("1h 10m")),
+            (MudaeLanguage::French, "Il vous reste 4 rolls restants. Prochain reset dans 2 heures", 4, Some("2h")),
+            (MudaeLanguage::French, "**tester**, la roulette est limitée à 10 utilisations par heure. **15** min restantes.", 0, Some("15m")),
+            (MudaeLanguage::Spanish, "**tester**, te quedan **7** rolls restantes. Próximo reinicio en **2** h.", 7, Some("2h")),
+            (MudaeLanguage::Spanish, "**tester**, te quedan **3** rolls restantes. Próximo reinicio en **1h 05** min.", 3, Some("1h 5m")),
+            (MudaeLanguage::Spanish, "Te quedan 6 rolls restantes. Próximo reinicio en 30 minutos", 6, Some("30m")),
+            (MudaeLanguage::Spanish, "**tester**, la ruleta está limitada a 10 usos por hora. **9** min restantes.", 0, Some("9m")),
+        ];
+        for (language, content, expected_count, expected_reset) in cases {
+            match MudaeParser::parse(&content_message(content), Some("tester"), language) {
+                MudaeMessage::RollsRemaining { count, reset_time } => {
+                    assert_eq!(count, expected_count, "{}", content);
+                    assert_eq!(reset_time.as_deref(), expected_reset, "{}", content);
+                }
+                other => panic!("unexpected parse result for {}: {:?}", content, other),
+            }
+        }
+
+        let other_user = content_message("**alice**, the roulette is limited to 10 uses per hour. **32** min left.");
+        assert!(matches!(MudaeParser::parse(&other_user, Some("tester"), MudaeLanguage::English), MudaeMessage::Unknown));
+    }
+
+    #[test]
+    fn test_parse_captcha_prompt() {
+        let prompts = [
+            "**tester**, please complete the captcha before using more commands.",
+            "<@123> tester, verify you are human to keep rolling.",
+            "Human verification required. Solve the captcha to continue.",
+            "Are you a robot? Reply with the code shown below.",
+        ];
+        for content in prompts {
+            assert!(
+                matches!(MudaeParser::parse(&content_message(content), Some("tester"), MudaeLanguage::English), MudaeMessage::CaptchaPrompt { .. }),
+                "{}",
+                content
+            );
+        }
+
+        let other_user = content_message("**alice**, please complete the captcha before using more commands.");
+        assert!(!matches!(MudaeParser::parse(&other_user, Some("tester"), MudaeLanguage::English), MudaeMessage::CaptchaPrompt { .. }));
+
+        let unrelated = content_message("**tester**, you have **10** rolls left. Next rolls reset in **23** min.");
+        assert!(!matches!(MudaeParser::parse(&unrelated, Some("tester"), MudaeLanguage::English), MudaeMessage::CaptchaPrompt { .. }));
+    }
+
+    #[test]
+    fn test_claim_info_languages() {
+        let cases = [
+            (MudaeLanguage::English, "you can claim right now! The next claim reset is in 2h", true),
+            (MudaeLanguage::French, "vous pouvez vous marier maintenant ! Prochain reset dans 2h", true),
+            (MudaeLanguage::French, "vous ne pouvez pas vous marier maintenant", false),
+            (MudaeLanguage::Spanish, "no puedes casarte ahora, reinicio en 2h", false),
+            (MudaeLanguage::Spanish, "puedes casarte ahora", true),
+        ];
+        for (language, content, expected) in cases {
+            let phrases = MudaeParser::claim_info_phrases(content, &language.candidates())
+                .unwrap_or_else(|| panic!("not detected as claim info: {}", content));
+            match MudaeParser::parse_claim_info(content, phrases) {
+                MudaeMessage::ClaimAvailable { available, .. } => assert_eq!(available, expected, "{}", content),
+                other => panic!("unexpected parse result: {:?}", other),
+            }
+        }
+        assert_eq!(MudaeLanguage::parse("FR"), Some(MudaeLanguage::French));
+    }
+
+    #[test]
+    fn test_find_claim_emoji() {
+        let button = |label: &str, emoji: &str| Button {
+            button_type: 2,
+            style: Some(2),
+            label: Some(label.to_string()),
+            custom_id: Some(label.to_lowercase()),
+            emoji: Some(crate::client::ButtonEmoji { name: Some(emoji.to_string()), id: None }),
+        };
+        let row = |buttons: Vec<Button>| vec![crate::client::Component { component_type: 1, components: buttons }];
+
+        assert_eq!(MudaeParser::find_claim_emoji(&row(vec![button("Marry", "💕")]), "Re:Zero").as_deref(), Some("💕"));
+        assert_eq!(MudaeParser::find_claim_emoji(&row(vec![button("Divorce", "💘"), button("", "❤️")]), "Re:Zero 💖").as_deref(), Some("❤️"));
+        assert_eq!(MudaeParser::find_claim_emoji(&[], "Re:Zero ❤️\n**120**<:kakera:469835869059153940>").as_deref(), Some("❤️"));
+        assert_eq!(MudaeParser::find_claim_emoji(&[], "Re:Zero 💗").as_deref(), Some("💗"));
+        assert_eq!(MudaeParser::find_claim_emoji(&row(vec![button("Info", "💖")]), "Re:Zero"), None);
     }
 
     #[test]
@@ -383,4 +1285,109 @@ mod tests {
         assert!(MudaeParser::is_claim_emoji("❤️"));
         assert!(!MudaeParser::is_claim_emoji("💎"));
     }
+
+    fn describe(message: &MudaeMessage) -> serde_json::Value {
+        use serde_json::json;
+        match message {
+            MudaeMessage::CharacterRoll { character, has_claim_button, claim_button_id, .. } => json!({
+                "variant": "CharacterRoll",
+                "name": character.name,
+                "series": character.series,
+                "kakera_value": character.kakera_value,
+                "is_claimed": character.is_claimed,
+                "claim_rank": character.claim_rank,
+                "has_claim_button": has_claim_button,
+                "claim_button_id": claim_button_id,
+            }),
+            MudaeMessage::KakeraLoot { kakera_type, button_id, .. } => json!({
+                "variant": "KakeraLoot",
+                "kakera_type": format!("{:?}", kakera_type),
+                "button_id": button_id,
+            }),
+            MudaeMessage::CharacterInfo { name, series, exists } => json!({
+                "variant": "CharacterInfo",
+                "name": name,
+                "series": series,
+                "exists": exists,
+            }),
+            MudaeMessage::RollsRemaining { count, reset_time } => json!({
+                "variant": "RollsRemaining",
+                "count": count,
+                "reset_time": reset_time,
+            }),
+            MudaeMessage::ClaimAvailable { available, reset_time } => json!({
+                "variant": "ClaimAvailable",
+                "available": available,
+                "reset_time": reset_time,
+            }),
+            MudaeMessage::KakeraTransfer { from, to, amount } => json!({
+                "variant": "KakeraTransfer",
+                "from": from,
+                "to": to,
+                "amount": amount,
+            }),
+            MudaeMessage::KakeraPower { power, can_react, is_me } => json!({
+                "variant": "KakeraPower",
+                "power": power,
+                "can_react": can_react,
+                "is_me": is_me,
+            }),
+            MudaeMessage::Divorce { character, is_me } => json!({
+                "variant": "Divorce",
+                "character": character,
+                "is_me": is_me,
+            }),
+            MudaeMessage::Marriage { user, character } => json!({
+                "variant": "Marriage",
+                "user": user,
+                "character": character,
+            }),
+            MudaeMessage::HaremPage(page) => json!({
+                "variant": "HaremPage",
+                "owner": page.owner,
+                "characters": page.characters,
+                "page": page.page,
+                "total_pages": page.total_pages,
+                "next_button_id": page.next_button_id,
+            }),
+            MudaeMessage::DailyReady => json!({ "variant": "DailyReady" }),
+            MudaeMessage::DailyKakera { button_id, is_me, .. } => json!({
+                "variant": "DailyKakera",
+                "button_id": button_id,
+                "is_me": is_me,
+            }),
+            MudaeMessage::ServerConfigNotice { text } => json!({
+                "variant": "ServerConfigNotice",
+                "text": text,
+            }),
+            MudaeMessage::CaptchaPrompt { text } => json!({
+                "variant": "CaptchaPrompt",
+                "text": text,
+            }),
+            MudaeMessage::Unknown => json!({ "variant": "Unknown" }),
+        }
+    }
+
+    #[test]
+    fn test_fixture_corpus() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let mut paths: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().map(|ext| ext == "json").unwrap_or(false))
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty());
+
+        for path in paths {
+            let fixture: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            let message = crate::client::fixtures::message_from_json(&fixture["message"]);
+            let language = fixture["language"].as_str().and_then(MudaeLanguage::parse).unwrap_or_default();
+            let parsed = describe(&MudaeParser::parse(&message, Some("tester"), language));
+
+            for (key, expected) in fixture["expected"].as_object().unwrap() {
+                assert_eq!(&parsed[key], expected, "{}: field '{}' of {}", path.display(), key, parsed);
+            }
+        }
+    }
 }
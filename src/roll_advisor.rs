@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Utc};
+
+pub const DEFAULT_ROLL_WINDOW_MINS: i64 = 60;
+const OBSERVATION_LIMIT: usize = 20;
+const SAME_RESET_TOLERANCE_MINS: i64 = 5;
+const TIP_TOLERANCE_PERCENT: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RollObservation {
+    count: u32,
+    reset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CooldownSuggestion {
+    pub seconds: u64,
+    pub rolls: u32,
+    pub window_mins: i64,
+}
+
+impl CooldownSuggestion {
+    pub fn tip(&self) -> String {
+        let window = match self.window_mins {
+            60 => "hour".to_string(),
+            mins if mins % 60 == 0 => format!("{}h", mins / 60),
+            mins => format!("{}m", mins),
+        };
+        format!("Tip: set cooldown to ~{}s to spread {} rolls/{}", self.seconds, self.rolls, window)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RollAdvisor {
+    observations: VecDeque<RollObservation>,
+    last_tip: Option<u64>,
+}
+
+impl RollAdvisor {
+    pub fn observe(&mut self, count: u32, reset_at: Option<DateTime<Utc>>) {
+        if self.observations.len() >= OBSERVATION_LIMIT {
+            self.observations.pop_front();
+        }
+        self.observations.push_back(RollObservation { count, reset_at });
+    }
+
+    pub fn suggest(&self) -> Option<CooldownSuggestion> {
+        let rolls = self.observations.iter().map(|o| o.count).max().filter(|&rolls| rolls > 0)?;
+        let window_mins = self.window_mins().unwrap_or(DEFAULT_ROLL_WINDOW_MINS);
+        let raw = (window_mins as u64 * 60) / rolls as u64;
+        let seconds = ((raw + 5) / 10 * 10).max(10);
+        Some(CooldownSuggestion { seconds, rolls, window_mins })
+    }
+
+    pub fn tip_for(&mut self, current_cooldown: u64) -> Option<String> {
+        let suggestion = self.suggest()?;
+        let tolerance = suggestion.seconds * TIP_TOLERANCE_PERCENT / 100;
+        if current_cooldown.abs_diff(suggestion.seconds) <= tolerance {
+            return None;
+        }
+        if self.last_tip.replace(suggestion.seconds) == Some(suggestion.seconds) {
+            return None;
+        }
+        Some(suggestion.tip())
+    }
+
+    fn window_mins(&self) -> Option<i64> {
+        let mut resets: Vec<DateTime<Utc>> = self.observations.iter().filter_map(|o| o.reset_at).collect();
+        resets.sort();
+        let tolerance = Duration::minutes(SAME_RESET_TOLERANCE_MINS);
+        resets.dedup_by(|later, earlier| *later - *earlier < tolerance);
+        resets
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).num_minutes())
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn test_no_suggestion_without_rolls() {
+        let mut advisor = RollAdvisor::default();
+        assert_eq!(advisor.suggest(), None);
+
+        advisor.observe(0, Some(at(30)));
+        assert_eq!(advisor.suggest(), None);
+    }
+
+    #[test]
+    fn test_suggestion_defaults_to_hourly_window() {
+        let mut advisor = RollAdvisor::default();
+        advisor.observe(10, Some(at(58)));
+        advisor.observe(6, Some(at(59)));
+
+        let suggestion = advisor.suggest().unwrap();
+        assert_eq!(suggestion, CooldownSuggestion { seconds: 360, rolls: 10, window_mins: 60 });
+        assert_eq!(suggestion.tip(), "Tip: set cooldown to ~360s to spread 10 rolls/hour");
+    }
+
+    #[test]
+    fn test_window_inferred_from_distinct_resets() {
+        let mut advisor = RollAdvisor::default();
+        advisor.observe(8, Some(at(30)));
+        advisor.observe(3, Some(at(31)));
+        advisor.observe(8, Some(at(150)));
+
+        let suggestion = advisor.suggest().unwrap();
+        assert_eq!(suggestion.window_mins, 120);
+        assert_eq!(suggestion.seconds, 900);
+    }
+
+    #[test]
+    fn test_tip_only_when_cooldown_is_off_and_not_repeated() {
+        let mut advisor = RollAdvisor::default();
+        advisor.observe(10, None);
+
+        assert_eq!(advisor.tip_for(350), None);
+        assert!(advisor.tip_for(3600).is_some());
+        assert_eq!(advisor.tip_for(3600), None);
+
+        advisor.observe(12, None);
+        assert_eq!(advisor.tip_for(3600).as_deref(), Some("Tip: set cooldown to ~300s to spread 12 rolls/hour"));
+    }
+}
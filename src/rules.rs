@@ -0,0 +1,161 @@
+use crate::parser::ParsedCharacter;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Wished,
+    KakeraAtLeast(u32),
+    KakeraAtMost(u32),
+    Series(String),
+    RankAtMost(u32),
+    Unranked,
+}
+
+impl Condition {
+    pub fn parse(entry: &str) -> Option<Self> {
+        let entry = entry.trim();
+        let lower = entry.to_lowercase();
+        if lower == "wished" {
+            return Some(Condition::Wished);
+        }
+        if lower == "unranked" {
+            return Some(Condition::Unranked);
+        }
+        if let Some(value) = lower.strip_prefix("kakera>=") {
+            return value.trim().parse().ok().map(Condition::KakeraAtLeast);
+        }
+        if let Some(value) = lower.strip_prefix("kakera<=") {
+            return value.trim().parse().ok().map(Condition::KakeraAtMost);
+        }
+        if let Some(value) = lower.strip_prefix("rank<=") {
+            return value.trim().parse().ok().map(Condition::RankAtMost);
+        }
+        if entry.get(..7).is_some_and(|prefix| prefix.eq_ignore_ascii_case("series=")) {
+            let series = entry[7..].trim();
+            if !series.is_empty() {
+                return Some(Condition::Series(series.to_string()));
+            }
+        }
+        None
+    }
+
+    pub fn matches(&self, character: &ParsedCharacter, is_wished: bool) -> bool {
+        match self {
+            Condition::Wished => is_wished,
+            Condition::KakeraAtLeast(min) => character.kakera_value.is_some_and(|v| v >= *min),
+            Condition::KakeraAtMost(max) => character.kakera_value.is_some_and(|v| v <= *max),
+            Condition::Series(series) => character.series.trim().eq_ignore_ascii_case(series),
+            Condition::RankAtMost(max) => character.claim_rank.is_some_and(|r| r <= *max),
+            Condition::Unranked => character.claim_rank.is_none(),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Wished => write!(f, "wished"),
+            Condition::KakeraAtLeast(min) => write!(f, "kakera>={}", min),
+            Condition::KakeraAtMost(max) => write!(f, "kakera<={}", max),
+            Condition::Series(series) => write!(f, "series={}", series),
+            Condition::RankAtMost(max) => write!(f, "rank<={}", max),
+            Condition::Unranked => write!(f, "unranked"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimRules {
+    pub sets: Vec<Vec<Condition>>,
+}
+
+impl ClaimRules {
+    pub fn parse(rules: &str) -> Option<Self> {
+        let mut sets = Vec::new();
+        for set in rules.split('|') {
+            let conditions = set
+                .split('&')
+                .map(Condition::parse)
+                .collect::<Option<Vec<_>>>()?;
+            sets.push(conditions);
+        }
+        Some(Self { sets })
+    }
+
+    pub fn matches(&self, character: &ParsedCharacter, is_wished: bool) -> bool {
+        self.sets
+            .iter()
+            .any(|set| set.iter().all(|condition| condition.matches(character, is_wished)))
+    }
+}
+
+impl Default for ClaimRules {
+    fn default() -> Self {
+        Self {
+            sets: vec![vec![Condition::Wished]],
+        }
+    }
+}
+
+impl fmt::Display for ClaimRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sets: Vec<String> = self
+            .sets
+            .iter()
+            .map(|set| set.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" & "))
+            .collect();
+        write!(f, "{}", sets.join(" | "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(series: &str, kakera_value: Option<u32>, claim_rank: Option<u32>) -> ParsedCharacter {
+        ParsedCharacter {
+            name: "Rem".to_string(),
+            series: series.to_string(),
+            kakera_value,
+            image_url: None,
+            is_claimed: false,
+            claim_rank,
+            is_wished: false,
+            gender: None,
+            claim_emoji: None,
+        }
+    }
+
+    #[test]
+    fn test_default_rules_match_wished_only() {
+        let rules = ClaimRules::default();
+        assert!(rules.matches(&character("Re:Zero", Some(10), None), true));
+        assert!(!rules.matches(&character("Re:Zero", Some(5000), None), false));
+    }
+
+    #[test]
+    fn test_and_or_combination() {
+        let rules = ClaimRules::parse("wished | kakera>=150 & unranked").unwrap();
+        assert!(rules.matches(&character("Re:Zero", Some(10), Some(5)), true));
+        assert!(rules.matches(&character("Re:Zero", Some(200), None), false));
+        assert!(!rules.matches(&character("Re:Zero", Some(200), Some(5)), false));
+        assert!(!rules.matches(&character("Re:Zero", Some(100), None), false));
+        assert!(!rules.matches(&character("Re:Zero", None, None), false));
+    }
+
+    #[test]
+    fn test_series_and_rank_conditions() {
+        let rules = ClaimRules::parse("series=Re:Zero & rank<=100").unwrap();
+        assert!(rules.matches(&character("re:zero", None, Some(100)), false));
+        assert!(!rules.matches(&character("Re:Zero", None, Some(101)), false));
+        assert!(!rules.matches(&character("Naruto", None, Some(1)), false));
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let text = "wished | kakera>=150 & unranked | series=Re:Zero & kakera<=500 & rank<=100";
+        assert_eq!(ClaimRules::parse(text).unwrap().to_string(), text);
+        assert!(ClaimRules::parse("wished | kakera>=abc").is_none());
+        assert!(ClaimRules::parse("").is_none());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::client::DiscordClient;
+use crate::config::Config;
+use crate::database::Database;
+use crate::wishlist::WishlistManager;
+use std::fmt;
+
+pub struct CheckResult {
+    pub name: String,
+    pub outcome: Result<String, String>,
+}
+
+impl CheckResult {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), outcome: Ok(detail.into()) }
+    }
+
+    fn fail(name: impl Into<String>, error: impl fmt::Display) -> Self {
+        Self { name: name.into(), outcome: Err(format!("{:#}", error)) }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            Ok(detail) => write!(f, "PASS  {:<20} {}", self.name, detail),
+            Err(error) => write!(f, "FAIL  {:<20} {}", self.name, error),
+        }
+    }
+}
+
+pub async fn run(db: Result<&Database, &anyhow::Error>, config: &Config, token: anyhow::Result<Option<String>>) -> Vec<CheckResult> {
+    let mut results = vec![match db {
+        Ok(db) => check_database(db),
+        Err(e) => CheckResult::fail("Database", e),
+    }];
+
+    let client = match token {
+        Ok(Some(token)) => {
+            let client = DiscordClient::new(token);
+            match client.get_current_user().await {
+                Ok(user) => {
+                    results.push(CheckResult::pass("Token", format!("logged in as {}", user.username)));
+                    Some(client)
+                }
+                Err(e) => {
+                    results.push(CheckResult::fail("Token", e));
+                    None
+                }
+            }
+        }
+        Ok(None) => {
+            results.push(CheckResult::fail("Token", "no token configured"));
+            None
+        }
+        Err(e) => {
+            results.push(CheckResult::fail("Token", e));
+            None
+        }
+    };
+
+    match db.map(Database::get_channels) {
+        Err(_) => results.push(CheckResult::fail("Channels", "skipped, database check failed")),
+        Ok(Ok(channels)) if channels.is_empty() => results.push(CheckResult::fail("Channels", "no channels configured")),
+        Ok(Ok(channels)) => {
+            for channel_id in channels {
+                let name = format!("Channel {}", channel_id);
+                let Some(ref client) = client else {
+                    results.push(CheckResult::fail(name, "skipped, token check failed"));
+                    continue;
+                };
+                match client.get_channel(channel_id).await {
+                    Ok(channel) => results.push(CheckResult::pass(name, channel.name.map(|n| format!("#{}", n)).unwrap_or_else(|| "reachable".to_string()))),
+                    Err(e) => results.push(CheckResult::fail(name, e)),
+                }
+            }
+        }
+        Ok(Err(e)) => results.push(CheckResult::fail("Channels", e)),
+    }
+
+    results.push(check_wishlist(config).await);
+    results
+}
+
+fn check_database(db: &Database) -> CheckResult {
+    let location = match Database::get_db_path() {
+        Ok(path) => path.display().to_string(),
+        Err(e) => return CheckResult::fail("Database", e),
+    };
+    match db.load_config() {
+        Ok(_) => CheckResult::pass("Database", location),
+        Err(e) => CheckResult::fail("Database", format!("{}: {:#}", location, e)),
+    }
+}
+
+async fn check_wishlist(config: &Config) -> CheckResult {
+    if !config.wishlist_enabled {
+        return CheckResult::pass("Wishlist", "disabled");
+    }
+    let path = config.wishlist_path();
+    match WishlistManager::validate_file(&path).await {
+        Ok(Some(count)) => CheckResult::pass("Wishlist", format!("{} characters in {}", count, path.display())),
+        Ok(None) => CheckResult::pass("Wishlist", format!("{} not created yet", path.display())),
+        Err(e) => CheckResult::fail("Wishlist", format!("{}: {:#}", path.display(), e)),
+    }
+}
+
+pub fn report(results: &[CheckResult]) -> String {
+    let failed = results.iter().filter(|r| !r.passed()).count();
+    let mut lines: Vec<String> = results.iter().map(ToString::to_string).collect();
+    lines.push(String::new());
+    if failed == 0 {
+        lines.push(format!("All {} checks passed", results.len()));
+    } else {
+        lines.push(format!("{} of {} checks failed", failed, results.len()));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_check_reads_config() {
+        let db = Database::open_in_memory().unwrap();
+        let result = check_database(&db);
+        assert!(result.passed());
+        assert!(result.to_string().contains(&Database::get_db_path().unwrap().display().to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_wishlist_check_reports_unparseable_file() {
+        let path = std::env::temp_dir().join(format!("self-test-wishlist-{}.json", std::process::id()));
+        let config = Config { wishlist_file: path.to_string_lossy().to_string(), ..Config::default() };
+
+        assert!(check_wishlist(&config).await.passed());
+
+        tokio::fs::write(&path, "{ not json").await.unwrap();
+        let result = check_wishlist(&config).await;
+        assert!(!result.passed());
+        assert!(result.to_string().starts_with("FAIL  Wishlist"));
+
+        let report = report(&[CheckResult::pass("Database", "ok"), result]);
+        assert!(report.ends_with("1 of 2 checks failed"));
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+
+    #[tokio::test]
+    async fn test_database_failure_is_reported_as_a_row() {
+        let error = anyhow::anyhow!("unable to open database file");
+        let config = Config { wishlist_enabled: false, ..Config::default() };
+        let results = run(Err(&error), &config, Ok(None)).await;
+
+        let rows: Vec<String> = results.iter().map(ToString::to_string).collect();
+        assert!(rows[0].starts_with("FAIL  Database"));
+        assert!(rows[0].contains("unable to open database file"));
+        assert!(rows.iter().any(|row| row.starts_with("FAIL  Channels") && row.contains("skipped")));
+        assert!(report(&results).ends_with(&format!("3 of {} checks failed", results.len())));
+    }
+}
@@ -1,7 +1,9 @@
+use crate::config::Config;
 use crate::database::Database;
+use crate::glyphs;
 use anyhow::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -10,7 +12,7 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Borders, Clear, Paragraph},
     Frame, Terminal,
 };
 use std::io;
@@ -33,6 +35,7 @@ pub struct SetupWizard {
     channels_input: String,
     cursor_visible: bool,
     error_message: Option<String>,
+    suggest_ascii: bool,
 }
 
 impl SetupWizard {
@@ -42,6 +45,8 @@ impl SetupWizard {
         execute!(stdout, EnterAlternateScreen)?;
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
+        let ascii_mode = Config::load_from_db(&db).ascii_mode;
+        glyphs::set_ascii_mode(ascii_mode);
 
         Ok(Self {
             terminal,
@@ -51,6 +56,7 @@ impl SetupWizard {
             channels_input: String::new(),
             cursor_visible: true,
             error_message: None,
+            suggest_ascii: !ascii_mode && !glyphs::terminal_supports_unicode(),
         })
     }
 
@@ -65,6 +71,7 @@ impl SetupWizard {
                             SetupStep::Welcome => {
                                 match key.code {
                                     KeyCode::Enter => self.step = SetupStep::Token,
+                                    KeyCode::Char('a') | KeyCode::Char('A') if self.suggest_ascii => self.enable_ascii_mode(),
                                     KeyCode::Esc => {
                                         self.cleanup()?;
                                         return Ok(false);
@@ -140,6 +147,19 @@ impl SetupWizard {
         }
     }
 
+    fn enable_ascii_mode(&mut self) {
+        let mut config = Config::load_from_db(&self.db);
+        config.ascii_mode = true;
+        match config.save_to_db(&self.db) {
+            Ok(()) => {
+                glyphs::set_ascii_mode(true);
+                self.suggest_ascii = false;
+                self.error_message = None;
+            }
+            Err(e) => self.error_message = Some(format!("Failed to save ASCII mode: {}", e)),
+        }
+    }
+
     fn validate_token(&mut self) -> bool {
         let token = self.token_input.trim();
         if token.is_empty() {
@@ -195,6 +215,7 @@ impl SetupWizard {
         let channels_input = self.channels_input.clone();
         let cursor_visible = self.cursor_visible;
         let error_message = self.error_message.clone();
+        let suggest_ascii = self.suggest_ascii;
 
         self.terminal.draw(|frame| {
             let size = frame.size();
@@ -203,7 +224,7 @@ impl SetupWizard {
             frame.render_widget(Clear, area);
 
             match step {
-                SetupStep::Welcome => Self::render_welcome(frame, area),
+                SetupStep::Welcome => Self::render_welcome(frame, area, suggest_ascii, &error_message),
                 SetupStep::Token => Self::render_token_input(frame, area, &token_input, cursor_visible, &error_message),
                 SetupStep::Channels => Self::render_channels_input(frame, area, &channels_input, cursor_visible, &error_message),
                 SetupStep::Complete => Self::render_complete(frame, area),
@@ -213,8 +234,9 @@ impl SetupWizard {
         Ok(())
     }
 
-    fn render_welcome(frame: &mut Frame, area: Rect) {
-        let text = vec![
+    fn render_welcome(frame: &mut Frame, area: Rect, suggest_ascii: bool, error: &Option<String>) {
+        let g = glyphs::current();
+        let mut text = vec![
             Line::from(""),
             Line::from(vec![
                 Span::styled("  Welcome to ", Style::default().fg(Color::White)),
@@ -224,9 +246,23 @@ impl SetupWizard {
             Line::from(Span::styled("  This wizard will help you set up the bot.", Style::default().fg(Color::Gray))),
             Line::from(""),
             Line::from(Span::styled("  You will need:", Style::default().fg(Color::White))),
-            Line::from(Span::styled("    • Your Discord user token", Style::default().fg(Color::Cyan))),
-            Line::from(Span::styled("    • Channel IDs to monitor", Style::default().fg(Color::Cyan))),
+            Line::from(Span::styled(format!("    {} Your Discord user token", g.bullet), Style::default().fg(Color::Cyan))),
+            Line::from(Span::styled(format!("    {} Channel IDs to monitor", g.bullet), Style::default().fg(Color::Cyan))),
             Line::from(""),
+        ];
+
+        if suggest_ascii {
+            text.push(Line::from(Span::styled("  Your terminal may not display Unicode symbols.", Style::default().fg(Color::Yellow))));
+            text.push(Line::from(vec![
+                Span::styled("  Press ", Style::default().fg(Color::DarkGray)),
+                Span::styled("A", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(" to switch to ASCII mode", Style::default().fg(Color::DarkGray)),
+            ]));
+        } else if let Some(err) = error {
+            text.push(Line::from(Span::styled(format!("  {} {}", g.error, err), Style::default().fg(Color::Red))));
+        }
+
+        text.extend([
             Line::from(""),
             Line::from(vec![
                 Span::styled("  Press ", Style::default().fg(Color::DarkGray)),
@@ -235,10 +271,10 @@ impl SetupWizard {
                 Span::styled("Esc", Style::default().fg(Color::Red)),
                 Span::styled(" to exit", Style::default().fg(Color::DarkGray)),
             ]),
-        ];
+        ]);
 
         let paragraph = Paragraph::new(text).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Magenta))
                 .title(" Setup Wizard ")
@@ -249,7 +285,7 @@ impl SetupWizard {
     }
 
     fn render_token_input(frame: &mut Frame, area: Rect, input: &str, cursor: bool, error: &Option<String>) {
-        let cursor_char = if cursor { "▌" } else { " " };
+        let cursor_char = if cursor { glyphs::current().cursor } else { " " };
         let display_token = if input.len() > 20 {
             format!("{}...{}", &input[..10], &input[input.len()-10..])
         } else if input.is_empty() {
@@ -274,7 +310,7 @@ impl SetupWizard {
         ];
 
         if let Some(err) = error {
-            text.push(Line::from(Span::styled(format!("  ✗ {}", err), Style::default().fg(Color::Red))));
+            text.push(Line::from(Span::styled(format!("  {} {}", glyphs::current().error, err), Style::default().fg(Color::Red))));
         }
 
         text.push(Line::from(""));
@@ -285,7 +321,7 @@ impl SetupWizard {
         ]));
 
         let paragraph = Paragraph::new(text).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
                 .title(" Setup - Token ")
@@ -296,7 +332,7 @@ impl SetupWizard {
     }
 
     fn render_channels_input(frame: &mut Frame, area: Rect, input: &str, cursor: bool, error: &Option<String>) {
-        let cursor_char = if cursor { "▌" } else { " " };
+        let cursor_char = if cursor { glyphs::current().cursor } else { " " };
 
         let mut text = vec![
             Line::from(""),
@@ -314,7 +350,7 @@ impl SetupWizard {
         ];
 
         if let Some(err) = error {
-            text.push(Line::from(Span::styled(format!("  ✗ {}", err), Style::default().fg(Color::Red))));
+            text.push(Line::from(Span::styled(format!("  {} {}", glyphs::current().error, err), Style::default().fg(Color::Red))));
         }
 
         text.push(Line::from(""));
@@ -325,7 +361,7 @@ impl SetupWizard {
         ]));
 
         let paragraph = Paragraph::new(text).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
                 .title(" Setup - Channels ")
@@ -338,12 +374,12 @@ impl SetupWizard {
     fn render_complete(frame: &mut Frame, area: Rect) {
         let text = vec![
             Line::from(""),
-            Line::from(Span::styled("  ✓ Setup Complete!", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(format!("  {} Setup Complete!", glyphs::current().success), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
             Line::from(""),
             Line::from(Span::styled("  Your configuration has been saved.", Style::default().fg(Color::Gray))),
             Line::from(""),
             Line::from(Span::styled("  You can update these settings anytime by:", Style::default().fg(Color::White))),
-            Line::from(Span::styled("    • Pressing 's' in the main dashboard", Style::default().fg(Color::Cyan))),
+            Line::from(Span::styled(format!("    {} Pressing 's' in the main dashboard", glyphs::current().bullet), Style::default().fg(Color::Cyan))),
             Line::from(""),
             Line::from(""),
             Line::from(vec![
@@ -354,7 +390,7 @@ impl SetupWizard {
         ];
 
         let paragraph = Paragraph::new(text).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Green))
                 .title(" Setup Complete ")
@@ -398,6 +434,42 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+pub fn prompt_passphrase(prompt: &str) -> Result<String> {
+    use std::io::Write;
+
+    print!("{}: ", prompt);
+    io::stdout().flush()?;
+
+    enable_raw_mode()?;
+    let mut passphrase = String::new();
+    let result = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Esc => break Err(anyhow::anyhow!("Passphrase entry cancelled")),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(anyhow::anyhow!("Passphrase entry cancelled"));
+                }
+                KeyCode::Backspace => {
+                    passphrase.pop();
+                }
+                KeyCode::Char(c) => passphrase.push(c),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
+        }
+    };
+    disable_raw_mode()?;
+    println!();
+
+    result?;
+    if passphrase.is_empty() {
+        anyhow::bail!("Passphrase cannot be empty");
+    }
+    Ok(passphrase)
+}
+
 pub fn run_setup(db: Arc<Database>) -> Result<bool> {
     let mut wizard = SetupWizard::new(db)?;
     wizard.run()
@@ -1,10 +1,20 @@
+use crate::claim_state::ClaimState;
 use crate::database::{Database, SavedStats};
 use chrono::{DateTime, Utc};
-use std::collections::VecDeque;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::debug;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Notify, RwLock};
+use tracing::{debug, warn};
+
+pub const CHANNEL_ACCESS_FAILURE_LIMIT: u32 = 3;
+pub const LARGE_KAKERA_SAVE_THRESHOLD: u64 = 500;
+pub const HALT_MARKER_FILE: &str = "HALTED";
+pub const KAKERA_POWER_TTL_MINUTES: i64 = 30;
+pub const UNCONFIRMED_CLAIM_TTL_SECS: u64 = 60;
 
 #[derive(Debug, Clone)]
 pub struct ActivityEvent {
@@ -21,6 +31,22 @@ pub struct RollEntry {
     pub kakera_value: Option<u32>,
     pub claimed: bool,
     pub is_wished: bool,
+    pub message_id: u64,
+    pub channel_id: u64,
+    pub guild_id: Option<u64>,
+    pub application_id: u64,
+    pub claim_button_id: Option<String>,
+    pub claim_emoji: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingClaim {
+    pub name: String,
+    pub series: String,
+    pub message_id: u64,
+    pub channel_id: u64,
+    pub kakera_value: Option<u32>,
+    pub fuzzy_match: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +56,7 @@ pub enum ChannelActivity {
         kakera_value: Option<u32>,
         is_wished: bool,
         claimed: bool,
+        decision: ClaimDecision,
     },
     UserMessage {
         username: String,
@@ -38,6 +65,60 @@ pub enum ChannelActivity {
     MudaeInfo {
         message: String,
     },
+    KakeraPower {
+        power: u32,
+        can_react: bool,
+    },
+    Divorce {
+        character_name: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimDecision {
+    Claiming { wished: bool },
+    AlreadyClaimed,
+    Paused,
+    ClaimOnCooldown,
+    AlreadyOwned,
+    SoftWish,
+    NotWished,
+    NoRuleMatch,
+    BelowMinKakera,
+    AboveMaxKakera,
+    GenderFilter,
+}
+
+impl ClaimDecision {
+    pub fn is_claim(&self) -> bool {
+        matches!(self, ClaimDecision::Claiming { .. })
+    }
+}
+
+impl fmt::Display for ClaimDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            ClaimDecision::Claiming { wished: true } => return f.write_str("claiming (wished)"),
+            ClaimDecision::Claiming { wished: false } => return f.write_str("claiming (rule match)"),
+            ClaimDecision::AlreadyClaimed => "already claimed",
+            ClaimDecision::Paused => "paused",
+            ClaimDecision::ClaimOnCooldown => "claim on cooldown",
+            ClaimDecision::AlreadyOwned => "already owned",
+            ClaimDecision::SoftWish => "soft wish",
+            ClaimDecision::NotWished => "not wished",
+            ClaimDecision::NoRuleMatch => "no claim rule matched",
+            ClaimDecision::BelowMinKakera => "below min kakera",
+            ClaimDecision::AboveMaxKakera => "above max kakera",
+            ClaimDecision::GenderFilter => "gender filter",
+        };
+        write!(f, "skipped ({})", reason)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KakeraPower {
+    pub power: u32,
+    pub can_react: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -52,6 +133,59 @@ pub enum EventType {
     Wishlist,
 }
 
+impl EventType {
+    pub const COUNT: usize = EventType::Wishlist as usize + 1;
+
+    pub fn all() -> [EventType; Self::COUNT] {
+        [
+            EventType::Info,
+            EventType::Success,
+            EventType::Warning,
+            EventType::Error,
+            EventType::Roll,
+            EventType::Claim,
+            EventType::Kakera,
+            EventType::Wishlist,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EventType::Info => "Info",
+            EventType::Success => "Success",
+            EventType::Warning => "Warning",
+            EventType::Error => "Error",
+            EventType::Roll => "Roll",
+            EventType::Claim => "Claim",
+            EventType::Kakera => "Kakera",
+            EventType::Wishlist => "Wishlist",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsSnapshot {
+    pub characters_rolled: u64,
+    pub characters_claimed: u64,
+    pub claim_retries: u64,
+    pub wishlist_matches: u64,
+    pub kakera_collected: u64,
+    pub kakera_received: u64,
+    pub kakera_claimed_value: u64,
+    pub claims_without_value: u64,
+    pub rolls_executed: u64,
+    pub rolls_remaining: u64,
+    pub pending_actions: u64,
+    pub reconnects: u64,
+    pub session_uptime_seconds: u64,
+    pub total_uptime_seconds: u64,
+    pub events: BTreeMap<String, u64>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConnectionStatus {
     Disconnected,
@@ -60,26 +194,52 @@ pub enum ConnectionStatus {
     Reconnecting,
 }
 
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    pub status: ConnectionStatus,
+    pub reconnects: u64,
+    pub last_disconnect_reason: Option<String>,
+}
+
 #[allow(dead_code)]
 pub struct Stats {
     pub start_time: DateTime<Utc>,
     pub characters_rolled: AtomicU64,
     pub characters_claimed: AtomicU64,
+    claim_retries: AtomicU64,
     pub wishlist_matches: AtomicU64,
     pub kakera_collected: AtomicU64,
+    pub kakera_received: AtomicU64,
+    total_kakera_claimed_value: AtomicU64,
+    claims_without_value: AtomicU64,
     pub rolls_executed: AtomicU64,
     pub total_uptime_seconds: AtomicU64,
     pub connection_status: RwLock<ConnectionStatus>,
-    pub claim_available: AtomicBool,
+    reconnects: AtomicU64,
+    last_disconnect_reason: RwLock<Option<String>>,
+    claim_state: Mutex<ClaimState>,
     pub rolls_remaining: AtomicU64,
+    pending_rolls: AtomicU64,
+    queued_actions: AtomicU64,
+    pub window_rolls_used: AtomicU64,
+    roll_window_end: AtomicI64,
     pub next_roll_reset: RwLock<Option<DateTime<Utc>>>,
-    pub next_claim_reset: RwLock<Option<DateTime<Utc>>>,
+    kakera_power: RwLock<Option<(KakeraPower, DateTime<Utc>)>>,
+    pub idle_until: RwLock<Option<DateTime<Utc>>>,
     pub activity_log: RwLock<VecDeque<ActivityEvent>>,
+    event_counts: [AtomicU64; EventType::COUNT],
     pub roll_history: RwLock<VecDeque<RollEntry>>,
+    pending_claims: RwLock<HashMap<String, PendingClaim>>,
     pub channel_activity: RwLock<VecDeque<ChannelActivity>>,
     pub user_id: AtomicU64,
     pub username: RwLock<Option<String>>,
     pub paused: AtomicBool,
+    halted: AtomicBool,
+    captcha_alert: AtomicBool,
+    account_alert: AtomicBool,
+    owned_sync_requested: AtomicBool,
+    channel_access_failures: RwLock<HashMap<u64, u32>>,
+    save_requested: Notify,
     max_log_entries: usize,
     max_channel_activity: usize,
 }
@@ -91,21 +251,40 @@ impl Stats {
             start_time: Utc::now(),
             characters_rolled: AtomicU64::new(0),
             characters_claimed: AtomicU64::new(0),
+            claim_retries: AtomicU64::new(0),
             wishlist_matches: AtomicU64::new(0),
             kakera_collected: AtomicU64::new(0),
+            kakera_received: AtomicU64::new(0),
+            total_kakera_claimed_value: AtomicU64::new(0),
+            claims_without_value: AtomicU64::new(0),
             rolls_executed: AtomicU64::new(0),
             total_uptime_seconds: AtomicU64::new(0),
             connection_status: RwLock::new(ConnectionStatus::Disconnected),
-            claim_available: AtomicBool::new(true),
+            reconnects: AtomicU64::new(0),
+            last_disconnect_reason: RwLock::new(None),
+            claim_state: Mutex::new(ClaimState::default()),
             rolls_remaining: AtomicU64::new(0),
+            pending_rolls: AtomicU64::new(0),
+            queued_actions: AtomicU64::new(0),
+            window_rolls_used: AtomicU64::new(0),
+            roll_window_end: AtomicI64::new(0),
             next_roll_reset: RwLock::new(None),
-            next_claim_reset: RwLock::new(None),
+            kakera_power: RwLock::new(None),
+            idle_until: RwLock::new(None),
             activity_log: RwLock::new(VecDeque::with_capacity(100)),
+            event_counts: Default::default(),
             roll_history: RwLock::new(VecDeque::with_capacity(50)),
+            pending_claims: RwLock::new(HashMap::new()),
             channel_activity: RwLock::new(VecDeque::with_capacity(50)),
             user_id: AtomicU64::new(0),
             username: RwLock::new(None),
             paused: AtomicBool::new(false),
+            halted: AtomicBool::new(false),
+            captcha_alert: AtomicBool::new(false),
+            account_alert: AtomicBool::new(false),
+            owned_sync_requested: AtomicBool::new(false),
+            channel_access_failures: RwLock::new(HashMap::new()),
+            save_requested: Notify::new(),
             max_log_entries: 100,
             max_channel_activity: 50,
         })
@@ -116,21 +295,40 @@ impl Stats {
             start_time: Utc::now(),
             characters_rolled: AtomicU64::new(saved.characters_rolled),
             characters_claimed: AtomicU64::new(saved.characters_claimed),
+            claim_retries: AtomicU64::new(0),
             wishlist_matches: AtomicU64::new(saved.wishlist_matches),
             kakera_collected: AtomicU64::new(saved.kakera_collected),
+            kakera_received: AtomicU64::new(saved.kakera_received),
+            total_kakera_claimed_value: AtomicU64::new(0),
+            claims_without_value: AtomicU64::new(0),
             rolls_executed: AtomicU64::new(saved.rolls_executed),
             total_uptime_seconds: AtomicU64::new(saved.total_uptime_seconds),
             connection_status: RwLock::new(ConnectionStatus::Disconnected),
-            claim_available: AtomicBool::new(true),
+            reconnects: AtomicU64::new(0),
+            last_disconnect_reason: RwLock::new(None),
+            claim_state: Mutex::new(ClaimState::default()),
             rolls_remaining: AtomicU64::new(0),
+            pending_rolls: AtomicU64::new(0),
+            queued_actions: AtomicU64::new(0),
+            window_rolls_used: AtomicU64::new(0),
+            roll_window_end: AtomicI64::new(0),
             next_roll_reset: RwLock::new(None),
-            next_claim_reset: RwLock::new(None),
+            kakera_power: RwLock::new(None),
+            idle_until: RwLock::new(None),
             activity_log: RwLock::new(VecDeque::with_capacity(100)),
+            event_counts: Default::default(),
             roll_history: RwLock::new(VecDeque::with_capacity(50)),
+            pending_claims: RwLock::new(HashMap::new()),
             channel_activity: RwLock::new(VecDeque::with_capacity(50)),
             user_id: AtomicU64::new(0),
             username: RwLock::new(None),
             paused: AtomicBool::new(false),
+            halted: AtomicBool::new(false),
+            captcha_alert: AtomicBool::new(false),
+            account_alert: AtomicBool::new(false),
+            owned_sync_requested: AtomicBool::new(false),
+            channel_access_failures: RwLock::new(HashMap::new()),
+            save_requested: Notify::new(),
             max_log_entries: 100,
             max_channel_activity: 50,
         })
@@ -145,6 +343,31 @@ impl Stats {
             kakera_collected: self.get_kakera(),
             rolls_executed: self.get_rolls_executed(),
             total_uptime_seconds: self.total_uptime_seconds.load(Ordering::Relaxed) + session_uptime,
+            kakera_received: self.get_kakera_received(),
+        }
+    }
+
+    pub fn stats_snapshot(&self) -> StatsSnapshot {
+        let saved = self.to_saved();
+        StatsSnapshot {
+            characters_rolled: saved.characters_rolled,
+            characters_claimed: saved.characters_claimed,
+            claim_retries: self.get_claim_retries(),
+            wishlist_matches: saved.wishlist_matches,
+            kakera_collected: saved.kakera_collected,
+            kakera_received: saved.kakera_received,
+            kakera_claimed_value: self.get_total_kakera_claimed_value(),
+            claims_without_value: self.claims_without_value.load(Ordering::Relaxed),
+            rolls_executed: saved.rolls_executed,
+            rolls_remaining: self.get_rolls_remaining(),
+            pending_actions: self.get_pending_actions(),
+            reconnects: self.get_reconnects(),
+            session_uptime_seconds: self.uptime().num_seconds().max(0) as u64,
+            total_uptime_seconds: saved.total_uptime_seconds,
+            events: EventType::all()
+                .into_iter()
+                .map(|event_type| (event_type.label().to_lowercase(), self.get_event_count(event_type)))
+                .collect(),
         }
     }
 
@@ -159,6 +382,20 @@ impl Stats {
 
     pub fn increment_claimed(&self) {
         self.characters_claimed.fetch_add(1, Ordering::Relaxed);
+        self.request_save();
+    }
+
+    pub fn decrement_claimed(&self) {
+        let _ = self.characters_claimed.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+        self.request_save();
+    }
+
+    pub fn increment_claim_retries(&self) {
+        self.claim_retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_claim_retries(&self) -> u64 {
+        self.claim_retries.load(Ordering::Relaxed)
     }
 
     pub fn increment_wishlist_matches(&self) {
@@ -169,8 +406,51 @@ impl Stats {
         self.kakera_collected.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn add_kakera_received(&self, amount: u64) {
+        self.kakera_received.fetch_add(amount, Ordering::Relaxed);
+        if amount >= LARGE_KAKERA_SAVE_THRESHOLD {
+            self.request_save();
+        }
+    }
+
+    pub fn refresh_claimed_value(&self, db: &Database) {
+        match db.get_claimed_value() {
+            Ok(value) => {
+                self.total_kakera_claimed_value.store(value.total, Ordering::Relaxed);
+                self.claims_without_value.store(value.unknown, Ordering::Relaxed);
+            }
+            Err(e) => warn!("Failed to load claimed kakera value: {}", e),
+        }
+    }
+
+    pub fn get_total_kakera_claimed_value(&self) -> u64 {
+        self.total_kakera_claimed_value.load(Ordering::Relaxed)
+    }
+
+    pub fn claimed_value_is_partial(&self) -> bool {
+        self.claims_without_value.load(Ordering::Relaxed) > 0
+    }
+
+    pub fn request_save(&self) {
+        self.save_requested.notify_one();
+    }
+
+    pub async fn save_requested(&self) {
+        self.save_requested.notified().await;
+    }
+
     pub fn increment_rolls_executed(&self) {
         self.rolls_executed.fetch_add(1, Ordering::Relaxed);
+        self.roll_window_if_expired();
+        self.window_rolls_used.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn roll_window_if_expired(&self) {
+        let end = self.roll_window_end.load(Ordering::Relaxed);
+        if end > 0 && Utc::now().timestamp() >= end {
+            self.window_rolls_used.store(0, Ordering::Relaxed);
+            self.roll_window_end.store(0, Ordering::Relaxed);
+        }
     }
 
     pub fn get_rolled(&self) -> u64 {
@@ -189,6 +469,10 @@ impl Stats {
         self.kakera_collected.load(Ordering::Relaxed)
     }
 
+    pub fn get_kakera_received(&self) -> u64 {
+        self.kakera_received.load(Ordering::Relaxed)
+    }
+
     pub fn get_rolls_executed(&self) -> u64 {
         self.rolls_executed.load(Ordering::Relaxed)
     }
@@ -207,12 +491,40 @@ impl Stats {
         *self.connection_status.read().await
     }
 
-    pub fn set_claim_available(&self, available: bool) {
-        self.claim_available.store(available, Ordering::Relaxed);
+    pub fn increment_reconnects(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_reconnects(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+
+    pub async fn set_last_disconnect_reason(&self, reason: String) {
+        *self.last_disconnect_reason.write().await = Some(reason);
+    }
+
+    pub async fn get_last_disconnect_reason(&self) -> Option<String> {
+        self.last_disconnect_reason.read().await.clone()
+    }
+
+    pub async fn get_connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            status: self.get_connection_status().await,
+            reconnects: self.get_reconnects(),
+            last_disconnect_reason: self.get_last_disconnect_reason().await,
+        }
+    }
+
+    pub fn record_claim_report(&self, available: bool, reset_at: Option<DateTime<Utc>>) {
+        self.claim_state.lock().unwrap().record_report(available, reset_at, Utc::now());
+    }
+
+    pub fn record_successful_claim(&self) {
+        self.claim_state.lock().unwrap().record_claim(Utc::now());
     }
 
     pub fn is_claim_available(&self) -> bool {
-        self.claim_available.load(Ordering::Relaxed)
+        self.claim_state.lock().unwrap().is_available(Utc::now())
     }
 
     pub fn set_rolls_remaining(&self, count: u64) {
@@ -223,14 +535,87 @@ impl Stats {
         self.rolls_remaining.load(Ordering::Relaxed)
     }
 
+    pub fn consume_rolls(&self, rolls: u64) -> u64 {
+        let previous = self.rolls_remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| Some(count.saturating_sub(rolls)))
+            .unwrap_or(0);
+        previous.saturating_sub(rolls)
+    }
+
+    pub fn set_pending_rolls(&self, count: u64) {
+        self.pending_rolls.store(count, Ordering::Relaxed);
+    }
+
+    pub fn add_queued_actions(&self, count: u64) {
+        self.queued_actions.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn finish_queued_actions(&self, count: u64) {
+        let _ = self.queued_actions.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |queued| {
+            Some(queued.saturating_sub(count))
+        });
+    }
+
+    pub fn get_pending_actions(&self) -> u64 {
+        self.pending_rolls.load(Ordering::Relaxed) + self.queued_actions.load(Ordering::Relaxed)
+    }
+
     pub async fn set_next_roll_reset(&self, reset_time: Option<DateTime<Utc>>) {
+        self.roll_window_if_expired();
+        if let Some(reset) = reset_time {
+            self.roll_window_end.store(reset.timestamp(), Ordering::Relaxed);
+        }
         *self.next_roll_reset.write().await = reset_time;
     }
 
+    pub fn get_window_rolls_used(&self) -> Option<u64> {
+        let end = self.roll_window_end.load(Ordering::Relaxed);
+        if end == 0 {
+            return None;
+        }
+        if Utc::now().timestamp() >= end {
+            return Some(0);
+        }
+        Some(self.window_rolls_used.load(Ordering::Relaxed))
+    }
+
     pub async fn get_next_roll_reset(&self) -> Option<DateTime<Utc>> {
         *self.next_roll_reset.read().await
     }
 
+    pub fn get_next_claim_reset(&self) -> Option<DateTime<Utc>> {
+        self.claim_state.lock().unwrap().available_at(Utc::now())
+    }
+
+    pub async fn set_kakera_power(&self, power: KakeraPower) {
+        *self.kakera_power.write().await = Some((power, Utc::now()));
+    }
+
+    pub async fn get_kakera_power(&self) -> Option<KakeraPower> {
+        self.kakera_power_at(Utc::now()).await
+    }
+
+    async fn kakera_power_at(&self, now: DateTime<Utc>) -> Option<KakeraPower> {
+        let ttl = chrono::Duration::minutes(KAKERA_POWER_TTL_MINUTES);
+        let mut stored = self.kakera_power.write().await;
+        match *stored {
+            Some((power, seen_at)) if now - seen_at < ttl => Some(power),
+            Some(_) => {
+                *stored = None;
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub async fn set_idle_until(&self, until: Option<DateTime<Utc>>) {
+        *self.idle_until.write().await = until;
+    }
+
+    pub async fn get_idle_until(&self) -> Option<DateTime<Utc>> {
+        *self.idle_until.read().await
+    }
+
     pub async fn format_time_until_roll_reset(&self) -> String {
         if let Some(reset_time) = self.get_next_roll_reset().await {
             let now = Utc::now();
@@ -271,6 +656,7 @@ impl Stats {
     }
 
     pub async fn log_event(&self, event_type: EventType, message: String) {
+        self.event_counts[event_type.index()].fetch_add(1, Ordering::Relaxed);
         let event = ActivityEvent {
             timestamp: Utc::now(),
             event_type,
@@ -284,6 +670,10 @@ impl Stats {
         log.push_back(event);
     }
 
+    pub fn get_event_count(&self, event_type: EventType) -> u64 {
+        self.event_counts[event_type.index()].load(Ordering::Relaxed)
+    }
+
     pub async fn add_roll(&self, entry: RollEntry) {
         let mut history = self.roll_history.write().await;
         if history.len() >= 50 {
@@ -300,6 +690,63 @@ impl Stats {
         self.roll_history.read().await.iter().cloned().collect()
     }
 
+    pub async fn top_rolled_series(&self, limit: usize) -> Vec<(String, u64)> {
+        let history = self.roll_history.read().await;
+        crate::utils::top_series(history.iter().map(|e| e.series.as_str()), limit)
+    }
+
+    pub async fn find_claimable_roll(&self, name: &str) -> Option<RollEntry> {
+        let query = name.trim().to_lowercase();
+        if query.is_empty() {
+            return None;
+        }
+
+        let history = self.roll_history.read().await;
+        let unclaimed = || history.iter().rev().filter(|e| !e.claimed);
+        unclaimed()
+            .find(|e| e.character_name.to_lowercase() == query)
+            .or_else(|| unclaimed().find(|e| e.character_name.to_lowercase().contains(&query)))
+            .cloned()
+    }
+
+    pub async fn mark_roll_claimed(&self, message_id: u64) {
+        let mut history = self.roll_history.write().await;
+        for entry in history.iter_mut().filter(|e| e.message_id == message_id) {
+            entry.claimed = true;
+        }
+    }
+
+    pub async fn add_pending_claim(&self, claim: PendingClaim) {
+        self.pending_claims.write().await.insert(claim.name.to_lowercase(), claim);
+    }
+
+    pub async fn take_pending_claim(&self, name: &str) -> Option<PendingClaim> {
+        self.pending_claims.write().await.remove(&name.to_lowercase())
+    }
+
+    pub async fn cancel_pending_claim(&self, name: &str, message_id: u64) -> bool {
+        let mut pending = self.pending_claims.write().await;
+        let key = name.to_lowercase();
+        if pending.get(&key).is_some_and(|claim| claim.message_id == message_id) {
+            pending.remove(&key);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub async fn expire_pending_claim(&self, name: &str, message_id: u64, confirm_timeout: std::time::Duration) {
+        let expire_after = if confirm_timeout.is_zero() {
+            std::time::Duration::from_secs(UNCONFIRMED_CLAIM_TTL_SECS)
+        } else {
+            confirm_timeout
+        };
+        tokio::time::sleep(expire_after).await;
+        if self.cancel_pending_claim(name, message_id).await && !confirm_timeout.is_zero() {
+            self.log_event(EventType::Warning, format!("Claim of {} unconfirmed — likely lost", name)).await;
+        }
+    }
+
     pub async fn add_channel_activity(&self, activity: ChannelActivity) {
         let mut feed = self.channel_activity.write().await;
         debug!("Adding channel activity, current size: {}, max: {}", feed.len(), self.max_channel_activity);
@@ -342,12 +789,104 @@ impl Stats {
 
     pub fn set_paused(&self, paused: bool) {
         self.paused.store(paused, Ordering::Relaxed);
+        if !paused {
+            self.captcha_alert.store(false, Ordering::Relaxed);
+        }
     }
 
     pub fn toggle_paused(&self) -> bool {
         let was_paused = self.paused.fetch_xor(true, Ordering::Relaxed);
+        if was_paused {
+            self.captcha_alert.store(false, Ordering::Relaxed);
+        }
         !was_paused
     }
+
+    pub fn pause_for_captcha(&self) -> bool {
+        self.paused.store(true, Ordering::Relaxed);
+        !self.captcha_alert.swap(true, Ordering::Relaxed)
+    }
+
+    pub fn has_captcha_alert(&self) -> bool {
+        self.captcha_alert.load(Ordering::Relaxed)
+    }
+
+    pub fn request_owned_sync(&self) {
+        self.owned_sync_requested.store(true, Ordering::Relaxed);
+    }
+
+    pub fn take_owned_sync_request(&self) -> bool {
+        self.owned_sync_requested.swap(false, Ordering::Relaxed)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
+    pub fn halt(&self, marker: &Path) -> std::io::Result<()> {
+        self.halted.store(true, Ordering::SeqCst);
+        std::fs::write(marker, Utc::now().to_rfc3339())
+    }
+
+    pub fn clear_halt(&self, marker: &Path) -> std::io::Result<()> {
+        match std::fs::remove_file(marker) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        self.halted.store(false, Ordering::SeqCst);
+        self.account_alert.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    pub fn halt_for_account_alert(&self, marker: &Path) -> std::io::Result<bool> {
+        self.halt(marker)?;
+        Ok(!self.account_alert.swap(true, Ordering::SeqCst))
+    }
+
+    pub fn has_account_alert(&self) -> bool {
+        self.account_alert.load(Ordering::SeqCst)
+    }
+
+    pub fn restore_halt(&self, marker: &Path) -> bool {
+        let halted = marker.exists();
+        self.halted.store(halted, Ordering::SeqCst);
+        halted
+    }
+
+    pub async fn record_channel_access_failure(&self, channel_id: u64) -> bool {
+        let mut failures = self.channel_access_failures.write().await;
+        let count = failures.entry(channel_id).or_insert(0);
+        *count += 1;
+        *count == CHANNEL_ACCESS_FAILURE_LIMIT
+    }
+
+    pub async fn clear_channel_access_failures(&self, channel_id: u64) {
+        let mut failures = self.channel_access_failures.write().await;
+        failures.remove(&channel_id);
+    }
+
+    pub async fn is_channel_accessible(&self, channel_id: u64) -> bool {
+        let failures = self.channel_access_failures.read().await;
+        failures.get(&channel_id).is_none_or(|&count| count < CHANNEL_ACCESS_FAILURE_LIMIT)
+    }
+
+    pub async fn get_inaccessible_channels(&self) -> Vec<u64> {
+        let failures = self.channel_access_failures.read().await;
+        let mut channels: Vec<u64> = failures
+            .iter()
+            .filter(|(_, &count)| count >= CHANNEL_ACCESS_FAILURE_LIMIT)
+            .map(|(&channel_id, _)| channel_id)
+            .collect();
+        channels.sort_unstable();
+        channels
+    }
+
+    pub async fn reset_channel_access(&self) -> usize {
+        let inaccessible = self.get_inaccessible_channels().await.len();
+        self.channel_access_failures.write().await.clear();
+        inaccessible
+    }
 }
 
 impl Default for Stats {
@@ -356,23 +895,151 @@ impl Default for Stats {
             start_time: Utc::now(),
             characters_rolled: AtomicU64::new(0),
             characters_claimed: AtomicU64::new(0),
+            claim_retries: AtomicU64::new(0),
             wishlist_matches: AtomicU64::new(0),
             kakera_collected: AtomicU64::new(0),
+            kakera_received: AtomicU64::new(0),
+            total_kakera_claimed_value: AtomicU64::new(0),
+            claims_without_value: AtomicU64::new(0),
             rolls_executed: AtomicU64::new(0),
             total_uptime_seconds: AtomicU64::new(0),
             connection_status: RwLock::new(ConnectionStatus::Disconnected),
-            claim_available: AtomicBool::new(true),
+            reconnects: AtomicU64::new(0),
+            last_disconnect_reason: RwLock::new(None),
+            claim_state: Mutex::new(ClaimState::default()),
             rolls_remaining: AtomicU64::new(0),
+            pending_rolls: AtomicU64::new(0),
+            queued_actions: AtomicU64::new(0),
+            window_rolls_used: AtomicU64::new(0),
+            roll_window_end: AtomicI64::new(0),
             next_roll_reset: RwLock::new(None),
-            next_claim_reset: RwLock::new(None),
+            kakera_power: RwLock::new(None),
+            idle_until: RwLock::new(None),
             activity_log: RwLock::new(VecDeque::with_capacity(100)),
+            event_counts: Default::default(),
             roll_history: RwLock::new(VecDeque::with_capacity(50)),
+            pending_claims: RwLock::new(HashMap::new()),
             channel_activity: RwLock::new(VecDeque::with_capacity(50)),
             user_id: AtomicU64::new(0),
             username: RwLock::new(None),
             paused: AtomicBool::new(false),
+            halted: AtomicBool::new(false),
+            captcha_alert: AtomicBool::new(false),
+            account_alert: AtomicBool::new(false),
+            owned_sync_requested: AtomicBool::new(false),
+            channel_access_failures: RwLock::new(HashMap::new()),
+            save_requested: Notify::new(),
             max_log_entries: 100,
             max_channel_activity: 50,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_significant_events_request_save() {
+        let stats = Stats::new();
+        let wait = tokio::time::Duration::from_millis(50);
+
+        stats.add_kakera_received(LARGE_KAKERA_SAVE_THRESHOLD - 1);
+        assert!(tokio::time::timeout(wait, stats.save_requested()).await.is_err());
+
+        stats.add_kakera_received(LARGE_KAKERA_SAVE_THRESHOLD);
+        assert!(tokio::time::timeout(wait, stats.save_requested()).await.is_ok());
+
+        stats.increment_claimed();
+        assert!(tokio::time::timeout(wait, stats.save_requested()).await.is_ok());
+    }
+
+    #[test]
+    fn test_divorce_decrements_claimed_without_underflow() {
+        let stats = Stats::new();
+        stats.increment_claimed();
+        stats.decrement_claimed();
+        assert_eq!(stats.get_claimed(), 0);
+        stats.decrement_claimed();
+        assert_eq!(stats.get_claimed(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_kakera_power_expires() {
+        let stats = Stats::new();
+        let power = KakeraPower { power: 10, can_react: false };
+        stats.set_kakera_power(power).await;
+        assert_eq!(stats.get_kakera_power().await, Some(power));
+
+        let later = Utc::now() + chrono::Duration::minutes(KAKERA_POWER_TTL_MINUTES);
+        assert_eq!(stats.kakera_power_at(later).await, None);
+        assert_eq!(stats.get_kakera_power().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_event_counts_survive_log_rotation() {
+        let stats = Stats::new();
+        for i in 0..150 {
+            stats.log_event(EventType::Error, format!("error {}", i)).await;
+        }
+        stats.log_event(EventType::Roll, "rolled".to_string()).await;
+
+        assert_eq!(stats.get_activity_log().await.len(), 100);
+        assert_eq!(stats.get_event_count(EventType::Error), 150);
+        assert_eq!(stats.get_event_count(EventType::Warning), 0);
+
+        let snapshot = stats.stats_snapshot();
+        assert_eq!(snapshot.events["error"], 150);
+        assert_eq!(snapshot.events["roll"], 1);
+        assert_eq!(snapshot.events.len(), EventType::all().len());
+    }
+
+    #[tokio::test]
+    async fn test_channel_marked_inaccessible_after_repeated_failures() {
+        let stats = Stats::new();
+
+        for _ in 1..CHANNEL_ACCESS_FAILURE_LIMIT {
+            assert!(!stats.record_channel_access_failure(42).await);
+        }
+        assert!(stats.is_channel_accessible(42).await);
+
+        assert!(stats.record_channel_access_failure(42).await);
+        assert!(!stats.record_channel_access_failure(42).await);
+        assert!(!stats.is_channel_accessible(42).await);
+        assert!(stats.is_channel_accessible(7).await);
+        assert_eq!(stats.get_inaccessible_channels().await, vec![42]);
+
+        assert_eq!(stats.reset_channel_access().await, 1);
+        assert!(stats.is_channel_accessible(42).await);
+    }
+
+    #[test]
+    fn test_halt_survives_restart_until_cleared() {
+        let marker = std::env::temp_dir().join(format!("mudae-halt-test-{}", std::process::id()));
+        let stats = Stats::new();
+
+        stats.halt(&marker).unwrap();
+        assert!(stats.is_halted());
+
+        let restarted = Stats::new();
+        assert!(restarted.restore_halt(&marker));
+        assert!(restarted.is_halted());
+
+        restarted.clear_halt(&marker).unwrap();
+        assert!(!restarted.is_halted());
+        assert!(!marker.exists());
+        assert!(!Stats::new().restore_halt(&marker));
+    }
+
+    #[tokio::test]
+    async fn test_successful_send_clears_failures() {
+        let stats = Stats::new();
+
+        stats.record_channel_access_failure(42).await;
+        stats.record_channel_access_failure(42).await;
+        stats.clear_channel_access_failures(42).await;
+        stats.record_channel_access_failure(42).await;
+
+        assert!(stats.is_channel_accessible(42).await);
+    }
+}
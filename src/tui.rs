@@ -1,12 +1,20 @@
-use crate::config::Config;
-use crate::database::{ChannelInfo, Database};
+use crate::commands::CommandExecutor;
+use crate::config::{Config, DelayDistribution, DisplayTimezone, RollSpread, ScheduledCommand, SessionLimitAction, MIN_TUI_TICK_MS};
+use crate::database::{ChannelInfo, ClaimRecord, Database};
+use crate::glyphs;
+use crate::logging::{self, LogHandle, LogLevel};
+use crate::parser::{Gender, MudaeParser};
+use crate::rules::ClaimRules;
 use crate::search::{SearchRequest, SearchRequestSender, SearchResult};
-use crate::stats::{ChannelActivity, ConnectionStatus, EventType, Stats};
-use crate::wishlist::{WishedCharacter, WishlistManager};
-use chrono::Utc;
+use crate::stats::{ChannelActivity, ConnectionInfo, ConnectionStatus, EventType, KakeraPower, Stats};
+use crate::utils::{format_duration, truncate_string, Humanizer};
+use crate::verifier::WishlistVerifier;
+use crate::wishlist::{ImportEntry, ImportStatus, WishedCharacter, WishlistManager};
+use chrono::{DateTime, Local, Utc};
 use tokio::sync::oneshot;
 use anyhow::Result;
 use crossterm::{
+    cursor,
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -16,38 +24,236 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Borders, Clear, List, ListItem, Paragraph},
     Frame, Terminal,
 };
+use std::collections::HashMap;
 use std::io;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::watch;
 
+const SERIES_STATS_LIMIT: usize = 10;
+const CURSOR_BLINK_MS: u64 = 500;
+const LIVE_REFRESH_MS: u64 = 1000;
+const MAX_NAME_DISPLAY_LEN: usize = 40;
+const MAX_SERIES_DISPLAY_LEN: usize = 40;
+const CHANNEL_REFRESH_SPACING_MS: u64 = 1500;
+
 #[derive(Clone, PartialEq)]
 enum View {
     Dashboard,
     Settings,
     EditToken,
+    EditTokenPassphrase,
     EditChannels,
     EditRollCommands,
+    EditRollYields,
     EditCooldown,
+    EditDefaultRolls,
+    EditReconnectCommands,
+    EditScheduledCommands,
+    EditDailyCommands,
+    EditClaimWeights,
+    EditVerifyDelay,
+    EditClaimRetries,
+    EditClaimConfirmTimeout,
+    EditClaimGrace,
+    EditFailureLimit,
+    EditAuthErrorHalt,
+    EditRollDelays,
+    EditInterChannelDelay,
+    EditSessionLimit,
+    EditCatchupMessages,
+    EditStatsSaveInterval,
+    EditChannelRefresh,
+    EditTuiTick,
+    EditKakeraPowerMin,
+    EditAutoDivorceMaxKakera,
+    EditAutoDivorceCommands,
+    EditClaimRules,
+    EditClaimKakeraMin,
+    EditClaimKakeraMax,
+    EditHaltKey,
+    EditRemoteControlUsers,
+    EditMarkWishedEmoji,
+    EditClaimEmoji,
+    ConfirmClearHalt,
+    ConfirmDivorce,
+    ClaimByName,
+    Claims,
+    SeriesStats,
+    Logs,
     Wishlist,
+    ImportWishlist,
+    ImportPreview,
     SearchCharacter,
     ConfirmCharacter(SearchResult),
 }
 
+impl View {
+    fn accepts_text(&self) -> bool {
+        matches!(
+            self,
+            View::EditToken
+                | View::EditTokenPassphrase
+                | View::EditChannels
+                | View::EditRollCommands
+                | View::EditRollYields
+                | View::EditCooldown
+                | View::EditDefaultRolls
+                | View::EditReconnectCommands
+                | View::EditScheduledCommands
+                | View::EditDailyCommands
+                | View::EditClaimWeights
+                | View::EditVerifyDelay
+                | View::EditClaimRetries
+                | View::EditClaimConfirmTimeout
+                | View::EditClaimGrace
+                | View::EditFailureLimit
+                | View::EditAuthErrorHalt
+                | View::EditRollDelays
+                | View::EditInterChannelDelay
+                | View::EditSessionLimit
+                | View::EditCatchupMessages
+                | View::EditStatsSaveInterval
+                | View::EditChannelRefresh
+                | View::EditTuiTick
+                | View::EditKakeraPowerMin
+                | View::EditAutoDivorceMaxKakera
+                | View::EditAutoDivorceCommands
+                | View::EditClaimRules
+                | View::EditClaimKakeraMin
+                | View::EditClaimKakeraMax
+                | View::EditHaltKey
+                | View::EditRemoteControlUsers
+                | View::EditMarkWishedEmoji
+                | View::EditClaimEmoji
+                | View::ClaimByName
+                | View::ImportWishlist
+                | View::SearchCharacter
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RunState {
+    Running,
+    Idle(DateTime<Utc>),
+    Paused,
+    Captcha,
+    Halted,
+    AccountAlert,
+}
+
+#[derive(Clone, Default)]
+struct LogFilter {
+    hidden_types: Vec<EventType>,
+    search: String,
+    searching: bool,
+}
+
+impl LogFilter {
+    fn toggle(&mut self, event_type: EventType) {
+        if let Some(pos) = self.hidden_types.iter().position(|t| *t == event_type) {
+            self.hidden_types.remove(pos);
+        } else {
+            self.hidden_types.push(event_type);
+        }
+    }
+
+    fn apply<'a>(&self, events: &'a [crate::stats::ActivityEvent]) -> Vec<&'a crate::stats::ActivityEvent> {
+        let query = self.search.to_lowercase();
+        events
+            .iter()
+            .filter(|event| !self.hidden_types.contains(&event.event_type))
+            .filter(|event| query.is_empty() || event.message.to_lowercase().contains(&query))
+            .collect()
+    }
+}
+
+const DASHBOARD_KEYS: [char; 13] = ['q', 's', 'w', 'p', 'r', 'a', 'v', 'l', 'c', 'u', 'o', 't', 'm'];
+
+fn parse_halt_key(input: &str) -> Result<KeyCode, String> {
+    let input = input.trim();
+    let mut chars = input.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_whitespace() => KeyCode::Char(c.to_ascii_lowercase()),
+        (Some('F' | 'f'), Some(_)) => match input[1..].parse::<u8>() {
+            Ok(n @ 1..=12) => KeyCode::F(n),
+            _ => return Err("Enter a single character or F1-F12".to_string()),
+        },
+        _ => return Err("Enter a single character or F1-F12".to_string()),
+    };
+    match key {
+        KeyCode::Char(c) if DASHBOARD_KEYS.contains(&c) => {
+            Err(format!("'{}' is already bound on the dashboard, pick another key", c))
+        }
+        key => Ok(key),
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum SettingsItem {
     Token,
     Channels,
     RollCommands,
+    RollYields,
     Cooldown,
+    DefaultRolls,
+    RollSpread,
+    DisplayTimezone,
+    MudaeLanguage,
+    ReconnectCommands,
+    ScheduledCommands,
+    DailyCommands,
+    ClaimWeights,
+    ClaimRules,
+    VerifyDelay,
+    ClaimRetries,
+    ClaimConfirmTimeout,
+    ClaimGrace,
+    FailureLimit,
+    AuthErrorHalt,
+    RollDelays,
+    InterChannelDelay,
+    DelayDistribution,
+    SessionLimit,
+    SessionLimitAction,
+    CatchupMessages,
+    StatsSaveInterval,
+    ChannelRefresh,
+    TuiTick,
+    KakeraPowerMin,
+    HaltKey,
+    RemoteControlUsers,
+    MarkWishedEmoji,
+    ClaimEmoji,
+    GenderFilter,
+    ClaimKakeraMin,
+    ClaimKakeraMax,
     AutoRoll,
     AutoKakera,
     AutoDaily,
+    DailyKakeraReact,
     Wishlist,
+    TrustMudaeWish,
+    ShuffleChannels,
+    IncludeThreads,
+    IncludeDms,
+    TrackSeenCharacters,
     FuzzyMatch,
+    FuzzyDedup,
+    AutoIdle,
+    StrictWishlistLoad,
+    AutoVerifyOnStart,
+    ShowUserMessages,
+    AsciiMode,
+    EncryptToken,
+    AttemptClaimOnBelongsTo,
+    AutoDivorceNonWished,
+    AutoDivorceMaxKakera,
+    AutoDivorceCommands,
 }
 
 impl SettingsItem {
@@ -56,12 +262,62 @@ impl SettingsItem {
             SettingsItem::Token,
             SettingsItem::Channels,
             SettingsItem::RollCommands,
+            SettingsItem::RollYields,
             SettingsItem::Cooldown,
+            SettingsItem::DefaultRolls,
+            SettingsItem::RollSpread,
+            SettingsItem::DisplayTimezone,
+            SettingsItem::MudaeLanguage,
+            SettingsItem::ReconnectCommands,
+            SettingsItem::ScheduledCommands,
+            SettingsItem::DailyCommands,
+            SettingsItem::ClaimWeights,
+            SettingsItem::ClaimRules,
+            SettingsItem::VerifyDelay,
+            SettingsItem::ClaimRetries,
+            SettingsItem::ClaimConfirmTimeout,
+            SettingsItem::ClaimGrace,
+            SettingsItem::FailureLimit,
+            SettingsItem::AuthErrorHalt,
+            SettingsItem::RollDelays,
+            SettingsItem::InterChannelDelay,
+            SettingsItem::DelayDistribution,
+            SettingsItem::SessionLimit,
+            SettingsItem::SessionLimitAction,
+            SettingsItem::CatchupMessages,
+            SettingsItem::StatsSaveInterval,
+            SettingsItem::ChannelRefresh,
+            SettingsItem::TuiTick,
+            SettingsItem::KakeraPowerMin,
+            SettingsItem::HaltKey,
+            SettingsItem::RemoteControlUsers,
+            SettingsItem::MarkWishedEmoji,
+            SettingsItem::ClaimEmoji,
+            SettingsItem::GenderFilter,
+            SettingsItem::ClaimKakeraMin,
+            SettingsItem::ClaimKakeraMax,
             SettingsItem::AutoRoll,
             SettingsItem::AutoKakera,
             SettingsItem::AutoDaily,
+            SettingsItem::DailyKakeraReact,
             SettingsItem::Wishlist,
+            SettingsItem::TrustMudaeWish,
+            SettingsItem::ShuffleChannels,
+            SettingsItem::IncludeThreads,
+            SettingsItem::IncludeDms,
+            SettingsItem::TrackSeenCharacters,
             SettingsItem::FuzzyMatch,
+            SettingsItem::FuzzyDedup,
+            SettingsItem::AutoIdle,
+            SettingsItem::StrictWishlistLoad,
+            SettingsItem::AutoVerifyOnStart,
+            SettingsItem::ShowUserMessages,
+            SettingsItem::AsciiMode,
+            SettingsItem::EncryptToken,
+            SettingsItem::AttemptClaimOnBelongsTo,
+            SettingsItem::AutoDivorceNonWished,
+            SettingsItem::AutoDivorceMaxKakera,
+            SettingsItem::AutoDivorceCommands,
         ]
     }
 
@@ -70,12 +326,62 @@ impl SettingsItem {
             SettingsItem::Token => "Discord Token",
             SettingsItem::Channels => "Channel IDs",
             SettingsItem::RollCommands => "Roll Commands",
+            SettingsItem::RollYields => "Roll Yields",
             SettingsItem::Cooldown => "Roll Cooldown (seconds)",
+            SettingsItem::DefaultRolls => "Rolls per Reset",
+            SettingsItem::RollSpread => "Roll Spread",
+            SettingsItem::DisplayTimezone => "Time Display",
+            SettingsItem::MudaeLanguage => "Mudae Language",
+            SettingsItem::ReconnectCommands => "Reconnect Commands",
+            SettingsItem::ScheduledCommands => "Scheduled Commands",
+            SettingsItem::DailyCommands => "Daily Commands",
+            SettingsItem::ClaimWeights => "Claim Weights",
+            SettingsItem::ClaimRules => "Claim Rules",
+            SettingsItem::VerifyDelay => "Verify Delay (ms)",
+            SettingsItem::ClaimRetries => "Claim Retries",
+            SettingsItem::ClaimConfirmTimeout => "Claim Confirm Timeout",
+            SettingsItem::ClaimGrace => "Claim Grace",
+            SettingsItem::FailureLimit => "Failure Limit",
+            SettingsItem::AuthErrorHalt => "Auth Error Halt",
+            SettingsItem::RollDelays => "First Roll Delays",
+            SettingsItem::InterChannelDelay => "Channel Switch Delay",
+            SettingsItem::DelayDistribution => "Delay Distribution",
+            SettingsItem::SessionLimit => "Max Session Hours",
+            SettingsItem::SessionLimitAction => "At Session Limit",
+            SettingsItem::CatchupMessages => "Startup Catch-up",
+            SettingsItem::StatsSaveInterval => "Stats Save Interval",
+            SettingsItem::ChannelRefresh => "Channel Name Refresh",
+            SettingsItem::TuiTick => "Screen Tick",
+            SettingsItem::KakeraPowerMin => "Kakera Power Min (%)",
+            SettingsItem::HaltKey => "Halt Key",
+            SettingsItem::RemoteControlUsers => "Remote Control Users",
+            SettingsItem::MarkWishedEmoji => "Mark Missed Wishes",
+            SettingsItem::ClaimEmoji => "Fallback Claim Emoji",
+            SettingsItem::GenderFilter => "Claim Gender Filter",
+            SettingsItem::ClaimKakeraMin => "Claim Kakera Min",
+            SettingsItem::ClaimKakeraMax => "Claim Kakera Max",
             SettingsItem::AutoRoll => "Auto Roll",
             SettingsItem::AutoKakera => "Auto Kakera React",
             SettingsItem::AutoDaily => "Auto Daily",
+            SettingsItem::DailyKakeraReact => "Daily Kakera by React",
+            SettingsItem::TrustMudaeWish => "Trust Mudae Wishes",
+            SettingsItem::ShuffleChannels => "Shuffle Channels",
+            SettingsItem::IncludeThreads => "Include Threads",
+            SettingsItem::IncludeDms => "Include Mudae DMs",
+            SettingsItem::TrackSeenCharacters => "Track Seen Characters",
             SettingsItem::Wishlist => "Wishlist Enabled",
             SettingsItem::FuzzyMatch => "Fuzzy Match",
+            SettingsItem::FuzzyDedup => "Fuzzy Dedup",
+            SettingsItem::AutoIdle => "Auto Idle",
+            SettingsItem::StrictWishlistLoad => "Strict Wishlist Load",
+            SettingsItem::AutoVerifyOnStart => "Verify on Start",
+            SettingsItem::ShowUserMessages => "Show User Messages",
+            SettingsItem::AsciiMode => "ASCII Mode",
+            SettingsItem::EncryptToken => "Encrypt Stored Token",
+            SettingsItem::AttemptClaimOnBelongsTo => "Claim Owned if Button",
+            SettingsItem::AutoDivorceNonWished => "⚠ Auto Divorce Fuzzy",
+            SettingsItem::AutoDivorceMaxKakera => "Auto Divorce Below (ka)",
+            SettingsItem::AutoDivorceCommands => "Divorce Commands",
         }
     }
 
@@ -85,8 +391,23 @@ impl SettingsItem {
             SettingsItem::AutoRoll
                 | SettingsItem::AutoKakera
                 | SettingsItem::AutoDaily
+                | SettingsItem::DailyKakeraReact
+                | SettingsItem::TrustMudaeWish
+                | SettingsItem::ShuffleChannels
+                | SettingsItem::IncludeThreads
+                | SettingsItem::IncludeDms
+                | SettingsItem::TrackSeenCharacters
                 | SettingsItem::Wishlist
                 | SettingsItem::FuzzyMatch
+                | SettingsItem::FuzzyDedup
+                | SettingsItem::AutoIdle
+                | SettingsItem::StrictWishlistLoad
+                | SettingsItem::AutoVerifyOnStart
+                | SettingsItem::ShowUserMessages
+                | SettingsItem::AsciiMode
+                | SettingsItem::EncryptToken
+                | SettingsItem::AttemptClaimOnBelongsTo
+                | SettingsItem::AutoDivorceNonWished
         )
     }
 }
@@ -97,20 +418,41 @@ pub struct Tui {
     config: Config,
     db: Arc<Database>,
     wishlist: Arc<WishlistManager>,
+    wishlist_verifier: Arc<WishlistVerifier>,
     search_tx: SearchRequestSender,
     shutdown_rx: watch::Receiver<bool>,
     channel_infos: Vec<ChannelInfo>,
     client: Option<Arc<crate::client::DiscordClient>>,
+    executor: Arc<CommandExecutor>,
+    log_handle: LogHandle,
+    log_level: LogLevel,
+    halt_key: Option<KeyCode>,
+    log_filter: LogFilter,
+    log_scroll: usize,
     scroll_offset: u16,
     view: View,
     input_buffer: String,
     settings_cursor: usize,
     wishlist_cursor: usize,
+    import_preview: Vec<(ImportEntry, bool)>,
+    import_cursor: usize,
+    claims: Vec<ClaimRecord>,
+    claims_cursor: usize,
+    claims_per_series: Vec<(String, u64)>,
     cursor_visible: bool,
+    last_blink: Instant,
+    last_draw: Instant,
+    dirty: bool,
     message: Option<(String, bool)>,
     searching: bool,
     pending_search: Option<(String, oneshot::Receiver<Option<SearchResult>>)>,
-    pending_channel_refresh: Option<oneshot::Receiver<()>>,
+    pending_channel_refresh: Option<oneshot::Receiver<Vec<u64>>>,
+    last_channel_refresh: Instant,
+    periodic_channel_refresh: bool,
+    pending_divorce: Option<ClaimRecord>,
+    unreachable_channels: Vec<u64>,
+    token_fingerprint: String,
+    pending_token: Option<String>,
 }
 
 impl Tui {
@@ -119,16 +461,23 @@ impl Tui {
         config: Config,
         db: Arc<Database>,
         wishlist: Arc<WishlistManager>,
+        wishlist_verifier: Arc<WishlistVerifier>,
         search_tx: SearchRequestSender,
         shutdown_rx: watch::Receiver<bool>,
         channel_infos: Vec<ChannelInfo>,
         client: Option<Arc<crate::client::DiscordClient>>,
+        executor: Arc<CommandExecutor>,
+        log_handle: LogHandle,
+        log_level: LogLevel,
     ) -> Result<Self> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
+        let halt_key = parse_halt_key(&config.halt_key).ok();
+        glyphs::set_ascii_mode(config.ascii_mode);
+        let token_fingerprint = Self::load_token_fingerprint(&db);
 
         Ok(Self {
             terminal,
@@ -136,72 +485,196 @@ impl Tui {
             config,
             db,
             wishlist,
+            wishlist_verifier,
             search_tx,
             shutdown_rx,
             channel_infos,
             client,
+            executor,
+            log_handle,
+            log_level,
+            halt_key,
+            log_filter: LogFilter::default(),
+            log_scroll: 0,
             scroll_offset: 0,
             view: View::Dashboard,
             input_buffer: String::new(),
             settings_cursor: 0,
             wishlist_cursor: 0,
+            import_preview: Vec::new(),
+            import_cursor: 0,
+            claims: Vec::new(),
+            claims_cursor: 0,
+            claims_per_series: Vec::new(),
             cursor_visible: true,
+            last_blink: Instant::now(),
+            last_draw: Instant::now(),
+            dirty: true,
             message: None,
             searching: false,
             pending_search: None,
             pending_channel_refresh: None,
+            last_channel_refresh: Instant::now(),
+            periodic_channel_refresh: false,
+            pending_divorce: None,
+            unreachable_channels: Vec::new(),
+            token_fingerprint,
+            pending_token: None,
         })
     }
 
     pub async fn run(&mut self) -> Result<()> {
-        let mut tick = tokio::time::interval(Duration::from_millis(100));
-        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let tick = Duration::from_millis(self.config.tui_tick_ms.max(MIN_TUI_TICK_MS));
+        let mut last_tick = Instant::now();
+        self.start_channel_refresh(self.channel_infos.iter().map(|c| c.id).collect());
+        if !self.config.ascii_mode && !glyphs::terminal_supports_unicode() {
+            self.stats
+                .log_event(
+                    EventType::Warning,
+                    "Terminal may not support Unicode symbols; enable ASCII Mode in Settings".to_string(),
+                )
+                .await;
+        }
 
         loop {
             if *self.shutdown_rx.borrow() {
                 break;
             }
 
+            let had_pending = self.pending_search.is_some() || self.pending_channel_refresh.is_some();
             self.check_pending_search().await;
             self.check_pending_channel_refresh().await;
+            if had_pending && self.pending_search.is_none() && self.pending_channel_refresh.is_none() {
+                self.dirty = true;
+            }
+
+            if last_tick.elapsed() >= tick {
+                last_tick = Instant::now();
+                self.on_tick().await?;
+            }
 
-            tokio::select! {
-                _ = tick.tick() => {
-                    self.draw().await?;
-                    self.cursor_visible = !self.cursor_visible;
+            let timeout = tick.saturating_sub(last_tick.elapsed());
+            let result = tokio::task::spawn_blocking(move || {
+                if event::poll(timeout).unwrap_or(false) {
+                    event::read().ok()
+                } else {
+                    None
                 }
-                result = tokio::task::spawn_blocking(|| {
-                    if event::poll(Duration::from_millis(0)).unwrap_or(false) {
-                        event::read().ok()
-                    } else {
-                        None
-                    }
-                }) => {
-                    if let Ok(Some(Event::Key(key))) = result {
-                        if key.kind == KeyEventKind::Press {
-                            let should_quit = match &self.view {
-                                View::Dashboard => self.handle_dashboard_input(key.code),
-                                View::Settings => { self.handle_settings_input(key.code); false }
-                                View::EditToken => { self.handle_edit_token_input(key.code); false }
-                                View::EditChannels => { self.handle_edit_channels_input(key.code); false }
-                                View::EditRollCommands => { self.handle_edit_roll_commands_input(key.code); false }
-                                View::EditCooldown => { self.handle_edit_cooldown_input(key.code); false }
-                                View::Wishlist => { self.handle_wishlist_input(key.code).await; false }
-                                View::SearchCharacter => { self.handle_search_input(key.code).await; false }
-                                View::ConfirmCharacter(_) => { self.handle_confirm_input(key.code).await; false }
-                            };
-                            if should_quit {
-                                break;
+            }).await;
+
+            match result {
+                Ok(Some(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                    self.dirty = true;
+                    let is_halt_key = Some(key.code) == self.halt_key
+                        && (!matches!(key.code, KeyCode::Char(_)) || self.view == View::Dashboard);
+                    if is_halt_key && self.view != View::ConfirmClearHalt {
+                        self.handle_halt_key();
+                        continue;
+                    }
+                    let should_quit = match &self.view {
+                        View::Dashboard => self.handle_dashboard_input(key.code),
+                        View::Settings => { self.handle_settings_input(key.code); false }
+                        View::EditToken => { self.handle_edit_token_input(key.code); false }
+                        View::EditTokenPassphrase => { self.handle_edit_token_passphrase_input(key.code); false }
+                        View::EditChannels => { self.handle_edit_channels_input(key.code); false }
+                        View::EditRollCommands => { self.handle_edit_roll_commands_input(key.code); false }
+                        View::EditRollYields => { self.handle_edit_roll_yields_input(key.code); false }
+                        View::EditCooldown => { self.handle_edit_cooldown_input(key.code); false }
+                        View::EditDefaultRolls => { self.handle_edit_default_rolls_input(key.code); false }
+                        View::EditClaimKakeraMin => { self.handle_edit_claim_kakera_range_input(key.code, false); false }
+                        View::EditClaimKakeraMax => { self.handle_edit_claim_kakera_range_input(key.code, true); false }
+                        View::EditReconnectCommands => { self.handle_edit_reconnect_commands_input(key.code); false }
+                        View::EditScheduledCommands => { self.handle_edit_scheduled_commands_input(key.code); false }
+                        View::EditDailyCommands => { self.handle_edit_daily_commands_input(key.code); false }
+                        View::EditClaimWeights => { self.handle_edit_claim_weights_input(key.code); false }
+                        View::EditVerifyDelay => { self.handle_edit_verify_delay_input(key.code); false }
+                        View::EditClaimRetries => { self.handle_edit_claim_retries_input(key.code); false }
+                        View::EditClaimConfirmTimeout => { self.handle_edit_claim_confirm_timeout_input(key.code); false }
+                        View::EditClaimGrace => { self.handle_edit_claim_grace_input(key.code); false }
+                        View::EditFailureLimit => { self.handle_edit_failure_limit_input(key.code); false }
+                        View::EditAuthErrorHalt => { self.handle_edit_auth_error_halt_input(key.code); false }
+                        View::EditRollDelays => { self.handle_edit_roll_delays_input(key.code); false }
+                        View::EditInterChannelDelay => { self.handle_edit_inter_channel_delay_input(key.code); false }
+                        View::EditSessionLimit => { self.handle_edit_session_limit_input(key.code); false }
+                        View::EditCatchupMessages => { self.handle_edit_catchup_messages_input(key.code); false }
+                        View::EditStatsSaveInterval => { self.handle_edit_stats_save_interval_input(key.code); false }
+                        View::EditChannelRefresh => { self.handle_edit_channel_refresh_input(key.code); false }
+                        View::EditTuiTick => { self.handle_edit_tui_tick_input(key.code); false }
+                        View::EditKakeraPowerMin => { self.handle_edit_kakera_power_min_input(key.code); false }
+                        View::EditAutoDivorceMaxKakera => { self.handle_edit_auto_divorce_max_kakera_input(key.code); false }
+                        View::EditAutoDivorceCommands => { self.handle_edit_auto_divorce_commands_input(key.code); false }
+                        View::EditClaimRules => { self.handle_edit_claim_rules_input(key.code); false }
+                        View::EditHaltKey => { self.handle_edit_halt_key_input(key.code); false }
+                        View::EditRemoteControlUsers => { self.handle_edit_remote_control_users_input(key.code); false }
+                        View::EditMarkWishedEmoji => { self.handle_edit_mark_wished_emoji_input(key.code); false }
+                        View::EditClaimEmoji => { self.handle_edit_claim_emoji_input(key.code); false }
+                        View::ConfirmClearHalt => { self.handle_confirm_clear_halt_input(key.code); false }
+                        View::ConfirmDivorce => { self.handle_confirm_divorce_input(key.code); false }
+                        View::ClaimByName => { self.handle_claim_by_name_input(key.code).await; false }
+                        View::Claims => { self.handle_claims_input(key.code); false }
+                        View::SeriesStats => {
+                            if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                                self.view = View::Dashboard;
                             }
+                            false
                         }
+                        View::Logs => { self.handle_logs_input(key.code); false }
+                        View::Wishlist => { self.handle_wishlist_input(key.code).await; false }
+                        View::ImportWishlist => { self.handle_import_wishlist_input(key.code).await; false }
+                        View::ImportPreview => { self.handle_import_preview_input(key.code).await; false }
+                        View::SearchCharacter => { self.handle_search_input(key.code).await; false }
+                        View::ConfirmCharacter(_) => { self.handle_confirm_input(key.code).await; false }
+                    };
+                    if should_quit {
+                        break;
                     }
                 }
+                Ok(Some(Event::Resize(_, _))) => {
+                    self.terminal.clear()?;
+                    self.dirty = true;
+                }
+                _ => {}
             }
         }
 
         Ok(())
     }
 
+    fn is_typing(&self) -> bool {
+        self.view.accepts_text() || (self.view == View::Logs && self.log_filter.searching)
+    }
+
+    async fn on_tick(&mut self) -> Result<()> {
+        if !self.is_typing() {
+            self.cursor_visible = true;
+        } else if self.last_blink.elapsed() >= Duration::from_millis(CURSOR_BLINK_MS) {
+            self.cursor_visible = !self.cursor_visible;
+            self.last_blink = Instant::now();
+            self.dirty = true;
+        }
+
+        if self.dirty || self.last_draw.elapsed() >= Duration::from_millis(LIVE_REFRESH_MS) {
+            self.draw().await?;
+            self.dirty = false;
+            self.last_draw = Instant::now();
+        }
+
+        self.refresh_channel_names_if_due();
+        Ok(())
+    }
+
+    fn refresh_channel_names_if_due(&mut self) {
+        let minutes = self.config.channel_refresh_minutes;
+        if minutes == 0
+            || self.pending_channel_refresh.is_some()
+            || self.last_channel_refresh.elapsed() < Duration::from_secs(minutes * 60)
+        {
+            return;
+        }
+        self.periodic_channel_refresh = true;
+        self.start_channel_refresh(self.channel_infos.iter().map(|c| c.id).collect());
+    }
+
     async fn check_pending_search(&mut self) {
         if let Some((query, mut rx)) = self.pending_search.take() {
             match rx.try_recv() {
@@ -225,13 +698,46 @@ impl Tui {
         }
     }
 
+    fn start_channel_refresh(&mut self, channel_ids: Vec<u64>) {
+        self.last_channel_refresh = Instant::now();
+        if let Some(ref client) = self.client {
+            let client = client.clone();
+            let db = self.db.clone();
+            let spacing = if self.periodic_channel_refresh {
+                Duration::from_millis(CHANNEL_REFRESH_SPACING_MS)
+            } else {
+                Duration::ZERO
+            };
+            let (tx, rx) = oneshot::channel();
+            self.pending_channel_refresh = Some(rx);
+            tokio::spawn(async move {
+                let unreachable = Self::fetch_channel_names(client, db, channel_ids, spacing).await;
+                let _ = tx.send(unreachable);
+            });
+        }
+    }
+
+    fn reload_channel_infos(&mut self) {
+        match self.db.get_channels_with_names() {
+            Ok(infos) => self.channel_infos = infos,
+            Err(e) => tracing::warn!("Failed to reload channel infos: {}", e),
+        }
+    }
+
     async fn check_pending_channel_refresh(&mut self) {
         if let Some(mut rx) = self.pending_channel_refresh.take() {
             match rx.try_recv() {
-                Ok(()) => {
-                    if let Ok(updated_infos) = self.db.get_channels_with_names() {
-                        self.channel_infos = updated_infos;
+                Ok(unreachable) => {
+                    self.reload_channel_infos();
+                    let periodic = std::mem::take(&mut self.periodic_channel_refresh);
+                    if !periodic || unreachable != self.unreachable_channels {
+                        self.message = Some(if unreachable.is_empty() {
+                            ("All channels accessible".to_string(), true)
+                        } else {
+                            (Self::unreachable_channels_label(&unreachable), false)
+                        });
                     }
+                    self.unreachable_channels = unreachable;
                 }
                 Err(oneshot::error::TryRecvError::Empty) => {
                     self.pending_channel_refresh = Some(rx);
@@ -257,6 +763,44 @@ impl Tui {
             KeyCode::Char('p') | KeyCode::Char(' ') => {
                 self.stats.toggle_paused();
             }
+            KeyCode::Char('r') => {
+                self.trigger_manual_roll();
+            }
+            KeyCode::Char('a') => {
+                self.retry_channel_access();
+            }
+            KeyCode::Char('v') => {
+                self.view = View::Logs;
+                self.log_scroll = 0;
+                self.log_filter.searching = false;
+            }
+            KeyCode::Char('l') => {
+                self.cycle_log_level();
+            }
+            KeyCode::Char('c') => {
+                self.view = View::ClaimByName;
+                self.input_buffer.clear();
+                self.message = None;
+            }
+            KeyCode::Char('u') => {
+                self.prompt_divorce_last_claim();
+            }
+            KeyCode::Char('o') => {
+                self.trigger_owned_sync();
+            }
+            KeyCode::Char('t') => {
+                self.view = View::SeriesStats;
+                self.claims_per_series = self.db.get_claims_per_series(SERIES_STATS_LIMIT).unwrap_or_default();
+            }
+            KeyCode::Char('m') => {
+                self.view = View::Claims;
+                self.claims_cursor = 0;
+                self.message = None;
+                match self.db.get_claims() {
+                    Ok(claims) => self.claims = claims,
+                    Err(e) => self.message = Some((format!("Failed to load claims: {}", e), false)),
+                }
+            }
             KeyCode::Up => {
                 self.scroll_offset = self.scroll_offset.saturating_add(1);
             }
@@ -268,6 +812,175 @@ impl Tui {
         false
     }
 
+    fn trigger_manual_roll(&mut self) {
+        let Some(channel_id) = self.channel_infos.first().map(|c| c.id) else {
+            self.message = Some(("No channel configured".to_string(), false));
+            return;
+        };
+
+        let executor = self.executor.clone();
+        let stats = self.stats.clone();
+        tokio::spawn(async move {
+            match executor.execute_manual_roll(channel_id).await {
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    stats.log_event(EventType::Warning, "Manual roll skipped: no rolls remaining".to_string()).await;
+                }
+                Err(e) => {
+                    stats.log_event(EventType::Error, format!("Manual roll failed: {}", e)).await;
+                }
+            }
+        });
+    }
+
+    fn trigger_owned_sync(&mut self) {
+        let Some(channel_id) = self.channel_infos.first().map(|c| c.id) else {
+            self.message = Some(("No channel configured".to_string(), false));
+            return;
+        };
+
+        let executor = self.executor.clone();
+        let stats = self.stats.clone();
+        tokio::spawn(async move {
+            if let Err(e) = executor.execute_owned_sync(channel_id).await {
+                stats.log_event(EventType::Error, format!("Owned sync failed: {}", e)).await;
+            }
+        });
+        self.message = Some(("Syncing owned characters...".to_string(), true));
+    }
+
+    fn handle_logs_input(&mut self, key: KeyCode) {
+        if self.log_filter.searching {
+            match key {
+                KeyCode::Esc | KeyCode::Enter => self.log_filter.searching = false,
+                KeyCode::Backspace => {
+                    self.log_filter.search.pop();
+                }
+                KeyCode::Char(c) => self.log_filter.search.push(c),
+                _ => return,
+            }
+            self.log_scroll = 0;
+            return;
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.view = View::Dashboard,
+            KeyCode::Char('/') => self.log_filter.searching = true,
+            KeyCode::Char('x') => {
+                self.log_filter = LogFilter::default();
+                self.log_scroll = 0;
+            }
+            KeyCode::Char(c @ '1'..='8') => {
+                self.log_filter.toggle(EventType::all()[c as usize - '1' as usize]);
+                self.log_scroll = 0;
+            }
+            KeyCode::Up => self.log_scroll = self.log_scroll.saturating_add(1),
+            KeyCode::Down => self.log_scroll = self.log_scroll.saturating_sub(1),
+            KeyCode::PageUp => self.log_scroll = self.log_scroll.saturating_add(10),
+            KeyCode::PageDown => self.log_scroll = self.log_scroll.saturating_sub(10),
+            KeyCode::End | KeyCode::Char('g') => self.log_scroll = 0,
+            _ => {}
+        }
+    }
+
+    fn retry_channel_access(&mut self) {
+        let stats = self.stats.clone();
+        let executor = self.executor.clone();
+        tokio::spawn(async move {
+            let restored = stats.reset_channel_access().await;
+            if restored > 0 {
+                stats.log_event(EventType::Info, format!("Retrying access to {} channel(s)", restored)).await;
+            }
+            for kind in executor.reset_failure_breakers().await {
+                stats.log_event(EventType::Info, format!("Resumed {} after manual reset", kind.label())).await;
+            }
+        });
+    }
+
+    fn cycle_log_level(&mut self) {
+        let level = self.log_level.next();
+        if let Err(e) = logging::set_level(&self.log_handle, level) {
+            self.message = Some((format!("Failed to change log level: {}", e), false));
+            return;
+        }
+        self.log_level = level;
+    }
+
+    async fn handle_claim_by_name_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Dashboard;
+                self.input_buffer.clear();
+                self.message = None;
+            }
+            KeyCode::Enter => {
+                let query = self.input_buffer.trim().to_string();
+                if query.is_empty() {
+                    return;
+                }
+
+                if self.stats.is_paused() {
+                    self.message = Some(("Bot is paused, resume to claim".to_string(), false));
+                    return;
+                }
+                if !self.stats.is_claim_available() {
+                    self.message = Some(("Claim is on cooldown".to_string(), false));
+                    return;
+                }
+
+                let Some(entry) = self.stats.find_claimable_roll(&query).await else {
+                    self.message = Some((format!("No unclaimed roll matching '{}'", query), false));
+                    return;
+                };
+
+                let executor = self.executor.clone();
+                let stats = self.stats.clone();
+                let confirm_timeout = Duration::from_secs(self.config.claim_confirm_timeout_seconds);
+                let name = entry.character_name.clone();
+                tokio::spawn(async move {
+                    match executor.claim_roll(&entry).await {
+                        Ok(_) => {
+                            stats.log_event(EventType::Info, format!("Manual claim of {} sent", entry.character_name)).await;
+                            stats.expire_pending_claim(&entry.character_name, entry.message_id, confirm_timeout).await;
+                        }
+                        Err(e) => {
+                            stats.log_event(EventType::Error, format!("Failed to claim {}: {}", entry.character_name, e)).await;
+                        }
+                    }
+                });
+                self.message = Some((format!("Claiming '{}'...", name), true));
+                self.input_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_claims_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.view = View::Dashboard;
+                self.message = None;
+            }
+            KeyCode::Up => {
+                if self.claims_cursor > 0 {
+                    self.claims_cursor -= 1;
+                }
+            }
+            KeyCode::Down => {
+                if self.claims_cursor + 1 < self.claims.len() {
+                    self.claims_cursor += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn handle_settings_input(&mut self, key: KeyCode) {
         let items = SettingsItem::all();
         match key {
@@ -300,14 +1013,19 @@ impl Tui {
                             self.view = View::EditChannels;
                             self.input_buffer = self.channel_infos
                                 .iter()
-                                .map(|c| c.id.to_string())
+                                .map(ChannelInfo::entry)
                                 .collect::<Vec<_>>()
                                 .join(", ");
                             self.message = None;
                         }
                         SettingsItem::RollCommands => {
                             self.view = View::EditRollCommands;
-                            self.input_buffer = self.config.roll_commands.join(", ");
+                            self.input_buffer = self.config.roll_commands_input();
+                            self.message = None;
+                        }
+                        SettingsItem::RollYields => {
+                            self.view = View::EditRollYields;
+                            self.input_buffer = self.config.roll_yields_input();
                             self.message = None;
                         }
                         SettingsItem::Cooldown => {
@@ -315,6 +1033,225 @@ impl Tui {
                             self.input_buffer = self.config.roll_cooldown_seconds.to_string();
                             self.message = None;
                         }
+                        SettingsItem::DefaultRolls => {
+                            self.view = View::EditDefaultRolls;
+                            self.input_buffer = self.config.default_rolls.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::ClaimKakeraMin => {
+                            self.view = View::EditClaimKakeraMin;
+                            self.input_buffer = self.config.claim_kakera_min.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::ClaimKakeraMax => {
+                            self.view = View::EditClaimKakeraMax;
+                            self.input_buffer = self.config.claim_kakera_max.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::ReconnectCommands => {
+                            self.view = View::EditReconnectCommands;
+                            self.input_buffer = self.config.on_reconnect_commands.join(", ");
+                            self.message = None;
+                        }
+                        SettingsItem::DailyCommands => {
+                            self.view = View::EditDailyCommands;
+                            self.input_buffer = self.config.daily_commands.join(", ");
+                            self.message = None;
+                        }
+                        SettingsItem::RollSpread => {
+                            self.config.roll_spread = match self.config.roll_spread {
+                                RollSpread::Burst => RollSpread::Spread,
+                                RollSpread::Spread => RollSpread::Burst,
+                            };
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Roll spread saved! Restart to apply.".to_string(), true));
+                            }
+                        }
+                        SettingsItem::DisplayTimezone => {
+                            self.config.display_timezone = match self.config.display_timezone {
+                                DisplayTimezone::Local => DisplayTimezone::Utc,
+                                DisplayTimezone::Utc => DisplayTimezone::Local,
+                            };
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Time display saved!".to_string(), true));
+                            }
+                        }
+                        SettingsItem::MudaeLanguage => {
+                            self.config.mudae_language = self.config.mudae_language.next();
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Mudae language saved! Restart to apply.".to_string(), true));
+                            }
+                        }
+                        SettingsItem::GenderFilter => {
+                            self.config.claim_gender_filter = match self.config.claim_gender_filter {
+                                None => Some(Gender::Female),
+                                Some(Gender::Female) => Some(Gender::Male),
+                                Some(Gender::Male) => None,
+                            };
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Setting saved!".to_string(), true));
+                            }
+                        }
+                        SettingsItem::ClaimWeights => {
+                            self.view = View::EditClaimWeights;
+                            self.input_buffer = format!(
+                                "{}, {}",
+                                self.config.claim_priority_weight, self.config.claim_kakera_weight
+                            );
+                            self.message = None;
+                        }
+                        SettingsItem::ClaimRules => {
+                            self.view = View::EditClaimRules;
+                            self.input_buffer = self.config.claim_rules.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::HaltKey => {
+                            self.view = View::EditHaltKey;
+                            self.input_buffer = self.config.halt_key.clone();
+                            self.message = None;
+                        }
+                        SettingsItem::RemoteControlUsers => {
+                            self.view = View::EditRemoteControlUsers;
+                            self.input_buffer = self.config.remote_control_user_ids
+                                .iter()
+                                .map(|id| id.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            self.message = None;
+                        }
+                        SettingsItem::MarkWishedEmoji => {
+                            self.view = View::EditMarkWishedEmoji;
+                            self.input_buffer = self.config.mark_wished_emoji.clone().unwrap_or_default();
+                            self.message = None;
+                        }
+                        SettingsItem::ClaimEmoji => {
+                            self.view = View::EditClaimEmoji;
+                            self.input_buffer = self.config.claim_emoji.clone();
+                            self.message = None;
+                        }
+                        SettingsItem::VerifyDelay => {
+                            self.view = View::EditVerifyDelay;
+                            self.input_buffer = format!(
+                                "{}, {}",
+                                self.config.verify_delay_min_ms, self.config.verify_delay_max_ms
+                            );
+                            self.message = None;
+                        }
+                        SettingsItem::ClaimRetries => {
+                            self.view = View::EditClaimRetries;
+                            self.input_buffer = format!("{}, {}", self.config.claim_retries, self.config.claim_retry_window_ms);
+                            self.message = None;
+                        }
+                        SettingsItem::ClaimConfirmTimeout => {
+                            self.view = View::EditClaimConfirmTimeout;
+                            self.input_buffer = self.config.claim_confirm_timeout_seconds.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::ClaimGrace => {
+                            self.view = View::EditClaimGrace;
+                            self.input_buffer = self.config.claim_grace_seconds.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::FailureLimit => {
+                            self.view = View::EditFailureLimit;
+                            self.input_buffer = self.config.max_consecutive_failures.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::AuthErrorHalt => {
+                            self.view = View::EditAuthErrorHalt;
+                            self.input_buffer = self.config.auth_error_halt_count.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::RollDelays => {
+                            self.view = View::EditRollDelays;
+                            self.input_buffer = format!("{}, {}", self.config.startup_roll_delay_seconds, self.config.post_reset_roll_delay_seconds);
+                            self.message = None;
+                        }
+                        SettingsItem::InterChannelDelay => {
+                            self.view = View::EditInterChannelDelay;
+                            self.input_buffer = self.config.inter_channel_delay_ms.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::SessionLimit => {
+                            self.view = View::EditSessionLimit;
+                            self.input_buffer = self.config.max_session_hours.unwrap_or(0).to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::DelayDistribution => {
+                            self.config.delay_distribution = match self.config.delay_distribution {
+                                DelayDistribution::Uniform => DelayDistribution::Gaussian,
+                                DelayDistribution::Gaussian => DelayDistribution::Uniform,
+                            };
+                            Humanizer::set_current(self.config.delay_distribution);
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Delay distribution saved!".to_string(), true));
+                            }
+                        }
+                        SettingsItem::SessionLimitAction => {
+                            self.config.session_limit_action = match self.config.session_limit_action {
+                                SessionLimitAction::Stop => SessionLimitAction::Pause,
+                                SessionLimitAction::Pause => SessionLimitAction::Stop,
+                            };
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Session limit action saved! Restart to apply.".to_string(), true));
+                            }
+                        }
+                        SettingsItem::CatchupMessages => {
+                            self.view = View::EditCatchupMessages;
+                            self.input_buffer = self.config.catchup_messages.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::ChannelRefresh => {
+                            self.view = View::EditChannelRefresh;
+                            self.input_buffer = self.config.channel_refresh_minutes.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::StatsSaveInterval => {
+                            self.view = View::EditStatsSaveInterval;
+                            self.input_buffer = self.config.stats_save_interval_seconds.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::TuiTick => {
+                            self.view = View::EditTuiTick;
+                            self.input_buffer = self.config.tui_tick_ms.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::KakeraPowerMin => {
+                            self.view = View::EditKakeraPowerMin;
+                            self.input_buffer = self.config.kakera_power_min.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::AutoDivorceMaxKakera => {
+                            self.view = View::EditAutoDivorceMaxKakera;
+                            self.input_buffer = self.config.auto_divorce_max_kakera.to_string();
+                            self.message = None;
+                        }
+                        SettingsItem::AutoDivorceCommands => {
+                            self.view = View::EditAutoDivorceCommands;
+                            self.input_buffer = self.config.auto_divorce_commands.join(", ");
+                            self.message = None;
+                        }
+                        SettingsItem::ScheduledCommands => {
+                            self.view = View::EditScheduledCommands;
+                            self.input_buffer = self.config.scheduled_commands
+                                .iter()
+                                .map(|c| c.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            self.message = None;
+                        }
                         _ => {}
                     }
                 }
@@ -328,17 +1265,51 @@ impl Tui {
             SettingsItem::AutoRoll => self.config.auto_roll = !self.config.auto_roll,
             SettingsItem::AutoKakera => self.config.auto_react_kakera = !self.config.auto_react_kakera,
             SettingsItem::AutoDaily => self.config.auto_daily = !self.config.auto_daily,
+            SettingsItem::DailyKakeraReact => self.config.daily_kakera_react = !self.config.daily_kakera_react,
             SettingsItem::Wishlist => self.config.wishlist_enabled = !self.config.wishlist_enabled,
+            SettingsItem::TrustMudaeWish => self.config.trust_mudae_wish = !self.config.trust_mudae_wish,
+            SettingsItem::ShuffleChannels => self.config.shuffle_channels = !self.config.shuffle_channels,
+            SettingsItem::IncludeThreads => self.config.include_threads = !self.config.include_threads,
+            SettingsItem::IncludeDms => self.config.include_dms = !self.config.include_dms,
+            SettingsItem::TrackSeenCharacters => self.config.track_seen_characters = !self.config.track_seen_characters,
             SettingsItem::FuzzyMatch => self.config.fuzzy_match = !self.config.fuzzy_match,
+            SettingsItem::FuzzyDedup => self.config.fuzzy_dedup = !self.config.fuzzy_dedup,
+            SettingsItem::AutoIdle => self.config.auto_idle = !self.config.auto_idle,
+            SettingsItem::StrictWishlistLoad => self.config.strict_wishlist_load = !self.config.strict_wishlist_load,
+            SettingsItem::AutoVerifyOnStart => self.config.auto_verify_on_start = !self.config.auto_verify_on_start,
+            SettingsItem::ShowUserMessages => self.config.show_user_messages = !self.config.show_user_messages,
+            SettingsItem::AsciiMode => {
+                self.config.ascii_mode = !self.config.ascii_mode;
+                glyphs::set_ascii_mode(self.config.ascii_mode);
+            }
+            SettingsItem::EncryptToken => self.config.encrypt_token = !self.config.encrypt_token,
+            SettingsItem::AttemptClaimOnBelongsTo => {
+                self.config.attempt_claim_on_belongs_to = !self.config.attempt_claim_on_belongs_to
+            }
+            SettingsItem::AutoDivorceNonWished => {
+                self.config.auto_divorce_non_wished = !self.config.auto_divorce_non_wished
+            }
             _ => return,
         }
         if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
             self.message = Some((format!("Error: {}", e), false));
+        } else if matches!(item, SettingsItem::AutoDivorceNonWished) && self.config.auto_divorce_non_wished {
+            self.message = Some((
+                format!(
+                    "⚠ Fuzzy wishlist claims under {} ka will be DIVORCED automatically. Restart to apply.",
+                    self.config.auto_divorce_max_kakera
+                ),
+                false,
+            ));
         } else {
             self.message = Some(("Setting saved!".to_string(), true));
         }
     }
 
+    fn load_token_fingerprint(db: &Database) -> String {
+        db.token_fingerprint().ok().flatten().unwrap_or_else(|| "Not set".to_string())
+    }
+
     fn handle_edit_token_input(&mut self, key: KeyCode) {
         match key {
             KeyCode::Esc => {
@@ -348,10 +1319,15 @@ impl Tui {
             KeyCode::Enter => {
                 if self.input_buffer.is_empty() {
                     self.message = Some(("Token cannot be empty".to_string(), false));
+                } else if self.config.encrypt_token {
+                    self.pending_token = Some(std::mem::take(&mut self.input_buffer));
+                    self.message = None;
+                    self.view = View::EditTokenPassphrase;
                 } else if let Err(e) = self.db.save_token(&self.input_buffer) {
                     self.message = Some((format!("Error: {}", e), false));
                 } else {
                     self.message = Some(("Token saved! Restart to apply.".to_string(), true));
+                    self.token_fingerprint = Self::load_token_fingerprint(&self.db);
                     self.view = View::Settings;
                     self.input_buffer.clear();
                 }
@@ -366,59 +1342,84 @@ impl Tui {
         }
     }
 
-    fn handle_edit_channels_input(&mut self, key: KeyCode) {
+    fn handle_edit_token_passphrase_input(&mut self, key: KeyCode) {
         match key {
             KeyCode::Esc => {
                 self.view = View::Settings;
+                self.pending_token = None;
                 self.input_buffer.clear();
             }
             KeyCode::Enter => {
-                let channels: Result<Vec<u64>, _> = self.input_buffer
-                    .split(|c| c == ',' || c == ' ')
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.trim().parse::<u64>())
-                    .collect();
-
-                match channels {
-                    Ok(ids) if !ids.is_empty() => {
-                        if let Err(e) = self.db.save_channels(&ids) {
-                            self.message = Some((format!("Error: {}", e), false));
-                        } else {
-                            self.channel_infos = ids.iter().map(|&id| ChannelInfo {
-                                id,
-                                name: None,
-                                guild: None,
-                            }).collect();
-                            
-                            if let Some(ref client) = self.client {
-                                let client_clone = client.clone();
-                                let db_clone = self.db.clone();
-                                let ids_clone = ids.clone();
-                                let (tx, rx) = oneshot::channel();
-                                self.pending_channel_refresh = Some(rx);
-                                tokio::spawn(async move {
-                                    Self::fetch_channel_names(client_clone, db_clone, ids_clone).await;
-                                    let _ = tx.send(());
-                                });
-                            }
-                            
-                            self.message = Some(("Channels saved! Fetching names...".to_string(), true));
-                            self.view = View::Settings;
-                            self.input_buffer.clear();
-                        }
-                    }
+                let Some(token) = self.pending_token.as_deref() else {
+                    self.view = View::Settings;
+                    return;
+                };
+                if self.input_buffer.is_empty() {
+                    self.message = Some(("Passphrase cannot be empty".to_string(), false));
+                } else if let Err(e) = self.db.save_token_encrypted(token, &self.input_buffer) {
+                    self.message = Some((format!("Error: {}", e), false));
+                } else {
+                    self.message = Some(("Token saved encrypted! Restart to apply.".to_string(), true));
+                    self.token_fingerprint = Self::load_token_fingerprint(&self.db);
+                    self.view = View::Settings;
+                    self.pending_token = None;
+                    self.input_buffer.clear();
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_channels_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match ChannelInfo::parse_entries(&self.input_buffer) {
+                    Ok(channels) if !channels.is_empty() => {
+                        let ids: Vec<u64> = channels.iter().map(|c| c.id).collect();
+                        let saved = self.db.save_channels(&ids).and_then(|_| {
+                            channels.iter().try_for_each(|c| self.db.set_channel_roll_commands(c.id, c.roll_commands.as_deref()))
+                        });
+                        if let Err(e) = saved {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            let has_overrides = channels.iter().any(|c| c.roll_commands.is_some());
+                            self.channel_infos = channels;
+                            self.reload_channel_infos();
+                            
+                            self.start_channel_refresh(ids);
+                            self.unreachable_channels.clear();
+                            let message = if has_overrides {
+                                "Channels saved! Checking access... Restart to apply roll command overrides."
+                            } else {
+                                "Channels saved! Checking access..."
+                            };
+                            self.message = Some((message.to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
                     Ok(_) => {
                         self.message = Some(("Enter at least one channel".to_string(), false));
                     }
-                    Err(_) => {
-                        self.message = Some(("Invalid channel ID format".to_string(), false));
+                    Err(e) => {
+                        self.message = Some((e, false));
                     }
                 }
             }
             KeyCode::Backspace => {
                 self.input_buffer.pop();
             }
-            KeyCode::Char(c) if c.is_ascii_digit() || c == ',' || c == ' ' => {
+            KeyCode::Char(c) => {
                 self.input_buffer.push(c);
             }
             _ => {}
@@ -432,16 +1433,9 @@ impl Tui {
                 self.input_buffer.clear();
             }
             KeyCode::Enter => {
-                let commands: Vec<String> = self.input_buffer
-                    .split(',')
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-
-                if commands.is_empty() {
-                    self.message = Some(("Enter at least one command".to_string(), false));
+                if let Err(e) = self.config.set_roll_commands(&self.input_buffer) {
+                    self.message = Some((e, false));
                 } else {
-                    self.config.roll_commands = commands;
                     if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
                         self.message = Some((format!("Error: {}", e), false));
                     } else {
@@ -461,6 +1455,33 @@ impl Tui {
         }
     }
 
+    fn handle_edit_roll_yields_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                if let Err(e) = self.config.set_roll_yields(&self.input_buffer) {
+                    self.message = Some((e, false));
+                } else if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                    self.message = Some((format!("Error: {}", e), false));
+                } else {
+                    self.message = Some(("Roll yields saved! Restart to apply.".to_string(), true));
+                    self.view = View::Settings;
+                    self.input_buffer.clear();
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
     fn handle_edit_cooldown_input(&mut self, key: KeyCode) {
         match key {
             KeyCode::Esc => {
@@ -487,25 +1508,1092 @@ impl Tui {
             KeyCode::Backspace => {
                 self.input_buffer.pop();
             }
-            KeyCode::Char(c) if c.is_ascii_digit() => {
-                self.input_buffer.push(c);
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_claim_weights_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let weights: Vec<Option<f64>> = self.input_buffer
+                    .split(',')
+                    .map(|s| s.trim().parse::<f64>().ok().filter(|w| w.is_finite() && *w >= 0.0))
+                    .collect();
+                match weights.as_slice() {
+                    [Some(priority), Some(kakera)] => {
+                        self.config.claim_priority_weight = *priority;
+                        self.config.claim_kakera_weight = *kakera;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Claim weights saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some(("Enter two non-negative numbers: priority, kakera".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, '.' | ',' | ' ') => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_verify_delay_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let delays: Vec<Option<u64>> = self.input_buffer
+                    .split(',')
+                    .map(|s| s.trim().parse::<u64>().ok())
+                    .collect();
+                match delays.as_slice() {
+                    [Some(min), Some(max)] if *min > 0 && min <= max => {
+                        self.config.verify_delay_min_ms = *min;
+                        self.config.verify_delay_max_ms = *max;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Verify delay saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some(("Enter min, max in milliseconds with 0 < min ≤ max".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, ',' | ' ') => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_claim_retries_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let values: Vec<&str> = self.input_buffer.split(',').map(str::trim).collect();
+                match values.as_slice() {
+                    [retries, window] => match (retries.parse::<u32>(), window.parse::<u64>()) {
+                        (Ok(retries), Ok(window)) => {
+                            self.config.claim_retries = retries;
+                            self.config.claim_retry_window_ms = window;
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Claim retries saved! Restart to apply.".to_string(), true));
+                                self.view = View::Settings;
+                                self.input_buffer.clear();
+                            }
+                        }
+                        _ => {
+                            self.message = Some(("Enter retries, window in milliseconds".to_string(), false));
+                        }
+                    },
+                    _ => {
+                        self.message = Some(("Enter retries, window in milliseconds".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, ',' | ' ') => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_roll_delays_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let values: Vec<&str> = self.input_buffer.split(',').map(str::trim).collect();
+                match values.as_slice() {
+                    [startup, post_reset] => match (startup.parse::<u64>(), post_reset.parse::<u64>()) {
+                        (Ok(startup), Ok(post_reset)) => {
+                            self.config.startup_roll_delay_seconds = startup;
+                            self.config.post_reset_roll_delay_seconds = post_reset;
+                            if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                                self.message = Some((format!("Error: {}", e), false));
+                            } else {
+                                self.message = Some(("Roll delays saved! Restart to apply.".to_string(), true));
+                                self.view = View::Settings;
+                                self.input_buffer.clear();
+                            }
+                        }
+                        _ => {
+                            self.message = Some(("Enter startup, post-reset delay in seconds".to_string(), false));
+                        }
+                    },
+                    _ => {
+                        self.message = Some(("Enter startup, post-reset delay in seconds".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, ',' | ' ') => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_inter_channel_delay_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(ms) => {
+                        self.config.inter_channel_delay_ms = ms;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Channel switch delay saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter a delay in milliseconds".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_stats_save_interval_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(seconds) if seconds > 0 => {
+                        self.config.stats_save_interval_seconds = seconds;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Save interval saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some(("Enter a number of seconds greater than 0".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_tui_tick_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(ms) if ms >= MIN_TUI_TICK_MS => {
+                        self.config.tui_tick_ms = ms;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Screen tick saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some((format!("Enter at least {} ms", MIN_TUI_TICK_MS), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_session_limit_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(hours) => {
+                        self.config.max_session_hours = Some(hours).filter(|hours| *hours > 0);
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Session limit saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter a whole number of hours (0 = no limit)".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_claim_grace_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(secs) => {
+                        self.config.claim_grace_seconds = secs;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Claim grace saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter a number of seconds (0 = off)".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_claim_confirm_timeout_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(secs) => {
+                        self.config.claim_confirm_timeout_seconds = secs;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Claim confirm timeout saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter a number of seconds (0 = off)".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_auth_error_halt_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u32>() {
+                    Ok(count) => {
+                        self.config.auth_error_halt_count = count;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Auth error halt saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter a whole number (0 = off)".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_failure_limit_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u32>() {
+                    Ok(limit) => {
+                        self.config.max_consecutive_failures = limit;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Failure limit saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter a whole number (0 = off)".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_channel_refresh_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(minutes) => {
+                        self.config.channel_refresh_minutes = minutes;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Channel name refresh saved!".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter a number of minutes (0 = off)".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_catchup_messages_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u32>() {
+                    Ok(count) if count <= 100 => {
+                        self.config.catchup_messages = count;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Catch-up saved! Used from next start.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some(("Enter a number from 0 to 100".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_kakera_power_min_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u32>() {
+                    Ok(power) => {
+                        self.config.kakera_power_min = power;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Kakera power minimum saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some(("Enter a valid number".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_auto_divorce_max_kakera_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u32>() {
+                    Ok(value) => {
+                        self.config.auto_divorce_max_kakera = value;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Auto divorce threshold saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some(("Enter a valid number".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_auto_divorce_commands_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let commands: Vec<String> = self.input_buffer
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+
+                if !commands.iter().any(|c| c.contains("{name}")) {
+                    self.message = Some(("At least one command must contain {name}".to_string(), false));
+                } else {
+                    self.config.auto_divorce_commands = commands;
+                    if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                        self.message = Some((format!("Error: {}", e), false));
+                    } else {
+                        self.message = Some(("Divorce commands saved! Restart to apply.".to_string(), true));
+                        self.view = View::Settings;
+                        self.input_buffer.clear();
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_claim_kakera_range_input(&mut self, key: KeyCode, max: bool) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let Ok(value) = self.input_buffer.parse::<u32>() else {
+                    self.message = Some(("Enter a valid number".to_string(), false));
+                    return;
+                };
+
+                let (min, max_value) = if max {
+                    (self.config.claim_kakera_min, value)
+                } else {
+                    (value, self.config.claim_kakera_max)
+                };
+                if max_value > 0 && min > max_value {
+                    self.message = Some(("Minimum can't exceed the maximum".to_string(), false));
+                    return;
+                }
+
+                self.config.claim_kakera_min = min;
+                self.config.claim_kakera_max = max_value;
+                if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                    self.message = Some((format!("Error: {}", e), false));
+                } else {
+                    self.message = Some(("Kakera claim range saved! Restart to apply.".to_string(), true));
+                    self.view = View::Settings;
+                    self.input_buffer.clear();
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_default_rolls_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match self.input_buffer.parse::<u64>() {
+                    Ok(rolls) => {
+                        self.config.default_rolls = rolls;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Rolls per reset saved! Used from next start.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    _ => {
+                        self.message = Some(("Enter a valid number".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_reconnect_commands_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                self.config.on_reconnect_commands = self.input_buffer
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+
+                if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                    self.message = Some((format!("Error: {}", e), false));
+                } else {
+                    self.message = Some(("Reconnect commands saved! Restart to apply.".to_string(), true));
+                    self.view = View::Settings;
+                    self.input_buffer.clear();
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_daily_commands_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let commands: Vec<String> = self.input_buffer
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+
+                if commands.is_empty() {
+                    self.message = Some(("Enter at least one command".to_string(), false));
+                } else {
+                    self.config.daily_commands = commands;
+                    if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                        self.message = Some((format!("Error: {}", e), false));
+                    } else {
+                        self.message = Some(("Daily commands saved! Restart to apply.".to_string(), true));
+                        self.view = View::Settings;
+                        self.input_buffer.clear();
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_scheduled_commands_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let entries: Vec<&str> = self.input_buffer
+                    .split(',')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                let parsed = ScheduledCommand::parse_list(&entries);
+
+                if parsed.len() != entries.len() {
+                    self.message = Some(("Use command@seconds or command@HH:MM".to_string(), false));
+                } else {
+                    self.config.scheduled_commands = parsed;
+                    if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                        self.message = Some((format!("Error: {}", e), false));
+                    } else {
+                        self.message = Some(("Scheduled commands saved! Restart to apply.".to_string(), true));
+                        self.view = View::Settings;
+                        self.input_buffer.clear();
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_claim_rules_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match ClaimRules::parse(&self.input_buffer) {
+                    Some(rules) => {
+                        self.config.claim_rules = rules;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Claim rules saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    None => {
+                        self.message = Some(("Unknown condition; use wished, kakera>=N, kakera<=N, series=Name, rank<=N, unranked".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_halt_key_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                match parse_halt_key(&self.input_buffer) {
+                    Ok(halt_key) => {
+                        self.config.halt_key = self.input_buffer.trim().to_string();
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.halt_key = Some(halt_key);
+                            self.message = Some(("Halt key saved!".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(e) => {
+                        self.message = Some((e, false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_remote_control_users_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let ids: Result<Vec<u64>, _> = self.input_buffer
+                    .split(',')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse::<u64>())
+                    .collect();
+                match ids {
+                    Ok(ids) => {
+                        self.config.remote_control_user_ids = ids;
+                        if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                            self.message = Some((format!("Error: {}", e), false));
+                        } else {
+                            self.message = Some(("Remote control users saved! Restart to apply.".to_string(), true));
+                            self.view = View::Settings;
+                            self.input_buffer.clear();
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(("Enter Discord user IDs separated by commas".to_string(), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || c == ',' || c == ' ' => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_mark_wished_emoji_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let emoji = self.input_buffer.trim().to_string();
+                if MudaeParser::is_claim_emoji(&emoji) {
+                    self.message = Some(("That emoji would claim the character, pick another".to_string(), false));
+                    return;
+                }
+                self.config.mark_wished_emoji = Some(emoji).filter(|e| !e.is_empty());
+                if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                    self.message = Some((format!("Error: {}", e), false));
+                } else {
+                    self.message = Some(("Marker saved! Restart to apply.".to_string(), true));
+                    self.view = View::Settings;
+                    self.input_buffer.clear();
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_claim_emoji_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Settings;
+                self.input_buffer.clear();
+            }
+            KeyCode::Enter => {
+                let emoji = self.input_buffer.trim().to_string();
+                if !MudaeParser::is_claim_emoji(&emoji) {
+                    self.message = Some(("Enter a heart Mudae accepts for claiming, e.g. 💖".to_string(), false));
+                    return;
+                }
+                self.config.claim_emoji = emoji;
+                if let Err(e) = self.config.save_to_db(self.db.as_ref()) {
+                    self.message = Some((format!("Error: {}", e), false));
+                } else {
+                    self.message = Some(("Claim emoji saved! Restart to apply.".to_string(), true));
+                    self.view = View::Settings;
+                    self.input_buffer.clear();
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_halt_key(&mut self) {
+        if self.stats.is_halted() {
+            self.view = View::ConfirmClearHalt;
+            self.message = None;
+            return;
+        }
+
+        let marker = Database::halt_marker_path();
+        let stats = self.stats.clone();
+        match stats.halt(&marker) {
+            Ok(()) => {
+                tokio::spawn(async move {
+                    stats.log_event(EventType::Error, "HALTED: all outbound actions blocked".to_string()).await;
+                });
+            }
+            Err(e) => {
+                self.message = Some((format!("Halted, but failed to write {}: {}", marker.display(), e), false));
+            }
+        }
+    }
+
+    fn prompt_divorce_last_claim(&mut self) {
+        match self.db.get_last_claim() {
+            Ok(Some(claim)) => {
+                self.pending_divorce = Some(claim);
+                self.view = View::ConfirmDivorce;
+                self.message = None;
+            }
+            Ok(None) => {
+                self.message = Some(("No claims to undo".to_string(), false));
+            }
+            Err(e) => {
+                self.message = Some((format!("Failed to load claims: {}", e), false));
+            }
+        }
+    }
+
+    fn handle_confirm_divorce_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                let Some(claim) = self.pending_divorce.take() else {
+                    self.view = View::Dashboard;
+                    return;
+                };
+                let executor = self.executor.clone();
+                let stats = self.stats.clone();
+                let db = self.db.clone();
+                tokio::spawn(async move {
+                    match executor.execute_divorce(claim.channel_id, &claim.character_name).await {
+                        Ok(()) => {
+                            if let Err(e) = db.remove_latest_claim(&claim.character_name) {
+                                tracing::warn!("Failed to remove divorced claim: {}", e);
+                            }
+                            stats.decrement_claimed();
+                            stats.refresh_claimed_value(&db);
+                            stats.log_event(
+                                EventType::Warning,
+                                format!("Divorced last claim {} in channel {}", claim.character_name, claim.channel_id),
+                            ).await;
+                        }
+                        Err(e) => {
+                            stats.log_event(EventType::Error, format!("Divorce of {} failed: {}", claim.character_name, e)).await;
+                        }
+                    }
+                });
+                self.message = Some(("Divorcing last claim...".to_string(), true));
+                self.view = View::Dashboard;
+            }
+            KeyCode::Esc | KeyCode::Char('n') => {
+                self.pending_divorce = None;
+                self.view = View::Dashboard;
+                self.message = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_confirm_clear_halt_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('y') => {
+                match self.stats.clear_halt(&Database::halt_marker_path()) {
+                    Ok(()) => {
+                        let stats = self.stats.clone();
+                        tokio::spawn(async move {
+                            stats.log_event(EventType::Success, "Halt cleared, outbound actions resumed".to_string()).await;
+                        });
+                        self.view = View::Dashboard;
+                        self.message = None;
+                    }
+                    Err(e) => {
+                        self.message = Some((format!("Failed to remove halt marker: {}", e), false));
+                    }
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('n') => {
+                self.view = View::Dashboard;
+                self.message = None;
             }
             _ => {}
         }
     }
 
     async fn handle_wishlist_input(&mut self, key: KeyCode) {
-        let chars = tokio::task::block_in_place(|| {
-            let rt = tokio::runtime::Handle::current();
-            rt.block_on(self.wishlist.get_characters())
-        });
+        let chars = self.wishlist.get_characters().await;
         let char_count = chars.len();
 
         match key {
+            KeyCode::Esc if self.wishlist_verifier.is_running() => {
+                self.wishlist_verifier.cancel();
+                self.message = Some(("Cancelling verification...".to_string(), true));
+            }
             KeyCode::Esc => {
                 self.view = View::Dashboard;
                 self.message = None;
             }
+            KeyCode::Char('v') => {
+                if self.wishlist_verifier.is_running() {
+                    self.message = Some(("Verification is already running".to_string(), false));
+                    return;
+                }
+
+                let verifier = self.wishlist_verifier.clone();
+                let stats = self.stats.clone();
+                tokio::spawn(async move {
+                    match verifier.verify_unverified_characters().await {
+                        Ok(report) => {
+                            let outcome = if report.cancelled { "cancelled" } else { "finished" };
+                            stats.log_event(
+                                EventType::Wishlist,
+                                format!("Verification {}: {} verified, {} not found", outcome, report.verified, report.failed),
+                            ).await;
+                        }
+                        Err(e) => {
+                            stats.log_event(EventType::Error, format!("Verification failed: {}", e)).await;
+                        }
+                    }
+                });
+                self.message = Some(("Verifying unverified characters...".to_string(), true));
+            }
+            KeyCode::Char('i') => {
+                self.view = View::ImportWishlist;
+                self.input_buffer.clear();
+                self.message = None;
+            }
             KeyCode::Char('a') | KeyCode::Char('s') => {
                 self.view = View::SearchCharacter;
                 self.input_buffer.clear();
@@ -515,11 +2603,7 @@ impl Tui {
             KeyCode::Char('d') | KeyCode::Delete => {
                 if char_count > 0 && self.wishlist_cursor < char_count {
                     let char_name = chars[self.wishlist_cursor].name.clone();
-                    let wishlist = self.wishlist.clone();
-                    let result = tokio::task::block_in_place(|| {
-                        let rt = tokio::runtime::Handle::current();
-                        rt.block_on(wishlist.remove_character(&char_name))
-                    });
+                    let result = self.wishlist.remove_character(&char_name).await;
                     match result {
                         Ok(true) => {
                             self.message = Some((format!("Removed '{}'", char_name), true));
@@ -536,6 +2620,25 @@ impl Tui {
                     }
                 }
             }
+            KeyCode::Char('w') => {
+                if char_count > 0 && self.wishlist_cursor < char_count {
+                    let char_name = chars[self.wishlist_cursor].name.clone();
+                    let soft = !chars[self.wishlist_cursor].soft;
+                    let result = self.wishlist.set_soft(&char_name, soft).await;
+                    match result {
+                        Ok(true) => {
+                            let kind = if soft { "soft (watch only)" } else { "hard (auto-claim)" };
+                            self.message = Some((format!("'{}' is now a {} wish", char_name, kind), true));
+                        }
+                        Ok(false) => {
+                            self.message = Some(("Character not found".to_string(), false));
+                        }
+                        Err(e) => {
+                            self.message = Some((format!("Error: {}", e), false));
+                        }
+                    }
+                }
+            }
             KeyCode::Up => {
                 if self.wishlist_cursor > 0 {
                     self.wishlist_cursor -= 1;
@@ -550,6 +2653,102 @@ impl Tui {
         }
     }
 
+    async fn handle_import_wishlist_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.view = View::Wishlist;
+                self.input_buffer.clear();
+                self.message = None;
+            }
+            KeyCode::Enter => {
+                let path = self.input_buffer.trim().to_string();
+                if path.is_empty() {
+                    return;
+                }
+                match WishlistManager::read_import_file(std::path::Path::new(&path)).await {
+                    Ok(characters) if characters.is_empty() => {
+                        self.message = Some(("No characters in that file".to_string(), false));
+                    }
+                    Ok(characters) => {
+                        self.import_preview = self.wishlist
+                            .preview_import(characters)
+                            .await
+                            .into_iter()
+                            .map(|entry| {
+                                let selected = entry.status == ImportStatus::New;
+                                (entry, selected)
+                            })
+                            .collect();
+                        self.import_cursor = 0;
+                        self.view = View::ImportPreview;
+                        self.input_buffer.clear();
+                        self.message = None;
+                    }
+                    Err(e) => {
+                        self.message = Some((format!("Error: {:#}", e), false));
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    async fn handle_import_preview_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.import_preview.clear();
+                self.view = View::Wishlist;
+                self.message = Some(("Import cancelled".to_string(), false));
+            }
+            KeyCode::Up => {
+                self.import_cursor = self.import_cursor.saturating_sub(1);
+            }
+            KeyCode::Down if self.import_cursor + 1 < self.import_preview.len() => {
+                self.import_cursor += 1;
+            }
+            KeyCode::Char(' ') => {
+                if let Some((entry, selected)) = self.import_preview.get_mut(self.import_cursor) {
+                    if entry.status == ImportStatus::Duplicate {
+                        self.message = Some((format!("'{}' is already in the wishlist", entry.character.name), false));
+                    } else {
+                        *selected = !*selected;
+                    }
+                }
+            }
+            KeyCode::Enter => {
+                let characters: Vec<WishedCharacter> = self.import_preview
+                    .drain(..)
+                    .filter(|(_, selected)| *selected)
+                    .map(|(entry, _)| entry.character)
+                    .collect();
+                self.view = View::Wishlist;
+                if characters.is_empty() {
+                    self.message = Some(("Nothing selected to import".to_string(), false));
+                    return;
+                }
+                self.message = Some(match self.wishlist.import(characters).await {
+                    Ok(report) => {
+                        for (imported, existing) in &report.near_duplicates {
+                            self.stats.log_event(
+                                EventType::Warning,
+                                format!("Imported '{}' looks like existing '{}'", imported, existing),
+                            ).await;
+                        }
+                        (report.summary(), true)
+                    }
+                    Err(e) => (format!("Error: {}", e), false),
+                });
+            }
+            _ => {}
+        }
+    }
+
     async fn handle_search_input(&mut self, key: KeyCode) {
         if self.searching {
             if key == KeyCode::Esc {
@@ -623,13 +2822,10 @@ impl Tui {
                     added_date: Utc::now(),
                     notes: None,
                     priority: 0,
+                    soft: false,
                 };
 
-                let wishlist = self.wishlist.clone();
-                let add_result = tokio::task::block_in_place(|| {
-                    let rt = tokio::runtime::Handle::current();
-                    rt.block_on(wishlist.add_character(character))
-                });
+                let add_result = self.wishlist.add_character(character).await;
 
                 match add_result {
                     Ok(true) => {
@@ -653,23 +2849,60 @@ impl Tui {
     async fn draw(&mut self) -> Result<()> {
         let stats = self.stats.clone();
         let config = self.config.clone();
-        let channel_infos = self.db.get_channels_with_names().unwrap_or_else(|_| self.channel_infos.clone());
+        let channel_infos = self.channel_infos.clone();
         let scroll_offset = self.scroll_offset;
         let view = self.view.clone();
         let input_buffer = self.input_buffer.clone();
         let settings_cursor = self.settings_cursor;
+        let log_level = self.log_level;
         let wishlist_cursor = self.wishlist_cursor;
+        let import_preview = self.import_preview.clone();
+        let import_cursor = self.import_cursor;
+        let claims = self.claims.clone();
+        let claims_cursor = self.claims_cursor;
+        let claims_per_series = self.claims_per_series.clone();
         let cursor_visible = self.cursor_visible;
+        let pending_divorce = self.pending_divorce.clone();
+        let divorce_commands = self.config.auto_divorce_commands.clone();
         let message = self.message.clone();
+        let unreachable_channels = self.unreachable_channels.clone();
+        let token_fingerprint = self.token_fingerprint.clone();
         let searching = self.searching;
+        let log_filter = self.log_filter.clone();
+        let log_scroll = self.log_scroll;
         
-        let connection_status = stats.get_connection_status().await;
+        let connection = stats.get_connection_info().await;
         let activity_log = stats.get_activity_log().await;
         let channel_activity = stats.get_channel_activity().await;
         let username = stats.get_username().await;
         let is_paused = stats.is_paused();
+        let halt_key_label = self.config.halt_key.clone();
+        let idle_until = stats.get_idle_until().await.filter(|until| *until > Utc::now());
+        let run_state = if stats.is_halted() && stats.has_account_alert() {
+            RunState::AccountAlert
+        } else if stats.is_halted() {
+            RunState::Halted
+        } else if is_paused && stats.has_captcha_alert() {
+            RunState::Captcha
+        } else if is_paused {
+            RunState::Paused
+        } else if let Some(until) = idle_until {
+            RunState::Idle(until)
+        } else {
+            RunState::Running
+        };
+        let timezone = config.display_timezone;
         let reset_timer = stats.format_time_until_roll_reset().await;
+        let next_daily = self.executor.next_daily_at().await;
+        let inaccessible_channels = stats.get_inaccessible_channels().await;
+        let kakera_power = stats.get_kakera_power().await;
         let wishlist_chars = self.wishlist.get_characters().await;
+        let verification_progress = self.wishlist_verifier.is_running().then(|| self.wishlist_verifier.progress());
+        let top_rolled_series = if view == View::SeriesStats {
+            stats.top_rolled_series(SERIES_STATS_LIMIT).await
+        } else {
+            Vec::new()
+        };
 
         self.terminal.draw(|frame| {
             let size = frame.size();
@@ -678,13 +2911,13 @@ impl Tui {
                 .direction(Direction::Vertical)
                 .constraints([
                     Constraint::Length(3),
-                    Constraint::Length(12),
+                    Constraint::Length(15),
                     Constraint::Min(8),
                     Constraint::Length(1),
                 ])
                 .split(size);
 
-            Self::render_header(frame, main_chunks[0], &stats, connection_status, username.as_deref(), is_paused);
+            Self::render_header(frame, main_chunks[0], &stats, &connection, username.as_deref(), run_state, &config);
 
             let middle_chunks = Layout::default()
                 .direction(Direction::Horizontal)
@@ -692,48 +2925,238 @@ impl Tui {
                 .split(main_chunks[1]);
 
             Self::render_stats_panel(frame, middle_chunks[0], &stats, &reset_timer);
-            Self::render_config_panel(frame, middle_chunks[1], &config, &channel_infos);
+            Self::render_config_panel(frame, middle_chunks[1], &config, &channel_infos, next_daily, &inaccessible_channels, kakera_power);
 
             let bottom_chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
                 .split(main_chunks[2]);
 
-            Self::render_activity_log(frame, bottom_chunks[0], &activity_log, scroll_offset);
-            Self::render_channel_feed(frame, bottom_chunks[1], &channel_activity);
+            Self::render_activity_log(frame, bottom_chunks[0], &activity_log, scroll_offset, timezone);
+            Self::render_channel_feed(frame, bottom_chunks[1], &channel_activity, config.show_user_messages);
 
-            Self::render_help_bar(frame, main_chunks[3], is_paused);
+            Self::render_help_bar(frame, main_chunks[3], is_paused, log_level, &halt_key_label);
 
             match view {
                 View::Settings => {
                     let popup_area = centered_rect(55, 60, size);
                     frame.render_widget(Clear, popup_area);
-                    Self::render_settings(frame, popup_area, settings_cursor, &config, &message);
+                    Self::render_settings(frame, popup_area, settings_cursor, &config, &message, &unreachable_channels, &token_fingerprint);
                 }
                 View::EditToken => {
                     let popup_area = centered_rect(60, 30, size);
                     frame.render_widget(Clear, popup_area);
                     Self::render_text_input(frame, popup_area, "Edit Token", "Enter new Discord token:", &input_buffer, true, cursor_visible, &message);
                 }
+                View::EditTokenPassphrase => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Token", "Passphrase to encrypt the token (asked for at the next start):", &input_buffer, true, cursor_visible, &message);
+                }
                 View::EditChannels => {
                     let popup_area = centered_rect(60, 30, size);
                     frame.render_widget(Clear, popup_area);
-                    Self::render_text_input(frame, popup_area, "Edit Channels", "Enter channel IDs (comma separated):", &input_buffer, false, cursor_visible, &message);
+                    Self::render_text_input(frame, popup_area, "Edit Channels", "Enter channel IDs (comma separated, optional roll commands, e.g. 123=$w $wg):", &input_buffer, false, cursor_visible, &message);
                 }
                 View::EditRollCommands => {
                     let popup_area = centered_rect(60, 30, size);
                     frame.render_widget(Clear, popup_area);
-                    Self::render_text_input(frame, popup_area, "Edit Roll Commands", "Enter commands (comma separated, e.g. $wa, $ha):", &input_buffer, false, cursor_visible, &message);
+                    Self::render_text_input(frame, popup_area, "Edit Roll Commands", "Enter commands (comma separated, optional cooldown, e.g. $wa:3600, $ha):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditRollYields => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Roll Yields", "Rolls used by each command when it isn't 1 (comma separated, e.g. $w10=10; empty = all 1):", &input_buffer, false, cursor_visible, &message);
                 }
                 View::EditCooldown => {
                     let popup_area = centered_rect(60, 30, size);
                     frame.render_widget(Clear, popup_area);
                     Self::render_text_input(frame, popup_area, "Edit Cooldown", "Enter cooldown in seconds:", &input_buffer, false, cursor_visible, &message);
                 }
+                View::EditDefaultRolls => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Rolls per Reset", "Rolls assumed at startup until Mudae reports the real count:", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditClaimKakeraMin => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Claim Kakera Min", "Claim unwished characters worth at least this much kakera (0 = no minimum):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditClaimKakeraMax => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Claim Kakera Max", "Claim unwished characters worth at most this much kakera (0 = no maximum):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditScheduledCommands => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Scheduled Commands", "command@seconds or command@HH:MM (comma separated, e.g. $dk@72000, $p@12:00):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::ClaimByName => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Claim Character", "Enter the name of a character rolled on screen:", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditClaimWeights => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Claim Weights", "score = (wished ? 1e9 : 0) + priority × P + kakera × K. Enter P, K (e.g. 10000, 1):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditHaltKey => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Halt Key", "Key that halts all outbound actions from any screen (single character or F1-F12):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditRemoteControlUsers => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Remote Control Users", "User IDs allowed to send pause, resume or stop by DM or mention (comma separated, empty = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditMarkWishedEmoji => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Mark Missed Wishes", "Reaction added to wished rolls the bot can't claim (empty = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditClaimEmoji => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Fallback Claim Emoji", "Reaction used to claim when a roll has no claim button and shows no heart:", &input_buffer, false, cursor_visible, &message);
+                }
+                View::ConfirmClearHalt => {
+                    let popup_area = centered_rect(55, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_confirm_clear_halt(frame, popup_area, &message);
+                }
+                View::ConfirmDivorce => {
+                    let popup_area = centered_rect(55, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_confirm_divorce(frame, popup_area, pending_divorce.as_ref(), &divorce_commands);
+                }
+                View::EditClaimRules => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Claim Rules", "Claim when any |-separated set matches; & joins conditions (e.g. wished | kakera>=150 & unranked):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditVerifyDelay => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Verify Delay", "Delay between $im lookups backs off on timeouts and recovers on replies. Enter min, max ms (e.g. 1500, 15000):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditClaimRetries => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Claim Retries", "Retries after a failed claim and the window they must fit in. Enter retries, window ms (e.g. 2, 5000):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditRollDelays => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit First Roll Delays", "Seconds to wait before the first roll after startup and after a roll reset, plus up to 50% jitter. Enter startup, post-reset (e.g. 5, 10):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditInterChannelDelay => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Channel Switch Delay", "Minimum milliseconds since the last sent message before rolling in a different channel, plus up to 50% jitter (0 = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditSessionLimit => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Max Session Hours", "Hours to run before stopping or pausing, counted from startup (0 = no limit):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditClaimConfirmTimeout => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Claim Confirm Timeout", "Seconds to wait for Mudae's marriage message after a claim before warning that it was likely lost (0 = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditClaimGrace => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Claim Grace", "When a claimable roll shows up this many seconds or less before your claim resets, wait for the reset and claim it, unless the roll expires first (0 = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditFailureLimit => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Failure Limit", "Consecutive failed sends, claims or kakera reactions before that action pauses until a success or A is pressed (0 = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditAuthErrorHalt => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Auth Error Halt", "Discord 401/403 errors within 2 minutes that halt everything, since the account may be flagged or locked (0 = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditCatchupMessages => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Startup Catch-up", "Recent messages per channel to scan for unclaimed wished rolls on connect (0 = off, max 100):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditChannelRefresh => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Channel Name Refresh", "Minutes between re-fetching channel and server names, one channel at a time (0 = off):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditStatsSaveInterval => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Stats Save Interval", "Seconds between periodic stats saves (claims and large kakera also save immediately):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditTuiTick => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Screen Tick", "Milliseconds between input checks; the screen only redraws on changes or once a second:", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditDailyCommands => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Daily Commands", "Commands to run in order when daily is ready (comma separated, e.g. $daily, $dk, $vote):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditAutoDivorceMaxKakera => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Auto Divorce Threshold", "Fuzzy wishlist claims worth less than this many kakera are divorced when auto divorce is on:", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditAutoDivorceCommands => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Divorce Commands", "Commands sent in order to divorce, {name} is the character (e.g. $divorce {name}, y):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditKakeraPowerMin => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Kakera Power Min", "Skip kakera reactions while the last $mk power is below this percentage (0 = always react):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::EditReconnectCommands => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Edit Reconnect Commands", "Commands to run after a reconnect (comma separated, empty = none):", &input_buffer, false, cursor_visible, &message);
+                }
+                View::Claims => {
+                    let popup_area = centered_rect(70, 80, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_claims(frame, popup_area, &claims, claims_cursor, &message, timezone);
+                }
+                View::SeriesStats => {
+                    let popup_area = centered_rect(70, 60, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_series_stats(frame, popup_area, &top_rolled_series, &claims_per_series);
+                }
+                View::Logs => {
+                    let popup_area = centered_rect(90, 90, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_logs(frame, popup_area, &activity_log, &log_filter, log_scroll, cursor_visible, timezone);
+                }
                 View::Wishlist => {
                     let popup_area = centered_rect(70, 80, size);
                     frame.render_widget(Clear, popup_area);
-                    Self::render_wishlist(frame, popup_area, &wishlist_chars, wishlist_cursor, &message);
+                    Self::render_wishlist(frame, popup_area, &wishlist_chars, wishlist_cursor, verification_progress, &message);
+                }
+                View::ImportWishlist => {
+                    let popup_area = centered_rect(60, 30, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_text_input(frame, popup_area, "Import Wishlist", "Path to an exported wishlist JSON file. You can review the entries before anything is added:", &input_buffer, false, cursor_visible, &message);
+                }
+                View::ImportPreview => {
+                    let popup_area = centered_rect(70, 80, size);
+                    frame.render_widget(Clear, popup_area);
+                    Self::render_import_preview(frame, popup_area, &import_preview, import_cursor, &message);
                 }
                 View::SearchCharacter => {
                     let popup_area = centered_rect(60, 35, size);
@@ -752,44 +3175,111 @@ impl Tui {
         Ok(())
     }
 
-    fn render_header(frame: &mut Frame, area: Rect, stats: &Stats, status: ConnectionStatus, username: Option<&str>, is_paused: bool) {
-        let status_text = match status {
-            ConnectionStatus::Connected => ("● CONNECTED", Color::Green),
-            ConnectionStatus::Connecting => ("◐ CONNECTING", Color::Yellow),
-            ConnectionStatus::Reconnecting => ("◐ RECONNECTING", Color::Yellow),
-            ConnectionStatus::Disconnected => ("○ DISCONNECTED", Color::Red),
+    fn render_header(
+        frame: &mut Frame,
+        area: Rect,
+        stats: &Stats,
+        connection: &ConnectionInfo,
+        username: Option<&str>,
+        run_state: RunState,
+        config: &Config,
+    ) {
+        let g = glyphs::current();
+        let timezone = config.display_timezone;
+        let status_text = match connection.status {
+            ConnectionStatus::Connected => (format!("{} CONNECTED", g.connected), Color::Green),
+            ConnectionStatus::Connecting => (format!("{} CONNECTING", g.connecting), Color::Yellow),
+            ConnectionStatus::Reconnecting => (format!("{} RECONNECTING", g.connecting), Color::Yellow),
+            ConnectionStatus::Disconnected => (format!("{} DISCONNECTED", g.disconnected), Color::Red),
         };
+        let separator = format!(" {} ", g.separator);
 
         let user_display = username.unwrap_or("Not logged in");
         let uptime = stats.format_uptime();
 
         let mut spans = vec![
             Span::styled(" MUDAE ", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
-            Span::styled("│ ", Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{} ", g.separator), Style::default().fg(Color::DarkGray)),
             Span::styled(user_display, Style::default().fg(Color::Cyan)),
-            Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+            Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)),
             Span::styled(status_text.0, Style::default().fg(status_text.1)),
         ];
 
-        if is_paused {
-            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-            spans.push(Span::styled("⏸  PAUSED", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK)));
+        if connection.status != ConnectionStatus::Connected {
+            if let Some(ref reason) = connection.last_disconnect_reason {
+                spans.push(Span::styled(format!(" ({})", reason), Style::default().fg(Color::DarkGray)));
+            }
+        }
+        if connection.reconnects > 0 {
+            spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(format!("Reconnects: {}", connection.reconnects), Style::default().fg(Color::Yellow)));
+        }
+
+        match run_state {
+            RunState::Halted => {
+                spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(format!("{}  HALTED", g.halted), Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD)));
+            }
+            RunState::AccountAlert => {
+                spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(
+                    format!("{}  HALTED: auth errors, check your Discord account", g.halted),
+                    Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+                ));
+            }
+            RunState::Captcha => {
+                spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(
+                    format!("{}  CAPTCHA: solve it in Discord, then press p", g.warning),
+                    Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+                ));
+            }
+            RunState::Paused => {
+                spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(format!("{}  PAUSED", g.paused), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK)));
+            }
+            RunState::Idle(until) => {
+                spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(
+                    format!("{}  IDLE until {}", g.idle, timezone.format(until, "%H:%M")),
+                    Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD),
+                ));
+            }
+            RunState::Running => {}
+        }
+
+        let pending = stats.get_pending_actions();
+        if pending > 0 {
+            spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(format!("Pending: {}", pending), Style::default().fg(Color::Yellow)));
         }
 
-        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-        spans.push(Span::styled(format!("⏱  {}", uptime), Style::default().fg(Color::White)));
+        spans.push(Span::styled(separator.clone(), Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(format!("{}  {}", g.uptime, uptime), Style::default().fg(Color::White)));
+        if let Some(left) = config.session_time_left(stats.start_time, Utc::now()) {
+            let action = match config.session_limit_action {
+                SessionLimitAction::Stop => "stops",
+                SessionLimitAction::Pause => "pauses",
+            };
+            let color = if left < chrono::Duration::minutes(10) { Color::Red } else { Color::Yellow };
+            spans.push(Span::styled(format!(" ({} in {})", action, format_duration(left)), Style::default().fg(color)));
+        }
 
         let header = Paragraph::new(Line::from(spans))
         .block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(if is_paused { Color::Yellow } else { Color::Magenta })),
+                .border_style(Style::default().fg(match run_state {
+                    RunState::Halted | RunState::AccountAlert | RunState::Captcha => Color::Red,
+                    RunState::Paused => Color::Yellow,
+                    _ => Color::Magenta,
+                })),
         );
 
         frame.render_widget(header, area);
     }
 
-    fn render_help_bar(frame: &mut Frame, area: Rect, is_paused: bool) {
+    fn render_help_bar(frame: &mut Frame, area: Rect, is_paused: bool, log_level: LogLevel, halt_key: &str) {
         let help = Paragraph::new(Line::from(vec![
             Span::styled(" ", Style::default()),
             Span::styled("[S]", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
@@ -798,8 +3288,24 @@ impl Tui {
             Span::styled(" Wishlist  ", Style::default().fg(Color::DarkGray)),
             Span::styled("[P]", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             Span::styled(if is_paused { " Resume  " } else { " Pause  " }, Style::default().fg(Color::DarkGray)),
-            Span::styled("[↑↓]", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::styled("[R]", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" Roll  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[C]", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            Span::styled(" Claim  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[M]", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            Span::styled(" Claims  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[U]", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            Span::styled(" Undo  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[O]", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            Span::styled(" Owned  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[T]", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+            Span::styled(" Series  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[L]", Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" Log: {}  ", log_level.as_str()), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("[{}]", glyphs::current().arrows), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
             Span::styled(" Scroll  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("[{}]", halt_key.to_uppercase()), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(" Halt  ", Style::default().fg(Color::DarkGray)),
             Span::styled("[Q]", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
             Span::styled(" Quit", Style::default().fg(Color::DarkGray)),
         ]))
@@ -810,9 +3316,9 @@ impl Tui {
 
     fn render_stats_panel(frame: &mut Frame, area: Rect, stats: &Stats, reset_timer: &str) {
         let claim_status = if stats.is_claim_available() {
-            Span::styled("✓  Available", Style::default().fg(Color::Green))
+            Span::styled(format!("{}  Available", glyphs::current().success), Style::default().fg(Color::Green))
         } else {
-            Span::styled("✗  On Cooldown", Style::default().fg(Color::Red))
+            Span::styled(format!("{}  On Cooldown", glyphs::current().error), Style::default().fg(Color::Red))
         };
 
         let rolls_remaining = stats.get_rolls_remaining();
@@ -822,6 +3328,14 @@ impl Tui {
             Span::styled("0", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
         };
 
+        let window_usage = match stats.get_window_rolls_used() {
+            Some(used) => Span::styled(
+                format!("  (used {}/{})", used, used + rolls_remaining),
+                Style::default().fg(Color::DarkGray),
+            ),
+            None => Span::raw(""),
+        };
+
         let reset_timer_span = if reset_timer == "Available" || reset_timer == "Unknown" {
             Span::styled(reset_timer.to_string(), Style::default().fg(Color::Yellow))
         } else {
@@ -836,6 +3350,10 @@ impl Tui {
             ListItem::new(Line::from(vec![
                 Span::styled("  Characters Claimed ", Style::default().fg(Color::White)),
                 Span::styled(stats.get_claimed().to_string(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                match stats.get_claim_retries() {
+                    0 => Span::raw(""),
+                    retries => Span::styled(format!("  ({} retries)", retries), Style::default().fg(Color::DarkGray)),
+                },
             ])),
             ListItem::new(Line::from(vec![
                 Span::styled("  Wishlist Matches   ", Style::default().fg(Color::White)),
@@ -845,6 +3363,19 @@ impl Tui {
                 Span::styled("  Kakera Collected   ", Style::default().fg(Color::White)),
                 Span::styled(stats.get_kakera().to_string(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
             ])),
+            ListItem::new(Line::from(vec![
+                Span::styled("  Kakera Received    ", Style::default().fg(Color::White)),
+                Span::styled(stats.get_kakera_received().to_string(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            ])),
+            ListItem::new(Line::from(vec![
+                Span::styled("  Value Claimed      ", Style::default().fg(Color::White)),
+                Span::styled(format!("{}ka", stats.get_total_kakera_claimed_value()), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                if stats.claimed_value_is_partial() {
+                    Span::styled("  (partial)", Style::default().fg(Color::DarkGray))
+                } else {
+                    Span::raw("")
+                },
+            ])),
             ListItem::new(Line::from(vec![
                 Span::styled("  Rolls Executed     ", Style::default().fg(Color::White)),
                 Span::styled(stats.get_rolls_executed().to_string(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
@@ -853,6 +3384,7 @@ impl Tui {
             ListItem::new(Line::from(vec![
                 Span::styled("  Rolls Remaining    ", Style::default().fg(Color::White)),
                 rolls_status,
+                window_usage,
             ])),
             ListItem::new(Line::from(vec![
                 Span::styled("  Next Roll Reset    ", Style::default().fg(Color::White)),
@@ -870,7 +3402,7 @@ impl Tui {
         ];
 
         let stats_list = List::new(stats_items).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
                 .title(" Statistics ")
@@ -880,7 +3412,15 @@ impl Tui {
         frame.render_widget(stats_list, area);
     }
 
-    fn render_config_panel(frame: &mut Frame, area: Rect, config: &Config, channel_infos: &[ChannelInfo]) {
+    fn render_config_panel(
+        frame: &mut Frame,
+        area: Rect,
+        config: &Config,
+        channel_infos: &[ChannelInfo],
+        next_daily: Option<DateTime<Local>>,
+        inaccessible_channels: &[u64],
+        kakera_power: Option<KakeraPower>,
+    ) {
         let auto_roll_status = Self::status_indicator(config.auto_roll);
         let auto_kakera_status = Self::status_indicator(config.auto_react_kakera);
         let auto_daily_status = Self::status_indicator(config.auto_daily);
@@ -895,6 +3435,28 @@ impl Tui {
             format!("{} channels", channel_infos.len())
         };
 
+        let channels_span = if inaccessible_channels.is_empty() {
+            Span::styled(channels_str, Style::default().fg(Color::Cyan))
+        } else {
+            Span::styled(
+                format!("{} {} inaccessible [A] retry", glyphs::current().warning, inaccessible_channels.len()),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )
+        };
+
+        let kakera_power_span = match kakera_power {
+            _ if !config.auto_react_kakera => Span::styled("off", Style::default().fg(Color::DarkGray)),
+            None => Span::styled("unknown", Style::default().fg(Color::DarkGray)),
+            Some(p) if config.kakera_react_allowed(kakera_power) => Span::styled(
+                format!("{}% reacting", p.power),
+                Style::default().fg(Color::Green),
+            ),
+            Some(p) => Span::styled(
+                format!("{}% conserving", p.power),
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+
         let config_items = vec![
             ListItem::new(Line::from(vec![
                 Span::styled("  Auto Roll          ", Style::default().fg(Color::White)),
@@ -904,10 +3466,25 @@ impl Tui {
                 Span::styled("  Auto Kakera        ", Style::default().fg(Color::White)),
                 auto_kakera_status,
             ])),
+            ListItem::new(Line::from(vec![
+                Span::styled("  Kakera Power       ", Style::default().fg(Color::White)),
+                kakera_power_span,
+            ])),
             ListItem::new(Line::from(vec![
                 Span::styled("  Auto Daily         ", Style::default().fg(Color::White)),
                 auto_daily_status,
             ])),
+            ListItem::new(Line::from(vec![
+                Span::styled("  Next Daily         ", Style::default().fg(Color::White)),
+                match (config.auto_daily, next_daily) {
+                    (false, _) => Span::styled("off", Style::default().fg(Color::DarkGray)),
+                    (true, None) => Span::styled("ready now", Style::default().fg(Color::Green)),
+                    (true, Some(at)) => Span::styled(
+                        config.display_timezone.format(at.with_timezone(&Utc), "%H:%M"),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                },
+            ])),
             ListItem::new(Line::from(vec![
                 Span::styled("  Wishlist           ", Style::default().fg(Color::White)),
                 wishlist_status,
@@ -923,12 +3500,12 @@ impl Tui {
             ])),
             ListItem::new(Line::from(vec![
                 Span::styled("  Channels           ", Style::default().fg(Color::White)),
-                Span::styled(channels_str, Style::default().fg(Color::Cyan)),
+                channels_span,
             ])),
         ];
 
         let config_list = List::new(config_items).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Yellow))
                 .title(" Configuration ")
@@ -943,6 +3520,7 @@ impl Tui {
         area: Rect,
         events: &[crate::stats::ActivityEvent],
         scroll_offset: u16,
+        timezone: DisplayTimezone,
     ) {
         let max_visible = (area.height.saturating_sub(2)) as usize;
         let total_events = events.len();
@@ -955,52 +3533,152 @@ impl Tui {
             0
         };
 
-        let visible_events: Vec<ListItem> = events
+        let visible_events: Vec<ListItem> = events
+            .iter()
+            .skip(start_idx)
+            .take(max_visible)
+            .map(|event| {
+                let time_str = timezone.format(event.timestamp, "%H:%M:%S");
+                let (icon, color) = Self::event_style(event.event_type);
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", time_str), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format!("{}  ", icon), Style::default().fg(color)),
+                    Span::styled(glyphs::text(&event.message), Style::default().fg(Color::White)),
+                ]))
+            })
+            .collect();
+
+        let activity_list = List::new(visible_events).block(
+            glyphs::block()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
+                .title(" Activity Log ")
+                .title_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        );
+
+        frame.render_widget(activity_list, area);
+    }
+
+    fn event_style(event_type: EventType) -> (&'static str, Color) {
+        let g = glyphs::current();
+        match event_type {
+            EventType::Info => (g.info, Color::Blue),
+            EventType::Success => (g.success, Color::Green),
+            EventType::Warning => (g.warning, Color::Yellow),
+            EventType::Error => (g.error, Color::Red),
+            EventType::Roll => (g.roll, Color::Cyan),
+            EventType::Claim => (g.claim, Color::Magenta),
+            EventType::Kakera => (g.kakera, Color::Yellow),
+            EventType::Wishlist => (g.wish, Color::Magenta),
+        }
+    }
+
+    fn render_logs(
+        frame: &mut Frame,
+        area: Rect,
+        events: &[crate::stats::ActivityEvent],
+        filter: &LogFilter,
+        scroll: usize,
+        cursor_visible: bool,
+        timezone: DisplayTimezone,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+
+        let mut filter_spans = vec![Span::styled(" ", Style::default())];
+        for (i, event_type) in EventType::all().into_iter().enumerate() {
+            let (icon, color) = Self::event_style(event_type);
+            let style = if filter.hidden_types.contains(&event_type) {
+                Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(color).add_modifier(Modifier::BOLD)
+            };
+            filter_spans.push(Span::styled(format!("[{}]", i + 1), Style::default().fg(Color::DarkGray)));
+            filter_spans.push(Span::styled(format!("{} {}  ", icon, event_type.label()), style));
+        }
+
+        let cursor = if filter.searching && cursor_visible { glyphs::current().cursor } else { "" };
+        let search_line = Line::from(vec![
+            Span::styled(" Search: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{}{}", filter.search, cursor),
+                Style::default().fg(if filter.searching { Color::Yellow } else { Color::White }),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(vec![Line::from(filter_spans), search_line]), chunks[0]);
+
+        let matching = filter.apply(events);
+
+        let max_visible = (chunks[1].height.saturating_sub(2)) as usize;
+        let max_scroll = matching.len().saturating_sub(max_visible);
+        let scroll = scroll.min(max_scroll);
+        let start_idx = max_scroll - scroll;
+
+        let items: Vec<ListItem> = matching
             .iter()
             .skip(start_idx)
             .take(max_visible)
             .map(|event| {
-                let time_str = event.timestamp.format("%H:%M:%S").to_string();
-                let (icon, color) = match event.event_type {
-                    EventType::Info => ("ℹ", Color::Blue),
-                    EventType::Success => ("✓", Color::Green),
-                    EventType::Warning => ("⚠", Color::Yellow),
-                    EventType::Error => ("✗", Color::Red),
-                    EventType::Roll => ("🎲", Color::Cyan),
-                    EventType::Claim => ("💖", Color::Magenta),
-                    EventType::Kakera => ("💎", Color::Yellow),
-                    EventType::Wishlist => ("⭐", Color::Magenta),
-                };
-
+                let (icon, color) = Self::event_style(event.event_type);
                 ListItem::new(Line::from(vec![
-                    Span::styled(format!(" {} ", time_str), Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        format!(" {} ", timezone.format(event.timestamp, "%H:%M:%S")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
                     Span::styled(format!("{}  ", icon), Style::default().fg(color)),
-                    Span::styled(&event.message, Style::default().fg(Color::White)),
+                    Span::styled(glyphs::text(&event.message), Style::default().fg(Color::White)),
                 ]))
             })
             .collect();
 
-        let activity_list = List::new(visible_events).block(
-            Block::default()
+        let title = if scroll > 0 {
+            format!(" Logs ({} of {}, scrolled {}) ", matching.len(), events.len(), scroll)
+        } else {
+            format!(" Logs ({} of {}) ", matching.len(), events.len())
+        };
+        let list = List::new(items).block(
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Green))
-                .title(" Activity Log ")
+                .title(title)
                 .title_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
         );
+        frame.render_widget(list, chunks[1]);
 
-        frame.render_widget(activity_list, area);
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled(" [1-8]", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(" Toggle type  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[/]", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(" Search  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[X]", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(" Clear filters  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("[{}/PgUp/PgDn]", glyphs::current().arrows), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::styled(" Scroll  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[G]", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::styled(" Latest  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[Esc]", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(" Back", Style::default().fg(Color::DarkGray)),
+        ]))
+        .style(Style::default().bg(Color::Black));
+        frame.render_widget(help, chunks[2]);
     }
 
-    fn render_channel_feed(frame: &mut Frame, area: Rect, activities: &[ChannelActivity]) {
+    fn render_channel_feed(frame: &mut Frame, area: Rect, activities: &[ChannelActivity], show_user_messages: bool) {
         let max_visible = (area.height.saturating_sub(2)) as usize;
+        let max_text_len = area.width.saturating_sub(6) as usize;
+        let g = glyphs::current();
         
         let visible_items: Vec<ListItem> = activities
             .iter()
             .rev()
+            .filter(|activity| show_user_messages || !matches!(activity, ChannelActivity::UserMessage { .. }))
             .take(max_visible)
             .map(|activity| {
                 match activity {
-                    ChannelActivity::Roll { character_name, kakera_value, is_wished, claimed } => {
+                    ChannelActivity::Roll { character_name, kakera_value, is_wished, claimed, decision } => {
                         let name_style = if *is_wished {
                             Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
                         } else if *claimed {
@@ -1010,11 +3688,11 @@ impl Tui {
                         };
 
                         let indicator = if *is_wished {
-                            "⭐"
+                            g.wish
                         } else if *claimed {
-                            "💖"
+                            g.claim
                         } else {
-                            "🎲"
+                            g.roll
                         };
 
                         let kakera_str = kakera_value
@@ -1025,24 +3703,47 @@ impl Tui {
                             Span::raw(" "),
                             Span::styled(indicator, Style::default().fg(Color::Cyan)),
                             Span::raw("  "),
-                            Span::styled(character_name.clone(), name_style),
+                            Span::styled(truncate_string(&glyphs::text(character_name), MAX_NAME_DISPLAY_LEN), name_style),
                             Span::styled(kakera_str, Style::default().fg(Color::Yellow)),
+                            Span::styled(
+                                format!("  {}", decision),
+                                Style::default().fg(if decision.is_claim() { Color::Green } else { Color::DarkGray }),
+                            ),
                         ]))
                     }
                     ChannelActivity::UserMessage { username, content } => {
                         ListItem::new(Line::from(vec![
                             Span::raw(" "),
-                            Span::styled(username.clone(), Style::default().fg(Color::Cyan)),
+                            Span::styled(truncate_string(&glyphs::text(username), MAX_NAME_DISPLAY_LEN), Style::default().fg(Color::Cyan)),
                             Span::styled(": ", Style::default().fg(Color::DarkGray)),
-                            Span::styled(content.clone(), Style::default().fg(Color::White)),
+                            Span::styled(truncate_string(&glyphs::text(content), max_text_len), Style::default().fg(Color::White)),
                         ]))
                     }
                     ChannelActivity::MudaeInfo { message } => {
                         ListItem::new(Line::from(vec![
                             Span::raw(" "),
-                            Span::styled("ℹ", Style::default().fg(Color::Blue)),
+                            Span::styled(g.info, Style::default().fg(Color::Blue)),
+                            Span::raw("  "),
+                            Span::styled(truncate_string(&glyphs::text(message), max_text_len), Style::default().fg(Color::DarkGray)),
+                        ]))
+                    }
+                    ChannelActivity::KakeraPower { power, can_react } => {
+                        let power_color = if *can_react { Color::Green } else { Color::Red };
+                        ListItem::new(Line::from(vec![
+                            Span::raw(" "),
+                            Span::styled(g.power, Style::default().fg(Color::Yellow)),
+                            Span::raw("  "),
+                            Span::styled("Kakera power ", Style::default().fg(Color::DarkGray)),
+                            Span::styled(format!("{}%", power), Style::default().fg(power_color).add_modifier(Modifier::BOLD)),
+                        ]))
+                    }
+                    ChannelActivity::Divorce { character_name } => {
+                        ListItem::new(Line::from(vec![
+                            Span::raw(" "),
+                            Span::styled(g.divorce, Style::default().fg(Color::Red)),
                             Span::raw("  "),
-                            Span::styled(message.clone(), Style::default().fg(Color::DarkGray)),
+                            Span::styled("Divorced ", Style::default().fg(Color::DarkGray)),
+                            Span::styled(truncate_string(&glyphs::text(character_name), MAX_NAME_DISPLAY_LEN), Style::default().fg(Color::White)),
                         ]))
                     }
                 }
@@ -1050,7 +3751,7 @@ impl Tui {
             .collect();
 
         let feed_list = List::new(visible_items).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Magenta))
                 .title(" Channel Feed ")
@@ -1060,15 +3761,24 @@ impl Tui {
         frame.render_widget(feed_list, area);
     }
 
-    fn render_settings(frame: &mut Frame, area: Rect, cursor: usize, config: &Config, message: &Option<(String, bool)>) {
+    fn render_settings(
+        frame: &mut Frame,
+        area: Rect,
+        cursor: usize,
+        config: &Config,
+        message: &Option<(String, bool)>,
+        unreachable_channels: &[u64],
+        token_fingerprint: &str,
+    ) {
         let items = SettingsItem::all();
+        let g = glyphs::current();
         
         let mut list_items: Vec<ListItem> = items
             .iter()
             .enumerate()
             .map(|(i, item)| {
                 let is_selected = i == cursor;
-                let prefix = if is_selected { "► " } else { "  " };
+                let prefix = if is_selected { format!("{} ", g.pointer) } else { "  ".to_string() };
                 let label_style = if is_selected {
                     Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
                 } else {
@@ -1080,24 +3790,162 @@ impl Tui {
                         SettingsItem::AutoRoll => config.auto_roll,
                         SettingsItem::AutoKakera => config.auto_react_kakera,
                         SettingsItem::AutoDaily => config.auto_daily,
+                        SettingsItem::DailyKakeraReact => config.daily_kakera_react,
                         SettingsItem::Wishlist => config.wishlist_enabled,
+                        SettingsItem::TrustMudaeWish => config.trust_mudae_wish,
+                        SettingsItem::ShuffleChannels => config.shuffle_channels,
+                        SettingsItem::IncludeThreads => config.include_threads,
+                        SettingsItem::IncludeDms => config.include_dms,
+                        SettingsItem::TrackSeenCharacters => config.track_seen_characters,
                         SettingsItem::FuzzyMatch => config.fuzzy_match,
+                        SettingsItem::FuzzyDedup => config.fuzzy_dedup,
+                        SettingsItem::AutoIdle => config.auto_idle,
+                        SettingsItem::StrictWishlistLoad => config.strict_wishlist_load,
+                        SettingsItem::AutoVerifyOnStart => config.auto_verify_on_start,
+                        SettingsItem::ShowUserMessages => config.show_user_messages,
+                        SettingsItem::AsciiMode => config.ascii_mode,
+                        SettingsItem::EncryptToken => config.encrypt_token,
+                        SettingsItem::AttemptClaimOnBelongsTo => config.attempt_claim_on_belongs_to,
+                        SettingsItem::AutoDivorceNonWished => config.auto_divorce_non_wished,
                         _ => false,
                     };
                     Self::status_indicator(enabled)
                 } else {
                     match item {
-                        SettingsItem::Token => Span::styled("********", Style::default().fg(Color::DarkGray)),
-                        SettingsItem::Channels => Span::styled("Press Enter to edit", Style::default().fg(Color::DarkGray)),
-                        SettingsItem::RollCommands => Span::styled(config.roll_commands.join(", "), Style::default().fg(Color::Cyan)),
+                        SettingsItem::Token => Span::styled(token_fingerprint.to_string(), Style::default().fg(Color::DarkGray)),
+                        SettingsItem::Channels => if unreachable_channels.is_empty() {
+                            Span::styled("Press Enter to edit", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(Self::unreachable_channels_label(unreachable_channels), Style::default().fg(Color::Red))
+                        },
+                        SettingsItem::RollCommands => Span::styled(config.roll_commands_input(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::RollYields => if config.roll_command_yields.is_empty() {
+                            Span::styled("1 per command", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(config.roll_yields_input(), Style::default().fg(Color::Cyan))
+                        },
                         SettingsItem::Cooldown => Span::styled(format!("{}s", config.roll_cooldown_seconds), Style::default().fg(Color::Cyan)),
+                        SettingsItem::DefaultRolls => Span::styled(config.default_rolls.to_string(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::ScheduledCommands => if config.scheduled_commands.is_empty() {
+                            Span::styled("None", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("{} scheduled", config.scheduled_commands.len()), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::RollSpread => Span::styled(config.roll_spread.as_str(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::DisplayTimezone => Span::styled(config.display_timezone.as_str(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::MudaeLanguage => Span::styled(config.mudae_language.label(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::GenderFilter => match config.claim_gender_filter {
+                            Some(gender) => Span::styled(gender.as_str(), Style::default().fg(Color::Cyan)),
+                            None => Span::styled("Any", Style::default().fg(Color::DarkGray)),
+                        },
+                        SettingsItem::ClaimKakeraMin => if config.claim_kakera_min == 0 {
+                            Span::styled("None", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(config.claim_kakera_min.to_string(), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::ClaimKakeraMax => if config.claim_kakera_max == 0 {
+                            Span::styled("None", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(config.claim_kakera_max.to_string(), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::ClaimWeights => Span::styled(
+                            format!("priority x{}, kakera x{}", config.claim_priority_weight, config.claim_kakera_weight),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                        SettingsItem::ClaimRules => Span::styled(config.claim_rules.to_string(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::HaltKey => Span::styled(config.halt_key.to_uppercase(), Style::default().fg(Color::Red)),
+                        SettingsItem::RemoteControlUsers => if config.remote_control_user_ids.is_empty() {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("{} users", config.remote_control_user_ids.len()), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::MarkWishedEmoji => match config.mark_wished_emoji {
+                            Some(ref emoji) => Span::styled(emoji.clone(), Style::default().fg(Color::Cyan)),
+                            None => Span::styled("off", Style::default().fg(Color::DarkGray)),
+                        },
+                        SettingsItem::ClaimEmoji => Span::styled(config.claim_emoji.clone(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::VerifyDelay => Span::styled(
+                            format!("{}-{}", config.verify_delay_min_ms, config.verify_delay_max_ms),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                        SettingsItem::AutoDivorceMaxKakera => Span::styled(
+                            format!("< {} ka", config.auto_divorce_max_kakera),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                        SettingsItem::AutoDivorceCommands => Span::styled(config.auto_divorce_commands.join(", "), Style::default().fg(Color::Cyan)),
+                        SettingsItem::KakeraPowerMin => if config.kakera_power_min == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("{}%", config.kakera_power_min), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::ClaimRetries => if config.claim_retries == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(
+                                format!("{} within {}ms", config.claim_retries, config.claim_retry_window_ms),
+                                Style::default().fg(Color::Cyan),
+                            )
+                        },
+                        SettingsItem::RollDelays => Span::styled(
+                            format!("{}s / {}s after reset", config.startup_roll_delay_seconds, config.post_reset_roll_delay_seconds),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                        SettingsItem::InterChannelDelay => if config.inter_channel_delay_ms == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("{} ms", config.inter_channel_delay_ms), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::SessionLimit => match config.max_session_hours {
+                            Some(hours) => Span::styled(format!("{}h", hours), Style::default().fg(Color::Cyan)),
+                            None => Span::styled("Off", Style::default().fg(Color::DarkGray)),
+                        },
+                        SettingsItem::SessionLimitAction => Span::styled(config.session_limit_action.as_str(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::DelayDistribution => Span::styled(config.delay_distribution.as_str(), Style::default().fg(Color::Cyan)),
+                        SettingsItem::ClaimConfirmTimeout => if config.claim_confirm_timeout_seconds == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("{}s", config.claim_confirm_timeout_seconds), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::ClaimGrace => if config.claim_grace_seconds == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("{}s", config.claim_grace_seconds), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::FailureLimit => if config.max_consecutive_failures == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("After {} failures", config.max_consecutive_failures), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::AuthErrorHalt => if config.auth_error_halt_count == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("After {} in 2m", config.auth_error_halt_count), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::CatchupMessages => if config.catchup_messages == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("{} messages", config.catchup_messages), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::ChannelRefresh => if config.channel_refresh_minutes == 0 {
+                            Span::styled("Off", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(format!("Every {} min", config.channel_refresh_minutes), Style::default().fg(Color::Cyan))
+                        },
+                        SettingsItem::StatsSaveInterval => Span::styled(format!("Every {}s", config.stats_save_interval_seconds), Style::default().fg(Color::Cyan)),
+                        SettingsItem::TuiTick => Span::styled(format!("{} ms", config.tui_tick_ms), Style::default().fg(Color::Cyan)),
+                        SettingsItem::DailyCommands => Span::styled(config.daily_commands.join(", "), Style::default().fg(Color::Cyan)),
+                        SettingsItem::ReconnectCommands => if config.on_reconnect_commands.is_empty() {
+                            Span::styled("None", Style::default().fg(Color::DarkGray))
+                        } else {
+                            Span::styled(config.on_reconnect_commands.join(", "), Style::default().fg(Color::Cyan))
+                        },
                         _ => Span::raw(""),
                     }
                 };
 
                 ListItem::new(Line::from(vec![
                     Span::styled(prefix, label_style),
-                    Span::styled(format!("{:<22}", item.label()), label_style),
+                    Span::styled(format!("{:<22}", glyphs::text(item.label())), label_style),
                     value,
                 ]))
             })
@@ -1107,19 +3955,19 @@ impl Tui {
             list_items.push(ListItem::new(Line::from("")));
             let color = if *success { Color::Green } else { Color::Red };
             list_items.push(ListItem::new(Line::from(Span::styled(
-                format!("  {}", msg),
+                format!("  {}", glyphs::text(msg)),
                 Style::default().fg(color),
             ))));
         }
 
         list_items.push(ListItem::new(Line::from("")));
         list_items.push(ListItem::new(Line::from(Span::styled(
-            "  ↑↓ Navigate  •  Enter/Space Toggle  •  Esc Close",
+            format!("  {} Navigate  {}  Enter/Space Toggle  {}  Esc Close", g.arrows, g.bullet, g.bullet),
             Style::default().fg(Color::DarkGray),
         ))));
 
         let list = List::new(list_items).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Yellow))
                 .title(" Settings ")
@@ -1139,7 +3987,8 @@ impl Tui {
         cursor: bool,
         message: &Option<(String, bool)>,
     ) {
-        let cursor_char = if cursor { "▌" } else { " " };
+        let g = glyphs::current();
+        let cursor_char = if cursor { g.cursor } else { " " };
         let display = if masked && !input.is_empty() {
             "*".repeat(input.len().min(40))
         } else {
@@ -1148,7 +3997,7 @@ impl Tui {
 
         let mut text = vec![
             Line::from(""),
-            Line::from(Span::styled(format!("  {}", prompt), Style::default().fg(Color::White))),
+            Line::from(Span::styled(format!("  {}", glyphs::text(prompt)), Style::default().fg(Color::White))),
             Line::from(""),
             Line::from(vec![
                 Span::styled("  > ", Style::default().fg(Color::Yellow)),
@@ -1160,17 +4009,17 @@ impl Tui {
 
         if let Some((msg, success)) = message {
             let color = if *success { Color::Green } else { Color::Red };
-            text.push(Line::from(Span::styled(format!("  {}", msg), Style::default().fg(color))));
+            text.push(Line::from(Span::styled(format!("  {}", glyphs::text(msg)), Style::default().fg(color))));
         }
 
         text.push(Line::from(""));
-        text.push(Line::from(Span::styled("  Enter=save  •  Esc=cancel", Style::default().fg(Color::DarkGray))));
+        text.push(Line::from(Span::styled(format!("  Enter=save  {}  Esc=cancel", g.bullet), Style::default().fg(Color::DarkGray))));
 
         let paragraph = Paragraph::new(text).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
-                .title(format!(" {} ", title))
+                .title(format!(" {} ", glyphs::text(title)))
                 .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         );
 
@@ -1182,6 +4031,7 @@ impl Tui {
         area: Rect,
         characters: &[WishedCharacter],
         cursor: usize,
+        verification_progress: Option<(usize, usize)>,
         message: &Option<(String, bool)>,
     ) {
         let inner = Layout::default()
@@ -1194,18 +4044,28 @@ impl Tui {
             .margin(1)
             .split(area);
 
-        let title_block = Block::default()
+        let title_block = glyphs::block()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Magenta))
-            .title(" ♥ Wishlist Manager ")
+            .title(format!(" {} Wishlist Manager ", glyphs::current().heart))
             .title_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD));
         frame.render_widget(title_block, area);
 
-        let header = Paragraph::new(Line::from(vec![
+        let mut header_spans = vec![
             Span::styled(format!(" {} characters ", characters.len()), Style::default().fg(Color::Cyan)),
-            Span::styled("│", Style::default().fg(Color::DarkGray)),
-            Span::styled(" A=Add  D=Delete  Esc=Back ", Style::default().fg(Color::DarkGray)),
-        ]));
+            Span::styled(glyphs::current().separator, Style::default().fg(Color::DarkGray)),
+        ];
+        match verification_progress {
+            Some((done, total)) => header_spans.push(Span::styled(
+                format!(" Verifying {}/{}  Esc=Cancel ", done, total),
+                Style::default().fg(Color::Yellow),
+            )),
+            None => header_spans.push(Span::styled(
+                " A=Add  I=Import  D=Delete  W=Soft/Hard  V=Verify  Esc=Back ",
+                Style::default().fg(Color::DarkGray),
+            )),
+        }
+        let header = Paragraph::new(Line::from(header_spans));
         frame.render_widget(header, inner[0]);
 
         if characters.is_empty() {
@@ -1227,22 +4087,24 @@ impl Tui {
                 .map(|(i, c)| {
                     let actual_i = start + i;
                     let is_selected = actual_i == cursor;
-                    let prefix = if is_selected { "► " } else { "  " };
+                    let prefix = if is_selected { format!("{} ", glyphs::current().pointer) } else { "  ".to_string() };
                     
                     let verify_icon = if c.verified {
-                        Span::styled("✓  ", Style::default().fg(Color::Green))
+                        Span::styled(format!("{}  ", glyphs::current().success), Style::default().fg(Color::Green))
                     } else {
-                        Span::styled("?  ", Style::default().fg(Color::Yellow))
+                        Span::styled("?  ".to_string(), Style::default().fg(Color::Yellow))
                     };
 
                     let name_style = if is_selected {
                         Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                    } else if c.soft {
+                        Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC)
                     } else {
                         Style::default().fg(Color::White)
                     };
 
                     let series_display = c.series.as_ref()
-                        .map(|s| format!(" ({})", s))
+                        .map(|s| format!(" ({})", truncate_string(s, MAX_SERIES_DISPLAY_LEN)))
                         .unwrap_or_default();
 
                     let priority_display = if c.priority > 0 {
@@ -1254,9 +4116,10 @@ impl Tui {
                     ListItem::new(Line::from(vec![
                         Span::styled(prefix, name_style),
                         verify_icon,
-                        Span::styled(&c.name, name_style),
+                        Span::styled(truncate_string(&c.name, MAX_NAME_DISPLAY_LEN), name_style),
                         Span::styled(series_display, Style::default().fg(Color::DarkGray)),
                         Span::styled(priority_display, Style::default().fg(Color::Cyan)),
+                        Span::styled(if c.soft { " [soft]" } else { "" }, Style::default().fg(Color::Blue)),
                     ]))
                 })
                 .collect();
@@ -1266,18 +4129,257 @@ impl Tui {
         }
 
         let mut footer_text = vec![
-            Span::styled(" ↑↓=Navigate  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(format!(" {}=Navigate  ", glyphs::current().arrows), Style::default().fg(Color::DarkGray)),
         ];
         
         if let Some((msg, success)) = message {
             let color = if *success { Color::Green } else { Color::Red };
-            footer_text.push(Span::styled(msg.clone(), Style::default().fg(color)));
+            footer_text.push(Span::styled(glyphs::text(msg).into_owned(), Style::default().fg(color)));
+        }
+
+        let footer = Paragraph::new(Line::from(footer_text));
+        frame.render_widget(footer, inner[2]);
+    }
+
+    fn render_import_preview(
+        frame: &mut Frame,
+        area: Rect,
+        entries: &[(ImportEntry, bool)],
+        cursor: usize,
+        message: &Option<(String, bool)>,
+    ) {
+        let inner = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(5),
+                Constraint::Length(3),
+            ])
+            .margin(1)
+            .split(area);
+
+        let title_block = glyphs::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(format!(" {} Import Preview ", glyphs::current().heart))
+            .title_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD));
+        frame.render_widget(title_block, area);
+
+        let count = |status: ImportStatus| entries.iter().filter(|(e, _)| e.status == status).count();
+        let selected = entries.iter().filter(|(_, selected)| *selected).count();
+        let header = Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {} new ", count(ImportStatus::New)), Style::default().fg(Color::Green)),
+            Span::styled(format!(" {} near-duplicate ", count(ImportStatus::NearDuplicate)), Style::default().fg(Color::Yellow)),
+            Span::styled(format!(" {} duplicate ", count(ImportStatus::Duplicate)), Style::default().fg(Color::DarkGray)),
+            Span::styled(glyphs::current().separator, Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!(" {} selected  Space=Toggle  Enter=Import  Esc=Cancel ", selected),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        frame.render_widget(header, inner[0]);
+
+        let visible_height = inner[1].height.saturating_sub(2) as usize;
+        let start = cursor.saturating_sub(visible_height.saturating_sub(1));
+        let end = (start + visible_height).min(entries.len());
+
+        let list_items: Vec<ListItem> = entries[start..end]
+            .iter()
+            .enumerate()
+            .map(|(i, (entry, selected))| {
+                let is_selected = start + i == cursor;
+                let prefix = if is_selected { format!("{} ", glyphs::current().pointer) } else { "  ".to_string() };
+                let checkbox = if *selected { "[x] " } else { "[ ] " };
+
+                let (status_text, status_color) = match entry.status {
+                    ImportStatus::New => ("  new".to_string(), Color::Green),
+                    ImportStatus::NearDuplicate => (
+                        format!("  looks like {}", entry.existing.as_deref().unwrap_or("?")),
+                        Color::Yellow,
+                    ),
+                    ImportStatus::Duplicate => ("  already in wishlist".to_string(), Color::DarkGray),
+                };
+
+                let name_style = if is_selected {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else if entry.status == ImportStatus::Duplicate {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+
+                let series_display = entry.character.series.as_ref()
+                    .map(|s| format!(" ({})", truncate_string(s, MAX_SERIES_DISPLAY_LEN)))
+                    .unwrap_or_default();
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, name_style),
+                    Span::styled(checkbox, Style::default().fg(Color::Cyan)),
+                    Span::styled(truncate_string(&entry.character.name, MAX_NAME_DISPLAY_LEN), name_style),
+                    Span::styled(series_display, Style::default().fg(Color::DarkGray)),
+                    Span::styled(status_text, Style::default().fg(status_color)),
+                ]))
+            })
+            .collect();
+        frame.render_widget(List::new(list_items), inner[1]);
+
+        let mut footer_text = vec![
+            Span::styled(format!(" {}=Navigate  ", glyphs::current().arrows), Style::default().fg(Color::DarkGray)),
+        ];
+        if let Some((msg, success)) = message {
+            let color = if *success { Color::Green } else { Color::Red };
+            footer_text.push(Span::styled(glyphs::text(msg).into_owned(), Style::default().fg(color)));
+        }
+        frame.render_widget(Paragraph::new(Line::from(footer_text)), inner[2]);
+    }
+
+    fn render_claims(
+        frame: &mut Frame,
+        area: Rect,
+        claims: &[ClaimRecord],
+        cursor: usize,
+        message: &Option<(String, bool)>,
+        timezone: DisplayTimezone,
+    ) {
+        let inner = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(5),
+                Constraint::Length(3),
+            ])
+            .margin(1)
+            .split(area);
+
+        let title_block = glyphs::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(format!(" {} Claimed Characters ", glyphs::current().ring))
+            .title_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD));
+        frame.render_widget(title_block, area);
+
+        let total_kakera: u64 = claims.iter().filter_map(|c| c.kakera_value).map(u64::from).sum();
+        let header = Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {} claims ", claims.len()), Style::default().fg(Color::Cyan)),
+            Span::styled(glyphs::current().separator, Style::default().fg(Color::DarkGray)),
+            Span::styled(format!(" {} kakera ", total_kakera), Style::default().fg(Color::Yellow)),
+            Span::styled(glyphs::current().separator, Style::default().fg(Color::DarkGray)),
+            Span::styled(" Esc=Back ", Style::default().fg(Color::DarkGray)),
+        ]));
+        frame.render_widget(header, inner[0]);
+
+        if claims.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled("  No claims recorded yet", Style::default().fg(Color::DarkGray))),
+            ]);
+            frame.render_widget(empty, inner[1]);
+        } else {
+            let visible_height = inner[1].height.saturating_sub(2) as usize;
+            let start = cursor.saturating_sub(visible_height.saturating_sub(1));
+            let end = (start + visible_height).min(claims.len());
+
+            let list_items: Vec<ListItem> = claims[start..end]
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    let is_selected = start + i == cursor;
+                    let prefix = if is_selected { format!("{} ", glyphs::current().pointer) } else { "  ".to_string() };
+
+                    let name_style = if is_selected {
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::White)
+                    };
+
+                    let series_display = if c.series.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", c.series)
+                    };
+
+                    let kakera_display = c.kakera_value
+                        .map(|k| format!(" {}ka", k))
+                        .unwrap_or_default();
+
+                    ListItem::new(Line::from(vec![
+                        Span::styled(prefix, name_style),
+                        Span::styled(timezone.format(c.claimed_at, "%Y-%m-%d %H:%M  "), Style::default().fg(Color::DarkGray)),
+                        Span::styled(&c.character_name, name_style),
+                        Span::styled(series_display, Style::default().fg(Color::DarkGray)),
+                        Span::styled(kakera_display, Style::default().fg(Color::Yellow)),
+                    ]))
+                })
+                .collect();
+
+            let list = List::new(list_items);
+            frame.render_widget(list, inner[1]);
+        }
+
+        let mut footer_text = vec![
+            Span::styled(format!(" {}=Navigate  ", glyphs::current().arrows), Style::default().fg(Color::DarkGray)),
+        ];
+
+        if let Some((msg, success)) = message {
+            let color = if *success { Color::Green } else { Color::Red };
+            footer_text.push(Span::styled(glyphs::text(msg).into_owned(), Style::default().fg(color)));
         }
 
         let footer = Paragraph::new(Line::from(footer_text));
         frame.render_widget(footer, inner[2]);
     }
 
+    fn render_series_stats(
+        frame: &mut Frame,
+        area: Rect,
+        top_rolled: &[(String, u64)],
+        claims_per_series: &[(String, u64)],
+    ) {
+        let block = glyphs::block()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(format!(" {} Series Stats (Esc=Back) ", glyphs::current().books))
+            .title_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD));
+        frame.render_widget(block, area);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .margin(1)
+            .split(area);
+
+        let sections = [
+            (" Most Rolled (session) ", top_rolled, Color::Cyan),
+            (" Claims per Series ", claims_per_series, Color::Yellow),
+        ];
+        for ((title, entries, color), column) in sections.into_iter().zip(columns.iter()) {
+            let items: Vec<ListItem> = if entries.is_empty() {
+                vec![ListItem::new(Span::styled("  No data yet", Style::default().fg(Color::DarkGray)))]
+            } else {
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (series, count))| {
+                        ListItem::new(Line::from(vec![
+                            Span::styled(format!(" {:>2}. ", i + 1), Style::default().fg(Color::DarkGray)),
+                            Span::styled(format!("{:>4} ", count), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                            Span::styled(glyphs::text(series), Style::default().fg(Color::White)),
+                        ]))
+                    })
+                    .collect()
+            };
+
+            let list = List::new(items).block(
+                glyphs::block()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray))
+                    .title(title)
+                    .title_style(Style::default().fg(color)),
+            );
+            frame.render_widget(list, *column);
+        }
+    }
+
     fn render_search_character(
         frame: &mut Frame,
         area: Rect,
@@ -1286,11 +4388,12 @@ impl Tui {
         cursor: bool,
         message: &Option<(String, bool)>,
     ) {
-        let cursor_char = if cursor && !searching { "▌" } else { " " };
+        let g = glyphs::current();
+        let cursor_char = if cursor && !searching { g.cursor } else { " " };
 
         let mut text = vec![
             Line::from(""),
-            Line::from(Span::styled("  🔍 Search & Add Character", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(format!("  {} Search & Add Character", g.search), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
             Line::from(""),
             Line::from(Span::styled("  Enter character name to search:", Style::default().fg(Color::White))),
             Line::from(""),
@@ -1303,19 +4406,19 @@ impl Tui {
         ];
 
         if searching {
-            text.push(Line::from(Span::styled("  ◐  Searching...", Style::default().fg(Color::Yellow).add_modifier(Modifier::SLOW_BLINK))));
+            text.push(Line::from(Span::styled(format!("  {}  Searching...", g.connecting), Style::default().fg(Color::Yellow).add_modifier(Modifier::SLOW_BLINK))));
         }
 
         if let Some((msg, success)) = message {
             let color = if *success { Color::Green } else { Color::Red };
-            text.push(Line::from(Span::styled(format!("  {}", msg), Style::default().fg(color))));
+            text.push(Line::from(Span::styled(format!("  {}", glyphs::text(msg)), Style::default().fg(color))));
         }
 
         text.push(Line::from(""));
-        text.push(Line::from(Span::styled("  Enter=Search  •  Esc=Cancel", Style::default().fg(Color::DarkGray))));
+        text.push(Line::from(Span::styled(format!("  Enter=Search  {}  Esc=Cancel", g.bullet), Style::default().fg(Color::DarkGray))));
 
         let paragraph = Paragraph::new(text).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Magenta))
                 .title(" Add Character ")
@@ -1331,17 +4434,18 @@ impl Tui {
         result: &SearchResult,
         message: &Option<(String, bool)>,
     ) {
+        let max_len = area.width.saturating_sub(14) as usize;
         let mut text = vec![
             Line::from(""),
-            Line::from(Span::styled("  ✓  Character Found!", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(format!("  {}  Character Found!", glyphs::current().success), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))),
             Line::from(""),
             Line::from(vec![
                 Span::styled("  Name:   ", Style::default().fg(Color::DarkGray)),
-                Span::styled(&result.name, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Span::styled(truncate_string(&result.name, max_len), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
             ]),
             Line::from(vec![
                 Span::styled("  Series: ", Style::default().fg(Color::DarkGray)),
-                Span::styled(&result.series, Style::default().fg(Color::Cyan)),
+                Span::styled(truncate_string(&result.series, max_len), Style::default().fg(Color::Cyan)),
             ]),
         ];
 
@@ -1353,14 +4457,14 @@ impl Tui {
         }
 
         text.push(Line::from(""));
-        text.push(Line::from(Span::styled("  ─────────────────────────────", Style::default().fg(Color::DarkGray))));
+        text.push(Line::from(Span::styled(format!("  {}", glyphs::current().rule.repeat(29)), Style::default().fg(Color::DarkGray))));
         text.push(Line::from(""));
         text.push(Line::from(Span::styled("  Add this character to your wishlist?", Style::default().fg(Color::White))));
         text.push(Line::from(""));
 
         if let Some((msg, success)) = message {
             let color = if *success { Color::Green } else { Color::Red };
-            text.push(Line::from(Span::styled(format!("  {}", msg), Style::default().fg(color))));
+            text.push(Line::from(Span::styled(format!("  {}", glyphs::text(msg)), Style::default().fg(color))));
             text.push(Line::from(""));
         }
 
@@ -1373,7 +4477,7 @@ impl Tui {
         ]));
 
         let paragraph = Paragraph::new(text).block(
-            Block::default()
+            glyphs::block()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Green))
                 .title(" Confirm Character ")
@@ -1383,11 +4487,81 @@ impl Tui {
         frame.render_widget(paragraph, area);
     }
 
+    fn render_confirm_clear_halt(frame: &mut Frame, area: Rect, message: &Option<(String, bool)>) {
+        let mut text = vec![
+            Line::from(""),
+            Line::from(Span::styled(format!("  {}  Outbound actions are HALTED", glyphs::current().halted), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+            Line::from(""),
+            Line::from(Span::styled("  Clear the halt and resume sending messages,", Style::default().fg(Color::White))),
+            Line::from(Span::styled("  reactions and button clicks?", Style::default().fg(Color::White))),
+            Line::from(""),
+        ];
+
+        if let Some((msg, success)) = message {
+            let color = if *success { Color::Green } else { Color::Red };
+            text.push(Line::from(Span::styled(format!("  {}", glyphs::text(msg)), Style::default().fg(color))));
+            text.push(Line::from(""));
+        }
+
+        text.push(Line::from(vec![
+            Span::styled("  ", Style::default()),
+            Span::styled("[Y]", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::styled(" Resume   ", Style::default().fg(Color::DarkGray)),
+            Span::styled("[N]", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(" Stay halted", Style::default().fg(Color::DarkGray)),
+        ]));
+
+        let paragraph = Paragraph::new(text).block(
+            glyphs::block()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(" Clear Halt ")
+                .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        );
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_confirm_divorce(frame: &mut Frame, area: Rect, claim: Option<&ClaimRecord>, commands: &[String]) {
+        let name = claim.map(|c| c.character_name.as_str()).unwrap_or("?");
+        let series = claim.map(|c| c.series.as_str()).unwrap_or("");
+        let channel = claim.map(|c| c.channel_id.to_string()).unwrap_or_default();
+        let text = vec![
+            Line::from(""),
+            Line::from(Span::styled(format!("  {}  Divorce {}?", glyphs::current().warning, glyphs::text(name)), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(format!("  {}", series), Style::default().fg(Color::DarkGray))),
+            Line::from(""),
+            Line::from(Span::styled(format!("  Sends in channel {}:", channel), Style::default().fg(Color::White))),
+            Line::from(Span::styled(
+                format!("  {}", commands.iter().map(|c| c.replace("{name}", name)).collect::<Vec<_>>().join(", ")),
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("  ", Style::default()),
+                Span::styled("[Y]", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                Span::styled(" Divorce   ", Style::default().fg(Color::DarkGray)),
+                Span::styled("[N]", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                Span::styled(" Keep", Style::default().fg(Color::DarkGray)),
+            ]),
+        ];
+
+        let paragraph = Paragraph::new(text).block(
+            glyphs::block()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(" Undo Last Claim ")
+                .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        );
+
+        frame.render_widget(paragraph, area);
+    }
+
     fn status_indicator(enabled: bool) -> Span<'static> {
         if enabled {
-            Span::styled("✓  Enabled", Style::default().fg(Color::Green))
+            Span::styled(format!("{}  Enabled", glyphs::current().success), Style::default().fg(Color::Green))
         } else {
-            Span::styled("✗  Disabled", Style::default().fg(Color::Red))
+            Span::styled(format!("{}  Disabled", glyphs::current().error), Style::default().fg(Color::Red))
         }
     }
 
@@ -1395,28 +4569,54 @@ impl Tui {
         client: Arc<crate::client::DiscordClient>,
         db: Arc<Database>,
         channel_ids: Vec<u64>,
-    ) {
-        for channel_id in channel_ids {
-            if let Ok(channel) = client.get_channel(channel_id).await {
-                let guild_name = if let Some(guild_id_str) = &channel.guild_id {
-                    if let Ok(guild_id) = guild_id_str.parse::<u64>() {
-                        client.get_guild(guild_id).await.ok().map(|g| g.name)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-                
-                if let Err(e) = db.update_channel_name(
-                    channel_id,
-                    channel.name.as_deref().unwrap_or("Unknown"),
-                    guild_name.as_deref(),
-                ) {
-                    tracing::error!("Failed to update channel name: {}", e);
+        spacing: Duration,
+    ) -> Vec<u64> {
+        let mut unreachable = Vec::new();
+        let mut guild_names: HashMap<u64, Option<String>> = HashMap::new();
+        for (i, channel_id) in channel_ids.into_iter().enumerate() {
+            if i > 0 && !spacing.is_zero() {
+                tokio::time::sleep(spacing).await;
+            }
+            let channel = match client.get_channel(channel_id).await {
+                Ok(channel) => channel,
+                Err(crate::client::ClientError::Forbidden { .. } | crate::client::ClientError::NotFound { .. }) => {
+                    unreachable.push(channel_id);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to look up channel {}: {}", channel_id, e);
+                    continue;
                 }
+            };
+            let guild_name = match channel.guild_id.as_deref().and_then(|id| id.parse::<u64>().ok()) {
+                Some(guild_id) => match guild_names.get(&guild_id) {
+                    Some(name) => name.clone(),
+                    None => {
+                        let name = client.get_guild(guild_id).await.ok().map(|g| g.name);
+                        guild_names.insert(guild_id, name.clone());
+                        name
+                    }
+                },
+                None => None,
+            };
+            
+            if let Err(e) = db.update_channel_name(
+                channel_id,
+                channel.name.as_deref().unwrap_or("Unknown"),
+                guild_name.as_deref(),
+            ) {
+                tracing::error!("Failed to update channel name: {}", e);
             }
         }
+        unreachable
+    }
+
+    fn unreachable_channels_label(channel_ids: &[u64]) -> String {
+        channel_ids
+            .iter()
+            .map(|id| format!("{} {}: no access", glyphs::current().warning, id))
+            .collect::<Vec<_>>()
+            .join(", ")
     }
 
     pub fn cleanup(&mut self) -> Result<()> {
@@ -1462,14 +4662,39 @@ pub async fn run_tui(
     config: Config,
     db: Arc<Database>,
     wishlist: Arc<WishlistManager>,
+    wishlist_verifier: Arc<WishlistVerifier>,
     search_tx: SearchRequestSender,
     shutdown_rx: watch::Receiver<bool>,
     channel_infos: Vec<ChannelInfo>,
     client: Option<crate::client::DiscordClient>,
+    executor: Arc<CommandExecutor>,
+    log_handle: LogHandle,
+    log_level: LogLevel,
 ) -> Result<()> {
+    let previous_hook = Arc::new(std::panic::take_hook());
+    let hook = previous_hook.clone();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        hook(info);
+    }));
+
     let client_arc = client.map(Arc::new);
-    let mut tui = Tui::new(stats, config, db, wishlist, search_tx, shutdown_rx, channel_infos, client_arc)?;
-    tui.run().await?;
-    tui.cleanup()?;
-    Ok(())
+    let result = async {
+        let mut tui = Tui::new(stats, config, db, wishlist, wishlist_verifier, search_tx, shutdown_rx, channel_infos, client_arc, executor, log_handle, log_level)?;
+        tui.run().await?;
+        tui.cleanup()
+    }
+    .await;
+
+    drop(std::panic::take_hook());
+    if let Ok(previous_hook) = Arc::try_unwrap(previous_hook) {
+        std::panic::set_hook(previous_hook);
+    }
+
+    result
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, cursor::Show);
 }
@@ -1,8 +1,17 @@
 #![allow(dead_code)]
 
-use chrono::{DateTime, Duration, Utc};
+use crate::config::DelayDistribution;
+use chrono::{DateTime, Duration, Local, NaiveTime, Utc};
+use rand::Rng;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
+const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+const GAUSSIAN_SIGMAS_PER_RANGE: f64 = 6.0;
+const GAUSSIAN_MAX_DRAWS: usize = 8;
+
+static GAUSSIAN_DELAYS: AtomicBool = AtomicBool::new(false);
+
 pub fn format_duration(duration: Duration) -> String {
     let total_secs = duration.num_seconds();
     let hours = total_secs / 3600;
@@ -18,6 +27,10 @@ pub fn format_duration(duration: Duration) -> String {
     }
 }
 
+pub fn snowflake_timestamp(id: u64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(((id >> 22) + DISCORD_EPOCH_MS) as i64)
+}
+
 pub fn format_timestamp(dt: DateTime<Utc>) -> String {
     dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
 }
@@ -38,14 +51,58 @@ pub fn parse_time(time_str: &str) -> Option<(u32, u32)> {
     Some((hours, minutes))
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Humanizer {
+    pub distribution: DelayDistribution,
+}
+
+impl Humanizer {
+    pub fn new(distribution: DelayDistribution) -> Self {
+        Self { distribution }
+    }
+
+    pub fn current() -> Self {
+        if GAUSSIAN_DELAYS.load(Ordering::Relaxed) {
+            Self::new(DelayDistribution::Gaussian)
+        } else {
+            Self::new(DelayDistribution::Uniform)
+        }
+    }
+
+    pub fn set_current(distribution: DelayDistribution) {
+        GAUSSIAN_DELAYS.store(distribution == DelayDistribution::Gaussian, Ordering::Relaxed);
+    }
+
+    pub fn delay(&self, min_ms: u64, max_ms: u64) -> std::time::Duration {
+        self.delay_with(&mut rand::thread_rng(), min_ms, max_ms)
+    }
+
+    fn delay_with(&self, rng: &mut impl Rng, min_ms: u64, max_ms: u64) -> std::time::Duration {
+        let range = max_ms.saturating_sub(min_ms);
+        let random_offset = match self.distribution {
+            _ if range == 0 => 0,
+            DelayDistribution::Uniform => rng.gen_range(0..range),
+            DelayDistribution::Gaussian => {
+                let mean = range as f64 / 2.0;
+                let sigma = range as f64 / GAUSSIAN_SIGMAS_PER_RANGE;
+                (0..GAUSSIAN_MAX_DRAWS)
+                    .map(|_| mean + sigma * standard_normal(rng))
+                    .find(|offset| (0.0..range as f64).contains(offset))
+                    .map_or(range / 2, |offset| offset as u64)
+            }
+        };
+        std::time::Duration::from_millis(min_ms + random_offset)
+    }
+}
+
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
 pub fn random_delay(min_ms: u64, max_ms: u64) -> std::time::Duration {
-    let range = max_ms.saturating_sub(min_ms);
-    let random_offset = if range > 0 {
-        rand::random::<u64>() % range
-    } else {
-        0
-    };
-    std::time::Duration::from_millis(min_ms + random_offset)
+    Humanizer::current().delay(min_ms, max_ms)
 }
 
 pub fn normalize_character_name(name: &str) -> String {
@@ -77,12 +134,38 @@ pub fn normalize_series_name(series: &str) -> String {
         .join(" ")
 }
 
+pub fn top_series<'a, I: IntoIterator<Item = &'a str>>(series: I, limit: usize) -> Vec<(String, u64)> {
+    let mut counts: std::collections::HashMap<&str, u64> = std::collections::HashMap::new();
+    for name in series.into_iter().map(str::trim).filter(|s| !s.is_empty()) {
+        *counts.entry(name).or_insert(0) += 1;
+    }
+
+    let mut top: Vec<(String, u64)> = counts
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top.truncate(limit);
+    top
+}
+
+pub fn next_daily_run(last_run: Option<DateTime<Utc>>, schedule: NaiveTime, now: DateTime<Utc>) -> Option<DateTime<Local>> {
+    let last_run = last_run?;
+    let next_day = (last_run.date_naive() + chrono::Days::new(1)).and_time(NaiveTime::MIN).and_utc();
+    let start = next_day.max(now).with_timezone(&Local);
+
+    if start.time() >= schedule {
+        return (start > now).then_some(start);
+    }
+    start.date_naive().and_time(schedule).and_local_timezone(Local).earliest()
+}
+
 pub fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+    if s.chars().count() <= max_len {
+        return s.to_string();
     }
+    let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+    format!("{}...", kept)
 }
 
 pub struct RateLimiter {
@@ -168,6 +251,8 @@ impl Cooldown {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_format_duration() {
@@ -176,6 +261,12 @@ mod tests {
         assert_eq!(format_duration(Duration::seconds(3661)), "1h 1m 1s");
     }
 
+    #[test]
+    fn test_snowflake_timestamp() {
+        let ts = snowflake_timestamp(175928847299117063).unwrap();
+        assert_eq!(ts.timestamp_millis(), 1462015105796);
+    }
+
     #[test]
     fn test_parse_time() {
         assert_eq!(parse_time("12:30"), Some((12, 30)));
@@ -193,6 +284,16 @@ mod tests {
         assert_eq!(normalize_character_name("Kaguya  Shinomiya"), "kaguya shinomiya");
     }
 
+    #[test]
+    fn test_top_series() {
+        let series = ["Re:Zero", " Naruto ", "", "Re:Zero", "  ", "Bleach", "Naruto", "Re:Zero"];
+        assert_eq!(
+            top_series(series, 2),
+            vec![("Re:Zero".to_string(), 3), ("Naruto".to_string(), 2)]
+        );
+        assert!(top_series(["", " "], 5).is_empty());
+    }
+
     #[test]
     fn test_normalize_series_name() {
         assert_eq!(normalize_series_name("Re:Zero"), "re zero");
@@ -205,6 +306,16 @@ mod tests {
         assert_eq!(truncate_string("Hello World!", 8), "Hello...");
     }
 
+    #[test]
+    fn test_truncate_string_multibyte() {
+        let name = "Ōtsutsuki Kaguya 大筒木カグヤ 💖💖";
+        for max_len in 0..=name.chars().count() + 1 {
+            let truncated = truncate_string(name, max_len);
+            assert!(truncated.chars().count() <= max_len.max(3));
+        }
+        assert_eq!(truncate_string("レム・ラム姉妹", 5), "レム...");
+    }
+
     #[test]
     fn test_cooldown() {
         let mut cd = Cooldown::new(1);
@@ -212,4 +323,32 @@ mod tests {
         cd.trigger("test");
         assert!(!cd.is_ready("test"));
     }
+
+    #[test]
+    fn test_next_daily_run() {
+        let now = Utc::now();
+        let schedule = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        assert_eq!(next_daily_run(None, schedule, now), None);
+
+        let next = next_daily_run(Some(now), schedule, now).unwrap();
+        assert!(next > now);
+        assert!(next <= now + Duration::days(2));
+
+        let long_ago = now - Duration::days(3);
+        assert_eq!(next_daily_run(Some(long_ago), schedule, now), None);
+    }
+
+    #[test]
+    fn test_humanizer_distributions() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let middle_share = |humanizer: Humanizer, rng: &mut StdRng| {
+            let delays: Vec<u64> = (0..3000).map(|_| humanizer.delay_with(rng, 1000, 4000).as_millis() as u64).collect();
+            assert!(delays.iter().all(|d| (1000..4000).contains(d)));
+            delays.iter().filter(|d| (2000..3000).contains(*d)).count() as f64 / delays.len() as f64
+        };
+
+        assert!(middle_share(Humanizer::new(DelayDistribution::Uniform), &mut rng) < 0.4);
+        assert!(middle_share(Humanizer::new(DelayDistribution::Gaussian), &mut rng) > 0.6);
+        assert_eq!(Humanizer::new(DelayDistribution::Gaussian).delay(500, 500).as_millis(), 500);
+    }
 }
@@ -3,9 +3,10 @@
 use crate::client::DiscordClient;
 use crate::parser::MudaeMessage;
 use crate::wishlist::{WishlistManager, WishedCharacter};
-use anyhow::Result;
+use anyhow::{bail, Result};
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::oneshot;
 use tracing::{debug, info, warn};
@@ -19,11 +20,45 @@ pub struct VerificationResult {
     pub exists: bool,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveDelay {
+    current: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl AdaptiveDelay {
+    pub fn new(min: Duration, max: Duration) -> Self {
+        let min = min.max(Duration::from_millis(1));
+        let max = max.max(min);
+        Self { current: min, min, max }
+    }
+
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+
+    pub fn on_success(&mut self) {
+        self.current = self.current.mul_f64(0.8).max(self.min);
+    }
+
+    pub fn on_timeout(&mut self) {
+        self.current = (self.current * 2).min(self.max);
+    }
+}
+
+impl Default for AdaptiveDelay {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(1500), Duration::from_millis(15000))
+    }
+}
+
 pub struct CharacterVerifier {
     client: DiscordClient,
     cache: Arc<DashMap<String, VerificationResult>>,
     pending_verifications: Arc<DashMap<String, oneshot::Sender<VerificationResult>>>,
     verification_channel: u64,
+    delay: Mutex<AdaptiveDelay>,
 }
 
 impl CharacterVerifier {
@@ -33,9 +68,19 @@ impl CharacterVerifier {
             cache: Arc::new(DashMap::new()),
             pending_verifications: Arc::new(DashMap::new()),
             verification_channel,
+            delay: Mutex::new(AdaptiveDelay::default()),
         }
     }
 
+    pub fn with_delay_bounds(self, min: Duration, max: Duration) -> Self {
+        *self.delay.lock().unwrap() = AdaptiveDelay::new(min, max);
+        self
+    }
+
+    pub fn next_delay(&self) -> Duration {
+        self.delay.lock().unwrap().current()
+    }
+
     pub async fn verify_character(&self, name: &str) -> Result<VerificationResult> {
         let name_lower = name.to_lowercase();
         
@@ -56,6 +101,7 @@ impl CharacterVerifier {
 
         match result {
             Ok(Ok(verification)) => {
+                self.delay.lock().unwrap().on_success();
                 self.cache.insert(name_lower, verification.clone());
                 Ok(verification)
             }
@@ -71,7 +117,10 @@ impl CharacterVerifier {
                 Ok(not_found)
             }
             Err(_) => {
-                warn!("Verification timed out for '{}'", name);
+                let mut delay = self.delay.lock().unwrap();
+                delay.on_timeout();
+                warn!("Verification timed out for '{}', backing off to {:?}", name, delay.current());
+                drop(delay);
                 let not_found = VerificationResult {
                     original_name: name.to_string(),
                     canonical_name: None,
@@ -122,6 +171,7 @@ impl CharacterVerifier {
         let mut results = Vec::new();
         
         for name in names {
+            let cached = self.is_cached(&name);
             match self.verify_character(&name).await {
                 Ok(result) => results.push(result),
                 Err(e) => {
@@ -135,7 +185,9 @@ impl CharacterVerifier {
                     });
                 }
             }
-            tokio::time::sleep(Duration::from_secs(2)).await;
+            if !cached {
+                tokio::time::sleep(self.next_delay()).await;
+            }
         }
         
         results
@@ -162,50 +214,112 @@ impl CharacterVerifier {
 pub struct WishlistVerifier {
     verifier: Arc<CharacterVerifier>,
     wishlist: Arc<WishlistManager>,
+    running: AtomicBool,
+    stop: AtomicBool,
+    processed: AtomicUsize,
+    total: AtomicUsize,
 }
 
 impl WishlistVerifier {
     pub fn new(verifier: Arc<CharacterVerifier>, wishlist: Arc<WishlistManager>) -> Self {
-        Self { verifier, wishlist }
+        Self {
+            verifier,
+            wishlist,
+            running: AtomicBool::new(false),
+            stop: AtomicBool::new(false),
+            processed: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    pub fn cancel(&self) {
+        if self.is_running() {
+            self.stop.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub fn progress(&self) -> (usize, usize) {
+        (self.processed.load(Ordering::SeqCst), self.total.load(Ordering::SeqCst))
     }
 
     pub async fn verify_unverified_characters(&self) -> Result<VerificationReport> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            bail!("Verification is already running");
+        }
+        self.stop.store(false, Ordering::SeqCst);
+
+        let report = self.run_verification().await;
+        self.running.store(false, Ordering::SeqCst);
+        Ok(report)
+    }
+
+    async fn run_verification(&self) -> VerificationReport {
         let unverified = self.wishlist.get_unverified().await;
         let total = unverified.len();
         let mut verified_count = 0;
         let mut failed_count = 0;
         let mut results = Vec::new();
+        let mut cancelled = false;
 
+        self.processed.store(0, Ordering::SeqCst);
+        self.total.store(total, Ordering::SeqCst);
         info!("Starting verification of {} unverified characters", total);
 
-        for character in unverified {
-            let result = self.verifier.verify_character(&character.name).await?;
+        for (i, character) in unverified.into_iter().enumerate() {
+            if i > 0 && !self.stop.load(Ordering::SeqCst) {
+                tokio::time::sleep(self.verifier.next_delay()).await;
+            }
+
+            if self.stop.load(Ordering::SeqCst) {
+                cancelled = true;
+                info!("Verification cancelled after {} of {} characters", i, total);
+                break;
+            }
+
+            let result = match self.verifier.verify_character(&character.name).await {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!("Failed to verify '{}': {}", character.name, e);
+                    failed_count += 1;
+                    self.processed.fetch_add(1, Ordering::SeqCst);
+                    continue;
+                }
+            };
             
             if result.exists {
-                self.wishlist.update_character_verification(
+                if let Err(e) = self.wishlist.update_character_verification(
                     &character.name,
                     true,
                     result.canonical_name.clone(),
                     result.series.clone(),
                     result.character_id.clone(),
-                ).await?;
-                verified_count += 1;
-                info!("Verified: {} -> {:?}", character.name, result.canonical_name);
+                ).await {
+                    failed_count += 1;
+                    warn!("Failed to save verification for '{}': {}", character.name, e);
+                } else {
+                    verified_count += 1;
+                    info!("Verified: {} -> {:?}", character.name, result.canonical_name);
+                }
             } else {
                 failed_count += 1;
                 warn!("Character not found: {}", character.name);
             }
 
             results.push(result);
-            tokio::time::sleep(Duration::from_secs(3)).await;
+            self.processed.fetch_add(1, Ordering::SeqCst);
         }
 
-        Ok(VerificationReport {
+        VerificationReport {
             total,
             verified: verified_count,
             failed: failed_count,
             results,
-        })
+            cancelled,
+        }
     }
 
     pub async fn add_and_verify(&self, name: String, series: Option<String>) -> Result<bool> {
@@ -224,6 +338,7 @@ impl WishlistVerifier {
             added_date: chrono::Utc::now(),
             notes: None,
             priority: 0,
+            soft: false,
         };
 
         self.wishlist.add_character(character).await
@@ -238,6 +353,7 @@ impl WishlistVerifier {
             added_date: chrono::Utc::now(),
             notes: Some("Pending verification".to_string()),
             priority: 0,
+            soft: false,
         };
 
         self.wishlist.add_character(character).await
@@ -250,6 +366,7 @@ pub struct VerificationReport {
     pub verified: usize,
     pub failed: usize,
     pub results: Vec<VerificationResult>,
+    pub cancelled: bool,
 }
 
 impl VerificationReport {
@@ -286,8 +403,119 @@ mod tests {
             verified: 8,
             failed: 2,
             results: vec![],
+            cancelled: false,
         };
 
         assert_eq!(report.success_rate(), 80.0);
     }
+
+    #[tokio::test]
+    async fn test_cancel_when_idle_does_not_stop_next_run() {
+        let verifier = Arc::new(CharacterVerifier::new(DiscordClient::new("test-token".to_string()), 0));
+        let wishlist = Arc::new(WishlistManager::new("test-wishlist.json".to_string(), 0.8, true, true));
+        let wishlist_verifier = WishlistVerifier::new(verifier, wishlist);
+
+        wishlist_verifier.cancel();
+        let report = wishlist_verifier.verify_unverified_characters().await.unwrap();
+
+        assert!(!report.cancelled);
+        assert_eq!(report.total, 0);
+        assert!(!wishlist_verifier.is_running());
+        assert_eq!(wishlist_verifier.progress(), (0, 0));
+    }
+
+    fn cached_verifier(names: &[&str]) -> CharacterVerifier {
+        let verifier = CharacterVerifier::new(DiscordClient::new("test-token".to_string()), 0)
+            .with_delay_bounds(Duration::from_secs(60), Duration::from_secs(60));
+        for name in names {
+            verifier.cache.insert(name.to_lowercase(), VerificationResult {
+                original_name: name.to_string(),
+                canonical_name: Some(name.to_string()),
+                series: None,
+                character_id: None,
+                exists: true,
+            });
+        }
+        verifier
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_mid_verification_keeps_saved_progress() {
+        let path = std::env::temp_dir().join(format!("wishlist-verify-cancel-{}.json", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+        let names = ["Rem", "Ram", "Emilia"];
+
+        let wishlist = Arc::new(WishlistManager::new(path_str.clone(), 0.8, true, true));
+        for name in names {
+            wishlist.add_character(WishedCharacter::new(name.to_string())).await.unwrap();
+        }
+        let wishlist_verifier = Arc::new(WishlistVerifier::new(Arc::new(cached_verifier(&names)), wishlist.clone()));
+
+        let running = wishlist_verifier.clone();
+        let handle = tokio::spawn(async move { running.verify_unverified_characters().await });
+        while wishlist_verifier.progress().0 < 1 {
+            tokio::task::yield_now().await;
+        }
+        wishlist_verifier.cancel();
+        let report = handle.await.unwrap().unwrap();
+
+        assert!(report.cancelled);
+        assert_eq!(report.verified, 1);
+        assert_eq!(wishlist.get_characters().await.iter().filter(|c| c.verified).count(), 1);
+        assert_eq!(wishlist.get_unverified().await.len(), 2);
+        assert!(!wishlist_verifier.is_running());
+
+        for suffix in ["", ".bak"] {
+            let _ = std::fs::remove_file(format!("{}{}", path_str, suffix));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsaved_verification_is_not_counted() {
+        let path = std::env::temp_dir().join(format!("wishlist-verify-unsaved-{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let wishlist = Arc::new(WishlistManager::new(path.to_string_lossy().to_string(), 0.8, true, true));
+        let _ = wishlist.add_character(WishedCharacter::new("Rem".to_string())).await;
+        let wishlist_verifier = WishlistVerifier::new(Arc::new(cached_verifier(&["Rem"])), wishlist.clone());
+
+        let report = wishlist_verifier.verify_unverified_characters().await.unwrap();
+
+        assert_eq!(report.verified, 0);
+        assert_eq!(report.failed, 1);
+        assert_eq!(wishlist.get_unverified().await.len(), 1);
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn test_adaptive_delay_backs_off_to_max() {
+        let mut delay = AdaptiveDelay::new(Duration::from_secs(1), Duration::from_secs(5));
+        delay.on_timeout();
+        assert_eq!(delay.current(), Duration::from_secs(2));
+        delay.on_timeout();
+        delay.on_timeout();
+        assert_eq!(delay.current(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_adaptive_delay_recovers_to_min() {
+        let mut delay = AdaptiveDelay::new(Duration::from_secs(1), Duration::from_secs(5));
+        delay.on_timeout();
+        delay.on_timeout();
+        delay.on_success();
+        assert_eq!(delay.current(), Duration::from_millis(3200));
+        for _ in 0..20 {
+            delay.on_success();
+        }
+        assert_eq!(delay.current(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_adaptive_delay_backs_off_from_zero_min() {
+        let mut delay = AdaptiveDelay::new(Duration::ZERO, Duration::from_millis(100));
+        assert_eq!(delay.current(), Duration::from_millis(1));
+        delay.on_timeout();
+        assert_eq!(delay.current(), Duration::from_millis(2));
+    }
 }
@@ -9,6 +9,8 @@ use strsim::normalized_levenshtein;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+use crate::utils::normalize_character_name;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WishlistData {
     pub characters: Vec<WishedCharacter>,
@@ -26,6 +28,8 @@ pub struct WishedCharacter {
     pub notes: Option<String>,
     #[serde(default)]
     pub priority: u8,
+    #[serde(default)]
+    pub soft: bool,
 }
 
 impl Default for WishlistData {
@@ -37,12 +41,51 @@ impl Default for WishlistData {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub added: usize,
+    pub merged: usize,
+    pub near_duplicates: Vec<(String, String)>,
+}
+
+impl ImportReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Imported {} characters", self.added);
+        if self.merged > 0 {
+            summary.push_str(&format!(", merged {}", self.merged));
+        }
+        if !self.near_duplicates.is_empty() {
+            let pairs: Vec<String> = self.near_duplicates
+                .iter()
+                .map(|(imported, existing)| format!("{} ~ {}", imported, existing))
+                .collect();
+            summary.push_str(&format!("; {} near-duplicate(s): {}", pairs.len(), pairs.join(", ")));
+        }
+        summary
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportStatus {
+    New,
+    Duplicate,
+    NearDuplicate,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportEntry {
+    pub character: WishedCharacter,
+    pub status: ImportStatus,
+    pub existing: Option<String>,
+}
+
 pub struct WishlistManager {
     data: Arc<RwLock<WishlistData>>,
     file_path: String,
     fuzzy_threshold: f64,
     fuzzy_enabled: bool,
     priority_verified: bool,
+    fuzzy_dedup: bool,
 }
 
 impl WishlistManager {
@@ -53,9 +96,15 @@ impl WishlistManager {
             fuzzy_threshold,
             fuzzy_enabled,
             priority_verified,
+            fuzzy_dedup: false,
         }
     }
 
+    pub fn with_fuzzy_dedup(mut self, fuzzy_dedup: bool) -> Self {
+        self.fuzzy_dedup = fuzzy_dedup;
+        self
+    }
+
     pub async fn load(&self) -> Result<()> {
         let path = Path::new(&self.file_path);
         if !path.exists() {
@@ -64,28 +113,78 @@ impl WishlistManager {
             return Ok(());
         }
 
-        let content = tokio::fs::read_to_string(path)
-            .await
-            .context("Failed to read wishlist file")?;
-        
-        let data: WishlistData = serde_json::from_str(&content)
-            .context("Failed to parse wishlist file")?;
-        
+        let data = Self::read_data(path).await?;
         *self.data.write().await = data;
         info!("Loaded {} characters from wishlist", self.data.read().await.characters.len());
         Ok(())
     }
 
+    pub async fn load_or_recover(&self) -> bool {
+        let error = match self.load().await {
+            Ok(()) => return true,
+            Err(e) => e,
+        };
+        warn!("Failed to load wishlist: {:#}", error);
+
+        let corrupt_path = format!("{}.corrupt", self.file_path);
+        match tokio::fs::rename(&self.file_path, &corrupt_path).await {
+            Ok(()) => warn!("Moved unreadable wishlist to {}", corrupt_path),
+            Err(e) => warn!("Failed to move unreadable wishlist aside: {}", e),
+        }
+
+        let data = match Self::read_data(Path::new(&self.backup_path())).await {
+            Ok(data) => {
+                info!("Recovered {} characters from wishlist backup", data.characters.len());
+                data
+            }
+            Err(e) => {
+                warn!("No usable wishlist backup ({:#}), starting with an empty wishlist", e);
+                WishlistData::default()
+            }
+        };
+        *self.data.write().await = data;
+        false
+    }
+
+    async fn read_data(path: &Path) -> Result<WishlistData> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .context("Failed to read wishlist file")?;
+
+        serde_json::from_str(&content).context("Failed to parse wishlist file")
+    }
+
+    pub async fn read_import_file(path: &Path) -> Result<Vec<WishedCharacter>> {
+        Ok(Self::read_data(path).await?.characters)
+    }
+
+    pub async fn validate_file(path: &Path) -> Result<Option<usize>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(Self::read_data(path).await?.characters.len()))
+    }
+
+    fn backup_path(&self) -> String {
+        format!("{}.bak", self.file_path)
+    }
+
     pub async fn save(&self) -> Result<()> {
         let mut data = self.data.write().await;
         data.last_updated = Utc::now();
         
         let content = serde_json::to_string_pretty(&*data)
             .context("Failed to serialize wishlist")?;
+
+        if Path::new(&self.file_path).exists() {
+            if let Err(e) = tokio::fs::copy(&self.file_path, self.backup_path()).await {
+                warn!("Failed to back up wishlist: {}", e);
+            }
+        }
         
         tokio::fs::write(&self.file_path, content)
             .await
-            .context("Failed to write wishlist file")?;
+            .with_context(|| format!("Failed to write wishlist file {}", self.file_path))?;
         
         debug!("Saved wishlist to {}", self.file_path);
         Ok(())
@@ -103,6 +202,16 @@ impl WishlistManager {
             return Ok(false);
         }
 
+        if self.fuzzy_dedup {
+            if let Some(index) = self.find_near_duplicate(&data.characters, &character) {
+                warn!("Character '{}' looks like '{}', merged instead of adding", character.name, data.characters[index].name);
+                Self::merge_into(&mut data.characters[index], &character);
+                drop(data);
+                self.save().await?;
+                return Ok(false);
+            }
+        }
+
         character.added_date = Utc::now();
         data.characters.push(character.clone());
         drop(data);
@@ -140,28 +249,36 @@ impl WishlistManager {
     ) -> Result<bool> {
         let mut data = self.data.write().await;
         
-        let character = data.characters.iter_mut().find(|c| {
+        let Some(index) = data.characters.iter().position(|c| {
             c.name.to_lowercase() == name.to_lowercase()
-        });
+        }) else {
+            return Ok(false);
+        };
 
-        if let Some(c) = character {
-            c.verified = verified;
-            if let Some(cn) = canonical_name {
-                c.name = cn;
-            }
-            if series.is_some() {
-                c.series = series;
-            }
-            if character_id.is_some() {
-                c.character_id = character_id;
+        let previous = data.characters[index].clone();
+        let c = &mut data.characters[index];
+        c.verified = verified;
+        if let Some(cn) = canonical_name {
+            c.name = cn;
+        }
+        if series.is_some() {
+            c.series = series;
+        }
+        if character_id.is_some() {
+            c.character_id = character_id;
+        }
+        let updated_name = c.name.clone();
+        drop(data);
+
+        if let Err(e) = self.save().await {
+            let mut data = self.data.write().await;
+            if let Some(c) = data.characters.iter_mut().find(|c| c.name == updated_name) {
+                *c = previous;
             }
-            drop(data);
-            self.save().await?;
-            info!("Updated verification for '{}'", name);
-            Ok(true)
-        } else {
-            Ok(false)
+            return Err(e);
         }
+        info!("Updated verification for '{}'", name);
+        Ok(true)
     }
 
     pub async fn get_characters(&self) -> Vec<WishedCharacter> {
@@ -290,25 +407,115 @@ impl WishlistManager {
         }
     }
 
-    pub async fn import(&self, characters: Vec<WishedCharacter>) -> Result<usize> {
+    pub async fn set_soft(&self, name: &str, soft: bool) -> Result<bool> {
+        let mut data = self.data.write().await;
+        
+        let character = data.characters.iter_mut().find(|c| {
+            c.name.to_lowercase() == name.to_lowercase()
+        });
+
+        if let Some(c) = character {
+            c.soft = soft;
+            drop(data);
+            self.save().await?;
+            info!("Marked '{}' as a {} wish", name, if soft { "soft" } else { "hard" });
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn dedup_key(name: &str) -> String {
+        let base = name
+            .trim_end()
+            .strip_suffix(')')
+            .and_then(|rest| rest.rfind('(').map(|open| &rest[..open]))
+            .filter(|base| !base.trim().is_empty())
+            .unwrap_or(name);
+        normalize_character_name(base)
+    }
+
+    fn find_near_duplicate(&self, existing: &[WishedCharacter], candidate: &WishedCharacter) -> Option<usize> {
+        let key = Self::dedup_key(&candidate.name);
+        existing.iter().position(|c| {
+            if let (Some(a), Some(b)) = (&c.series, &candidate.series) {
+                if !a.eq_ignore_ascii_case(b) {
+                    return false;
+                }
+            }
+            let other = Self::dedup_key(&c.name);
+            other == key || normalized_levenshtein(&other, &key) >= self.fuzzy_threshold
+        })
+    }
+
+    fn merge_into(existing: &mut WishedCharacter, duplicate: &WishedCharacter) {
+        if existing.series.is_none() {
+            existing.series = duplicate.series.clone();
+        }
+        if existing.character_id.is_none() {
+            existing.character_id = duplicate.character_id.clone();
+        }
+        if existing.notes.is_none() {
+            existing.notes = duplicate.notes.clone();
+        }
+        existing.priority = existing.priority.max(duplicate.priority);
+    }
+
+    pub async fn preview_import(&self, characters: Vec<WishedCharacter>) -> Vec<ImportEntry> {
+        let mut known = self.data.read().await.characters.clone();
+        let mut entries = Vec::with_capacity(characters.len());
+
+        for character in characters {
+            let duplicate = known
+                .iter()
+                .find(|c| c.name.to_lowercase() == character.name.to_lowercase())
+                .map(|c| c.name.clone());
+            let (status, existing) = match duplicate {
+                Some(name) => (ImportStatus::Duplicate, Some(name)),
+                None => match self.find_near_duplicate(&known, &character) {
+                    Some(index) => (ImportStatus::NearDuplicate, Some(known[index].name.clone())),
+                    None => (ImportStatus::New, None),
+                },
+            };
+            if status == ImportStatus::New {
+                known.push(character.clone());
+            }
+            entries.push(ImportEntry { character, status, existing });
+        }
+        entries
+    }
+
+    pub async fn import(&self, characters: Vec<WishedCharacter>) -> Result<ImportReport> {
         let mut data = self.data.write().await;
-        let mut added = 0;
+        let mut report = ImportReport::default();
 
         for character in characters {
             let exists = data.characters.iter().any(|c| {
                 c.name.to_lowercase() == character.name.to_lowercase()
             });
+            if exists {
+                continue;
+            }
 
-            if !exists {
-                data.characters.push(character);
-                added += 1;
+            if let Some(index) = self.find_near_duplicate(&data.characters, &character) {
+                let existing = &mut data.characters[index];
+                warn!("Imported '{}' looks like existing '{}'", character.name, existing.name);
+                report.near_duplicates.push((character.name.clone(), existing.name.clone()));
+                if self.fuzzy_dedup {
+                    Self::merge_into(existing, &character);
+                    report.merged += 1;
+                    continue;
+                }
             }
+
+            data.characters.push(character);
+            report.added += 1;
         }
 
         drop(data);
         self.save().await?;
-        info!("Imported {} characters", added);
-        Ok(added)
+        info!("Imported {} characters ({} near-duplicates)", report.added, report.near_duplicates.len());
+        Ok(report)
     }
 
     pub async fn export(&self) -> Result<String> {
@@ -337,6 +544,7 @@ impl WishedCharacter {
             added_date: Utc::now(),
             notes: None,
             priority: 0,
+            soft: false,
         }
     }
 
@@ -354,6 +562,11 @@ impl WishedCharacter {
         self.notes = Some(notes);
         self
     }
+
+    pub fn with_soft(mut self, soft: bool) -> Self {
+        self.soft = soft;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -379,11 +592,117 @@ mod tests {
         let char = WishedCharacter::new("Rem".to_string())
             .with_series("Re:Zero".to_string())
             .with_priority(10)
-            .with_notes("Best girl".to_string());
+            .with_notes("Best girl".to_string())
+            .with_soft(true);
         
         assert_eq!(char.name, "Rem");
+        assert!(char.soft);
         assert_eq!(char.series, Some("Re:Zero".to_string()));
         assert_eq!(char.priority, 10);
         assert_eq!(char.notes, Some("Best girl".to_string()));
     }
+
+    #[test]
+    fn test_soft_defaults_to_false() {
+        let json = r#"{"name":"Rem","series":null,"character_id":null,"verified":true,"added_date":"2024-01-01T00:00:00Z"}"#;
+        let character: WishedCharacter = serde_json::from_str(json).unwrap();
+        assert!(!character.soft);
+    }
+
+    #[tokio::test]
+    async fn test_fuzzy_dedup_merges_near_duplicates() {
+        let path = std::env::temp_dir().join(format!("wishlist-dedup-{}.json", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+
+        let manager = WishlistManager::new(path_str.clone(), 0.8, true, true).with_fuzzy_dedup(true);
+        assert!(manager.add_character(WishedCharacter::new("Rem".to_string())).await.unwrap());
+        assert!(!manager.add_character(WishedCharacter::new("Rem ".to_string())).await.unwrap());
+        let merged = WishedCharacter::new("Rem (Re:Zero)".to_string()).with_series("Re:Zero".to_string()).with_priority(5);
+        assert!(!manager.add_character(merged).await.unwrap());
+        assert!(manager.add_character(WishedCharacter::new("Ram".to_string())).await.unwrap());
+
+        let characters = manager.get_characters().await;
+        assert_eq!(characters.len(), 2);
+        assert_eq!(characters[0].series.as_deref(), Some("Re:Zero"));
+        assert_eq!(characters[0].priority, 5);
+
+        let kirito = WishedCharacter::new("Kirito".to_string()).with_series("Sword Art Online".to_string());
+        let kirino = WishedCharacter::new("Kirino".to_string()).with_series("Oreimo".to_string());
+        assert!(manager.add_character(kirito).await.unwrap());
+        assert!(manager.add_character(kirino).await.unwrap());
+
+        for suffix in ["", ".bak"] {
+            let _ = std::fs::remove_file(format!("{}{}", path_str, suffix));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_near_duplicates() {
+        let path = std::env::temp_dir().join(format!("wishlist-import-{}.json", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+        let incoming = || vec![
+            WishedCharacter::new("Emilia (Re:Zero)".to_string()),
+            WishedCharacter::new("Subaru".to_string()),
+        ];
+
+        let manager = WishlistManager::new(path_str.clone(), 0.8, true, true);
+        manager.add_character(WishedCharacter::new("Emilia".to_string())).await.unwrap();
+        let report = manager.import(incoming()).await.unwrap();
+        assert_eq!(report.added, 2);
+        assert_eq!(report.near_duplicates, vec![("Emilia (Re:Zero)".to_string(), "Emilia".to_string())]);
+        assert_eq!(report.summary(), "Imported 2 characters; 1 near-duplicate(s): Emilia (Re:Zero) ~ Emilia");
+
+        let manager = WishlistManager::new(path_str.clone(), 0.8, true, true).with_fuzzy_dedup(true);
+        manager.add_character(WishedCharacter::new("Emilia".to_string())).await.unwrap();
+        let report = manager.import(incoming()).await.unwrap();
+        assert_eq!(report.added, 1);
+        assert_eq!(report.merged, 1);
+        assert_eq!(manager.count().await, 2);
+
+        for suffix in ["", ".bak"] {
+            let _ = std::fs::remove_file(format!("{}{}", path_str, suffix));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preview_import_categorises_entries() {
+        let manager = WishlistManager::new("test-wishlist-preview.json".to_string(), 0.8, true, true);
+        manager.data.write().await.characters.push(WishedCharacter::new("Emilia".to_string()));
+
+        let preview = manager.preview_import(vec![
+            WishedCharacter::new("emilia".to_string()),
+            WishedCharacter::new("Emilia (Re:Zero)".to_string()),
+            WishedCharacter::new("Subaru".to_string()),
+            WishedCharacter::new("Subaru ".to_string()),
+        ]).await;
+
+        let statuses: Vec<_> = preview.iter().map(|e| (e.status, e.existing.as_deref())).collect();
+        assert_eq!(statuses, vec![
+            (ImportStatus::Duplicate, Some("Emilia")),
+            (ImportStatus::NearDuplicate, Some("Emilia")),
+            (ImportStatus::New, None),
+            (ImportStatus::NearDuplicate, Some("Subaru")),
+        ]);
+        assert_eq!(manager.count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_or_recover_uses_backup() {
+        let path = std::env::temp_dir().join(format!("wishlist-recover-{}.json", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+
+        let manager = WishlistManager::new(path_str.clone(), 0.8, true, true);
+        manager.add_character(WishedCharacter::new("Rem".to_string())).await.unwrap();
+        manager.save().await.unwrap();
+        tokio::fs::write(&path, "{ not json").await.unwrap();
+
+        let manager = WishlistManager::new(path_str.clone(), 0.8, true, true);
+        assert!(!manager.load_or_recover().await);
+        assert_eq!(manager.count().await, 1);
+        assert!(Path::new(&format!("{}.corrupt", path_str)).exists());
+
+        for suffix in ["", ".bak", ".corrupt"] {
+            let _ = std::fs::remove_file(format!("{}{}", path_str, suffix));
+        }
+    }
 }